@@ -1,6 +1,9 @@
-use alloy_sol_types::{
-    sol_data::{Bytes, Uint},
-    SolType,
+use example_token::{
+    payload::{
+        AssetTransfer, Refund, Transfer, TransferBatch, TransferForward, MAX_BATCH_RECIPIENTS,
+        MAX_CALL_ACCOUNTS,
+    },
+    state::Escrow,
 };
 use solana_program::{instruction::AccountMeta, pubkey, pubkey::Pubkey, system_program};
 use uip_solana_sdk::{deserialize_message_data, MessageDataRef};
@@ -33,38 +36,458 @@ pub unsafe extern "C" fn get_instruction_info(
     result: &mut InstructionInfo,
 ) {
     let msg_data = core::slice::from_raw_parts(msg_data_ptr, msg_data_len);
-    let MessageDataRef { payload, .. } = deserialize_message_data(msg_data).unwrap();
+    let MessageDataRef {
+        payload,
+        src_chain_id,
+        msg_hash,
+        ..
+    } = deserialize_message_data(msg_data).unwrap();
 
-    let (_, to, _) = <(Bytes, Bytes, Uint<256>)>::abi_decode_params(payload, true).unwrap();
+    if let Some(batch) = TransferBatch::decode(payload) {
+        batch_instruction_info(batch, src_chain_id, msg_hash, result);
+        return;
+    }
 
-    let to = (&to as &[u8]).try_into().unwrap();
+    if let Some(transfer) = AssetTransfer::decode(payload) {
+        asset_instruction_info(transfer, src_chain_id, msg_hash, result);
+        return;
+    }
 
+    if let Some(transfer) = TransferForward::decode(payload) {
+        forward_instruction_info(transfer, src_chain_id, msg_hash, result);
+        return;
+    }
+
+    if let Some(refund) = Refund::decode(payload) {
+        refund_instruction_info(refund, src_chain_id, msg_hash, result);
+        return;
+    }
+
+    let transfer = Transfer::decode(payload).unwrap();
+
+    let to: Option<Pubkey> = (&transfer.to as &[u8]).try_into().ok();
+
+    let (config_pda, _) =
+        Pubkey::find_program_address(&[b"CONFIG"], &example_token::ID.to_bytes().into());
+    result.accounts[0] = AccountMeta::new(config_pda, false);
+
+    let (origin_pda, _) = Pubkey::find_program_address(
+        &[b"ORIGIN", &src_chain_id.to_le_bytes()],
+        &example_token::ID.to_bytes().into(),
+    );
+    result.accounts[1] = AccountMeta::new(origin_pda, false);
+
+    let (queued_pda, _) =
+        Pubkey::find_program_address(&[b"QUEUED", msg_hash], &example_token::ID.to_bytes().into());
+    result.accounts[2] = AccountMeta::new(queued_pda, false);
+
+    let (escrow_pda, _) =
+        Pubkey::find_program_address(&[b"ESCROW", msg_hash], &example_token::ID.to_bytes().into());
+    result.accounts[3] = AccountMeta::new(escrow_pda, false);
+
+    let (forward_pda, _) = Pubkey::find_program_address(
+        &[b"FORWARD", msg_hash],
+        &example_token::ID.to_bytes().into(),
+    );
+    result.accounts[4] = AccountMeta::new(forward_pda, false);
+
+    let (receipt_pda, _) = Pubkey::find_program_address(
+        &[b"RECEIPT", msg_hash],
+        &example_token::ID.to_bytes().into(),
+    );
+    result.accounts[5] = AccountMeta::new(receipt_pda, false);
+
+    let mint = match LOCKED_MINT {
+        Some(mint) => mint,
+        None => {
+            Pubkey::find_program_address(&[b"EXA_MINT"], &example_token::ID.to_bytes().into()).0
+        }
+    };
+    result.accounts[6] = AccountMeta::new(mint, false);
+
+    // The vault is an optional account, which is substituted by the program id
+    // if absent.
+    result.accounts[7] = match LOCKED_MINT {
+        Some(mint) => AccountMeta::new(find_ata(&config_pda, &mint), false),
+        None => AccountMeta::new_readonly(example_token::ID.to_bytes().into(), false),
+    };
+
+    let (escrow_authority, _) =
+        Pubkey::find_program_address(&[b"ESCROW"], &example_token::ID.to_bytes().into());
+    result.accounts[8] = AccountMeta::new_readonly(escrow_authority, false);
+    result.accounts[9] = AccountMeta::new(find_ata(&escrow_authority, &mint), false);
+
+    // The tokens of a transfer with a call go to the `CALLER` PDA of the source
+    // chain and sender, which signs the call.
+    let caller = transfer.call.as_ref().map(|_| {
+        Pubkey::find_program_address(
+            &[b"CALLER", &src_chain_id.to_le_bytes(), &transfer.from],
+            &example_token::ID.to_bytes().into(),
+        )
+        .0
+    });
+
+    // A malformed recipient gets the tokens escrowed, so the program id stands
+    // in for its accounts.
+    match caller.or(to) {
+        Some(owner) => {
+            result.accounts[10] = AccountMeta::new(find_ata(&owner, &mint), false);
+            result.accounts[11] = AccountMeta::new_readonly(owner, false);
+        }
+        None => {
+            result.accounts[10] =
+                AccountMeta::new_readonly(example_token::ID.to_bytes().into(), false);
+            result.accounts[11] =
+                AccountMeta::new_readonly(example_token::ID.to_bytes().into(), false);
+        }
+    }
+    result.accounts[12] = AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false);
+    result.accounts[13] = AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false);
+    result.accounts[14] = AccountMeta::new_readonly(system_program::ID, false);
+
+    let (blocklist_pda, _) =
+        Pubkey::find_program_address(&[b"BLOCKLIST"], &example_token::ID.to_bytes().into());
+    result.accounts[15] = AccountMeta::new_readonly(blocklist_pda, false);
+    result.accounts[16] = attestation_meta(to);
+
+    result.accounts_len = 17;
+    result.compute_units = 50_000;
+
+    // The called program is followed by the caller PDA and the call accounts.
+    if let (Some(call), Some(caller)) = (transfer.call, caller) {
+        result.accounts[17] = AccountMeta::new_readonly(call.program_id.into(), false);
+        result.accounts[18] = AccountMeta::new_readonly(caller, false);
+        result.accounts_len = (CALL_ACCOUNTS_START + call.accounts.len()) as u32;
+        for (i, (key, is_writable)) in call.accounts.into_iter().enumerate() {
+            result.accounts[CALL_ACCOUNTS_START + i] = match is_writable {
+                true => AccountMeta::new(key.into(), false),
+                false => AccountMeta::new_readonly(key.into(), false),
+            };
+        }
+        result.compute_units += CALL_COMPUTE_UNITS;
+    }
+    result.heap_frame = 0;
+}
+
+/// Populates `result` for a batch transfer, whose fixed accounts are followed
+/// by the escrow, the queued transfer, the token account, the recipient and its
+/// attestation for each transfer.
+fn batch_instruction_info(
+    batch: TransferBatch,
+    src_chain_id: u128,
+    msg_hash: &[u8; 32],
+    result: &mut InstructionInfo,
+) {
     let (config_pda, _) =
         Pubkey::find_program_address(&[b"CONFIG"], &example_token::ID.to_bytes().into());
+    result.accounts[0] = AccountMeta::new(config_pda, false);
+
+    let (origin_pda, _) = Pubkey::find_program_address(
+        &[b"ORIGIN", &src_chain_id.to_le_bytes()],
+        &example_token::ID.to_bytes().into(),
+    );
+    result.accounts[1] = AccountMeta::new(origin_pda, false);
+
+    let (receipt_pda, _) = Pubkey::find_program_address(
+        &[b"RECEIPT", msg_hash],
+        &example_token::ID.to_bytes().into(),
+    );
+    result.accounts[2] = AccountMeta::new(receipt_pda, false);
+
+    let mint = match LOCKED_MINT {
+        Some(mint) => mint,
+        None => {
+            Pubkey::find_program_address(&[b"EXA_MINT"], &example_token::ID.to_bytes().into()).0
+        }
+    };
+    result.accounts[3] = AccountMeta::new(mint, false);
+    result.accounts[4] = match LOCKED_MINT {
+        Some(mint) => AccountMeta::new(find_ata(&config_pda, &mint), false),
+        None => AccountMeta::new_readonly(example_token::ID.to_bytes().into(), false),
+    };
+
+    let (escrow_authority, _) =
+        Pubkey::find_program_address(&[b"ESCROW"], &example_token::ID.to_bytes().into());
+    result.accounts[5] = AccountMeta::new_readonly(escrow_authority, false);
+    result.accounts[6] = AccountMeta::new(find_ata(&escrow_authority, &mint), false);
+    result.accounts[7] = AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false);
+    result.accounts[8] = AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false);
+    result.accounts[9] = AccountMeta::new_readonly(system_program::ID, false);
+
+    let (blocklist_pda, _) =
+        Pubkey::find_program_address(&[b"BLOCKLIST"], &example_token::ID.to_bytes().into());
+    result.accounts[10] = AccountMeta::new_readonly(blocklist_pda, false);
+
+    for (i, (to, _)) in batch.transfers.iter().enumerate() {
+        let start = BATCH_RECIPIENTS_START + BATCH_RECIPIENT_ACCOUNTS * i;
+        let key = Escrow::batch_key(msg_hash, i as u8);
+        let (escrow_pda, _) =
+            Pubkey::find_program_address(&[b"ESCROW", &key], &example_token::ID.to_bytes().into());
+        result.accounts[start] = AccountMeta::new(escrow_pda, false);
+
+        let (queued_pda, _) =
+            Pubkey::find_program_address(&[b"QUEUED", &key], &example_token::ID.to_bytes().into());
+        result.accounts[start + 1] = AccountMeta::new(queued_pda, false);
+
+        let to = Pubkey::try_from(&to[..]).ok();
+        match to {
+            Some(to) => {
+                result.accounts[start + 2] = AccountMeta::new(find_ata(&to, &mint), false);
+                result.accounts[start + 3] = AccountMeta::new_readonly(to, false);
+            }
+            None => {
+                result.accounts[start + 2] =
+                    AccountMeta::new_readonly(example_token::ID.to_bytes().into(), false);
+                result.accounts[start + 3] =
+                    AccountMeta::new_readonly(example_token::ID.to_bytes().into(), false);
+            }
+        }
+        result.accounts[start + 4] = attestation_meta(to);
+    }
+
+    result.accounts_len =
+        (BATCH_RECIPIENTS_START + BATCH_RECIPIENT_ACCOUNTS * batch.transfers.len()) as u32;
+    result.compute_units = 50_000 + BATCH_RECIPIENT_COMPUTE_UNITS * batch.transfers.len() as u32;
+    result.heap_frame = 0;
+}
+
+/// Populates `result` for a transfer of a registered asset.
+fn asset_instruction_info(
+    transfer: AssetTransfer,
+    src_chain_id: u128,
+    msg_hash: &[u8; 32],
+    result: &mut InstructionInfo,
+) {
+    let program_id: Pubkey = example_token::ID.to_bytes().into();
+    let asset_id = transfer.asset_id.to_le_bytes();
+    let to: Option<Pubkey> = (&transfer.to as &[u8]).try_into().ok();
+
+    let (config_pda, _) = Pubkey::find_program_address(&[b"CONFIG"], &program_id);
     result.accounts[0] = AccountMeta::new_readonly(config_pda, false);
 
-    let (mint_pda, _) =
-        Pubkey::find_program_address(&[b"EXA_MINT"], &example_token::ID.to_bytes().into());
-    result.accounts[1] = AccountMeta::new(mint_pda, false);
-    result.accounts[2] = AccountMeta::new(find_ata(&to, &mint_pda), false);
-    result.accounts[3] = AccountMeta::new_readonly(to, false);
-    result.accounts[4] = AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false);
-    result.accounts[5] = AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false);
-    result.accounts[6] = AccountMeta::new_readonly(system_program::ID, false);
+    let (asset_pda, _) = Pubkey::find_program_address(&[b"ASSET", &asset_id], &program_id);
+    result.accounts[1] = AccountMeta::new(asset_pda, false);
+
+    let (origin_pda, _) = Pubkey::find_program_address(
+        &[b"ORIGIN", &src_chain_id.to_le_bytes(), &asset_id],
+        &program_id,
+    );
+    result.accounts[2] = AccountMeta::new(origin_pda, false);
+
+    let (queued_pda, _) = Pubkey::find_program_address(&[b"QUEUED", msg_hash], &program_id);
+    result.accounts[3] = AccountMeta::new(queued_pda, false);
+
+    let (escrow_pda, _) = Pubkey::find_program_address(&[b"ESCROW", msg_hash], &program_id);
+    result.accounts[4] = AccountMeta::new(escrow_pda, false);
 
-    result.accounts_len = 7;
+    let (receipt_pda, _) = Pubkey::find_program_address(&[b"RECEIPT", msg_hash], &program_id);
+    result.accounts[5] = AccountMeta::new(receipt_pda, false);
+
+    // The mint of an asset bridged in the lock/release mode is carried in the
+    // payload, while assets created by the program are always SPL token mints.
+    let locked = transfer.mint.map(|mint| {
+        let token_program = match transfer.token_2022 {
+            true => TOKEN_2022_PROGRAM_ID,
+            false => SPL_TOKEN_PROGRAM_ID,
+        };
+        (Pubkey::from(mint), token_program)
+    });
+    let (mint, token_program) = match locked {
+        Some(locked) => locked,
+        None => (
+            Pubkey::find_program_address(&[b"ASSET_MINT", &asset_id], &program_id).0,
+            SPL_TOKEN_PROGRAM_ID,
+        ),
+    };
+    result.accounts[6] = AccountMeta::new(mint, false);
+    result.accounts[7] = match locked {
+        Some(_) => AccountMeta::new(find_ata_of(&asset_pda, &mint, &token_program), false),
+        None => AccountMeta::new_readonly(program_id, false),
+    };
+
+    let (escrow_authority, _) = Pubkey::find_program_address(&[b"ESCROW"], &program_id);
+    result.accounts[8] = AccountMeta::new_readonly(escrow_authority, false);
+    result.accounts[9] =
+        AccountMeta::new(find_ata_of(&escrow_authority, &mint, &token_program), false);
+
+    match to {
+        Some(to) => {
+            result.accounts[10] = AccountMeta::new(find_ata_of(&to, &mint, &token_program), false);
+            result.accounts[11] = AccountMeta::new_readonly(to, false);
+        }
+        None => {
+            result.accounts[10] = AccountMeta::new_readonly(program_id, false);
+            result.accounts[11] = AccountMeta::new_readonly(program_id, false);
+        }
+    }
+    result.accounts[12] = AccountMeta::new_readonly(token_program, false);
+    result.accounts[13] = AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false);
+    result.accounts[14] = AccountMeta::new_readonly(system_program::ID, false);
+
+    let (blocklist_pda, _) = Pubkey::find_program_address(&[b"BLOCKLIST"], &program_id);
+    result.accounts[15] = AccountMeta::new_readonly(blocklist_pda, false);
+    result.accounts[16] = attestation_meta(to);
+
+    result.accounts_len = 17;
+    result.compute_units = 50_000;
+    result.heap_frame = 0;
+}
+
+/// Populates `result` for a transfer routed through Solana, which is only
+/// recorded to be forwarded.
+fn forward_instruction_info(
+    transfer: TransferForward,
+    src_chain_id: u128,
+    msg_hash: &[u8; 32],
+    result: &mut InstructionInfo,
+) {
+    let program_id: Pubkey = example_token::ID.to_bytes().into();
+
+    let (config_pda, _) = Pubkey::find_program_address(&[b"CONFIG"], &program_id);
+    result.accounts[0] = AccountMeta::new_readonly(config_pda, false);
+
+    let (origin_pda, _) =
+        Pubkey::find_program_address(&[b"ORIGIN", &src_chain_id.to_le_bytes()], &program_id);
+    result.accounts[1] = AccountMeta::new(origin_pda, false);
+
+    let (dest_origin_pda, _) = Pubkey::find_program_address(
+        &[b"ORIGIN", &transfer.dest_chain_id.to_le_bytes()],
+        &program_id,
+    );
+    result.accounts[2] = AccountMeta::new_readonly(dest_origin_pda, false);
+
+    let (forward_pda, _) = Pubkey::find_program_address(&[b"FORWARD", msg_hash], &program_id);
+    result.accounts[3] = AccountMeta::new(forward_pda, false);
+
+    let (receipt_pda, _) = Pubkey::find_program_address(&[b"RECEIPT", msg_hash], &program_id);
+    result.accounts[4] = AccountMeta::new(receipt_pda, false);
+    result.accounts[5] = AccountMeta::new_readonly(system_program::ID, false);
+
+    result.accounts_len = 6;
     result.compute_units = 30_000;
     result.heap_frame = 0;
 }
 
-const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+/// Populates `result` for a refund of a transfer that could not be delivered on
+/// the destination chain, which is returned to its sender.
+fn refund_instruction_info(
+    refund: Refund,
+    src_chain_id: u128,
+    msg_hash: &[u8; 32],
+    result: &mut InstructionInfo,
+) {
+    let program_id: Pubkey = example_token::ID.to_bytes().into();
+    // Only a valid sender can match the outbound transfer, so the program id
+    // stands in for a malformed one.
+    let sender = Pubkey::try_from(&refund.from[..]).unwrap_or(program_id);
+
+    let (config_pda, _) = Pubkey::find_program_address(&[b"CONFIG"], &program_id);
+    result.accounts[0] = AccountMeta::new(config_pda, false);
+
+    let (origin_pda, _) =
+        Pubkey::find_program_address(&[b"ORIGIN", &src_chain_id.to_le_bytes()], &program_id);
+    result.accounts[1] = AccountMeta::new(origin_pda, false);
+
+    let (outbound_pda, _) =
+        Pubkey::find_program_address(&[b"OUTBOUND", &refund.nonce.to_le_bytes()], &program_id);
+    result.accounts[2] = AccountMeta::new(outbound_pda, false);
+
+    let (receipt_pda, _) = Pubkey::find_program_address(&[b"RECEIPT", msg_hash], &program_id);
+    result.accounts[3] = AccountMeta::new(receipt_pda, false);
+
+    let mint = match LOCKED_MINT {
+        Some(mint) => mint,
+        None => Pubkey::find_program_address(&[b"EXA_MINT"], &program_id).0,
+    };
+    result.accounts[4] = AccountMeta::new(mint, false);
+    result.accounts[5] = match LOCKED_MINT {
+        Some(mint) => AccountMeta::new(find_ata(&config_pda, &mint), false),
+        None => AccountMeta::new_readonly(program_id, false),
+    };
+    result.accounts[6] = AccountMeta::new(find_ata(&sender, &mint), false);
+    result.accounts[7] = AccountMeta::new(sender, false);
+    result.accounts[8] = AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false);
+    result.accounts[9] = AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false);
+    result.accounts[10] = AccountMeta::new_readonly(system_program::ID, false);
+
+    result.accounts_len = 11;
+    result.compute_units = 50_000;
+    result.heap_frame = 0;
+}
+
+/// Returns the attestation PDA of the recipient, or the program id in place of
+/// it if the recipient is malformed.
+fn attestation_meta(to: Option<Pubkey>) -> AccountMeta {
+    let key = match to {
+        Some(to) => {
+            Pubkey::find_program_address(
+                &[b"ATTESTATION", to.as_ref()],
+                &example_token::ID.to_bytes().into(),
+            )
+            .0
+        }
+        None => example_token::ID.to_bytes().into(),
+    };
+    AccountMeta::new_readonly(key, false)
+}
+
+/// Compute units reserved for each recipient of a batch transfer.
+const BATCH_RECIPIENT_COMPUTE_UNITS: u32 = 40_000;
+
+/// Index of the first account of the recipients of a batch transfer.
+const BATCH_RECIPIENTS_START: usize = 11;
+
+/// Number of accounts passed for each recipient of a batch transfer.
+const BATCH_RECIPIENT_ACCOUNTS: usize = 5;
+
+// The accounts of all recipients have to fit into the accounts of the
+// instruction info.
+const _: () =
+    assert!(BATCH_RECIPIENTS_START + BATCH_RECIPIENT_ACCOUNTS * MAX_BATCH_RECIPIENTS <= 32);
+
+/// Compute units reserved for the program called on delivery.
+const CALL_COMPUTE_UNITS: u32 = 200_000;
+
+/// Index of the first account passed to the program called on delivery.
+const CALL_ACCOUNTS_START: usize = 19;
+
+// All call accounts have to fit into the accounts of the instruction info.
+const _: () = assert!(CALL_ACCOUNTS_START + MAX_CALL_ACCOUNTS <= 32);
+
+/// The existing mint bridged in the lock/release mode, set with the
+/// `EXA_LOCKED_MINT` environment variable at build time, as the extension
+/// can't read the config. Unlike asset transfers, EXA transfers keep the
+/// format shared with other chains, so they can't carry the mint. It must match
+/// the mint passed to `initialize_lock_release`.
+const LOCKED_MINT: Option<Pubkey> = match option_env!("EXA_LOCKED_MINT") {
+    Some(mint) => Some(Pubkey::from_str_const(mint)),
+    None => None,
+};
+
+/// The token program owning the bridged mint, which is Token-2022 if the mint
+/// was initialized with its extensions or is a Token-2022 mint. The extension
+/// can't read the mint owner, so the `token-2022` feature must match the mint.
+#[cfg(not(feature = "token-2022"))]
+const TOKEN_PROGRAM_ID: Pubkey = SPL_TOKEN_PROGRAM_ID;
+#[cfg(feature = "token-2022")]
+const TOKEN_PROGRAM_ID: Pubkey = TOKEN_2022_PROGRAM_ID;
+const SPL_TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
 
 fn find_ata(wallet_address: &Pubkey, token_mint_address: &Pubkey) -> Pubkey {
+    find_ata_of(wallet_address, token_mint_address, &TOKEN_PROGRAM_ID)
+}
+
+fn find_ata_of(
+    wallet_address: &Pubkey,
+    token_mint_address: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Pubkey {
     Pubkey::find_program_address(
         &[
             &wallet_address.to_bytes(),
-            &TOKEN_PROGRAM_ID.to_bytes(),
+            &token_program_id.to_bytes(),
             &token_mint_address.to_bytes(),
         ],
         &ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -178,6 +178,65 @@ export async function executeFull(
   return { transactionSignature, message };
 }
 
+// loads, signs and executes in separate transactions, for payloads too large
+// to fit into one transaction with the execution
+export async function executeInSteps(
+  {
+    executor,
+    msgData,
+    signatures,
+    superSignatures,
+    accounts,
+    spendingLimit,
+    computeUnits,
+  }: ExecuteFullInput,
+): Promise<{ transactionSignature: TransactionSignature; message: PublicKey }> {
+  const message = findMessage(msgData);
+
+  await UIP_PROGRAM.methods
+    .loadMessage(msgData)
+    .accountsStrict({
+      executor: executor.publicKey,
+      endpointConfig: ENDPOINT_CONFIG,
+      message,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([executor])
+    .rpc();
+
+  await UIP_PROGRAM.methods
+    .checkConsensus(signatures, superSignatures)
+    .accounts({
+      endpointConfig: ENDPOINT_CONFIG,
+      message,
+      executor: executor.publicKey,
+    })
+    .signers([executor])
+    .rpc();
+
+  const preInstructions = new Array<TransactionInstruction>();
+  if (computeUnits != undefined) {
+    preInstructions.push(ComputeBudgetProgram.setComputeUnitLimit({
+      units: computeUnits,
+    }));
+  }
+
+  const transactionSignature = await UIP_PROGRAM.methods
+    .execute(spendingLimit)
+    .accounts({
+      endpointConfig: ENDPOINT_CONFIG,
+      message,
+      executor: executor.publicKey,
+      dstProgram: msgData.initialProposal.destAddr,
+    })
+    .preInstructions(preInstructions)
+    .remainingAccounts(accounts)
+    .signers([executor])
+    .rpc();
+
+  return { transactionSignature, message };
+}
+
 export type UnloadMessageInput = {
   payer: Keypair;
   message: PublicKey;
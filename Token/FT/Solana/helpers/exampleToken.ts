@@ -1,18 +1,29 @@
 import { IdlTypes, Program } from "@coral-xyz/anchor";
 import * as anchor from "@coral-xyz/anchor";
-import { Keypair, PublicKey, TransactionSignature } from "@solana/web3.js";
+import {
+  AccountMeta,
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  TransactionSignature,
+} from "@solana/web3.js";
 import { ExampleToken } from "../target/types/example_token";
 import BN from "bn.js";
 import { ENDPOINT_CONFIG, fetchUtsConnector, findExtension } from "./endpoint";
+import { SOLANA_CHAIN_ID } from "./utils";
 import { CID } from "multiformats";
-import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { createHash, createPrivateKey, sign } from "crypto";
+import {
+  approve,
+  getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 
 anchor.setProvider(anchor.AnchorProvider.env());
 export const EXAMPLE_TOKEN_PROGRAM: Program<ExampleToken> =
   anchor.workspace.ExampleToken;
 
-export type Destination = IdlTypes<ExampleToken>["destination"];
-
 export const EXAMPLE_TOKEN_CONFIG = PublicKey.findProgramAddressSync(
   [Buffer.from("CONFIG")],
   EXAMPLE_TOKEN_PROGRAM.programId,
@@ -23,10 +34,249 @@ export const EXA_MINT = PublicKey.findProgramAddressSync(
   EXAMPLE_TOKEN_PROGRAM.programId,
 )[0];
 
+export const TREASURY_AUTHORITY = PublicKey.findProgramAddressSync(
+  [Buffer.from("TREASURY")],
+  EXAMPLE_TOKEN_PROGRAM.programId,
+)[0];
+
+export const ESCROW_AUTHORITY = PublicKey.findProgramAddressSync(
+  [Buffer.from("ESCROW")],
+  EXAMPLE_TOKEN_PROGRAM.programId,
+)[0];
+
+export const PERMIT_AUTHORITY = PublicKey.findProgramAddressSync(
+  [Buffer.from("PERMIT")],
+  EXAMPLE_TOKEN_PROGRAM.programId,
+)[0];
+
+export const ROLES = PublicKey.findProgramAddressSync(
+  [Buffer.from("ROLES")],
+  EXAMPLE_TOKEN_PROGRAM.programId,
+)[0];
+
+export const BLOCKLIST = PublicKey.findProgramAddressSync(
+  [Buffer.from("BLOCKLIST")],
+  EXAMPLE_TOKEN_PROGRAM.programId,
+)[0];
+
+export const FORWARD_FEE_VAULT = PublicKey.findProgramAddressSync(
+  [Buffer.from("FORWARD_FEE_VAULT")],
+  EXAMPLE_TOKEN_PROGRAM.programId,
+)[0];
+
+export const findOrigin = (chainId: BN) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("ORIGIN"), chainId.toArrayLike(Buffer, "le", 16)],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findCaller = (chainId: BN, from: Buffer) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("CALLER"), chainId.toArrayLike(Buffer, "le", 16), from],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findUserUsage = (chainId: BN, user: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("USER_USAGE"),
+      chainId.toArrayLike(Buffer, "le", 16),
+      user.toBuffer(),
+    ],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+const assetIdSeed = (assetId: BN) => assetId.toArrayLike(Buffer, "le", 8);
+
+export const findAsset = (assetId: BN) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("ASSET"), assetIdSeed(assetId)],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findAssetMint = (assetId: BN) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("ASSET_MINT"), assetIdSeed(assetId)],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findAssetOrigin = (chainId: BN, assetId: BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("ORIGIN"),
+      chainId.toArrayLike(Buffer, "le", 16),
+      assetIdSeed(assetId),
+    ],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findAssetUserUsage = (
+  chainId: BN,
+  user: PublicKey,
+  assetId: BN,
+) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("USER_USAGE"),
+      chainId.toArrayLike(Buffer, "le", 16),
+      user.toBuffer(),
+      assetIdSeed(assetId),
+    ],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findQueuedTransfer = (msgHash: Buffer) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("QUEUED"), msgHash],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findPendingForward = (msgHash: Buffer) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("FORWARD"), msgHash],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findOutboundTransfer = (nonce: BN) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("OUTBOUND"), nonce.toArrayLike(Buffer, "le", 8)],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+/** Returns the record of the next outgoing transfer to a refundable origin. */
+export async function fetchNextOutboundTransfer(): Promise<PublicKey> {
+  const config = await EXAMPLE_TOKEN_PROGRAM.account.exampleTokenConfig.fetch(
+    EXAMPLE_TOKEN_CONFIG,
+  );
+  return findOutboundTransfer(config.nextTransferNonce);
+}
+
+/** Returns the token program owning the EXA mint. */
+export async function fetchExaTokenProgram(): Promise<PublicKey> {
+  const mint = await EXAMPLE_TOKEN_PROGRAM.provider.connection.getAccountInfo(
+    EXA_MINT,
+  );
+  return mint.owner;
+}
+
+export const findExaAta = async (owner: PublicKey) =>
+  getAssociatedTokenAddressSync(
+    EXA_MINT,
+    owner,
+    true,
+    await fetchExaTokenProgram(),
+  );
+
+export type BridgedMint = {
+  mint: PublicKey;
+  tokenProgram: PublicKey;
+  vault: PublicKey | null;
+};
+
+/** Returns the bridged mint, its token program and the vault, if any. */
+export async function fetchBridgedMint(): Promise<BridgedMint> {
+  const config = await EXAMPLE_TOKEN_PROGRAM.account.exampleTokenConfig.fetch(
+    EXAMPLE_TOKEN_CONFIG,
+  );
+  const { owner: tokenProgram } = await EXAMPLE_TOKEN_PROGRAM.provider
+    .connection.getAccountInfo(config.mint);
+  const vault = config.lockRelease
+    ? getAssociatedTokenAddressSync(
+      config.mint,
+      EXAMPLE_TOKEN_CONFIG,
+      true,
+      tokenProgram,
+    )
+    : null;
+  return { mint: config.mint, tokenProgram, vault };
+}
+
+/** Returns the mint of a registered asset, its token program and the vault,
+ * if any. */
+export async function fetchAssetMint(assetId: BN): Promise<BridgedMint> {
+  const asset = await EXAMPLE_TOKEN_PROGRAM.account.asset.fetch(
+    findAsset(assetId),
+  );
+  const { owner: tokenProgram } = await EXAMPLE_TOKEN_PROGRAM.provider
+    .connection.getAccountInfo(asset.mint);
+  const vault = asset.lockRelease
+    ? getAssociatedTokenAddressSync(
+      asset.mint,
+      findAsset(assetId),
+      true,
+      tokenProgram,
+    )
+    : null;
+  return { mint: asset.mint, tokenProgram, vault };
+}
+
+/** Returns the mint of the escrowed tokens and its token program. */
+async function fetchEscrowMint(
+  msgHash: Buffer,
+): Promise<{ escrow: Escrow; mint: PublicKey; tokenProgram: PublicKey }> {
+  const escrow = await EXAMPLE_TOKEN_PROGRAM.account.escrow.fetch(
+    findEscrow(msgHash),
+  );
+  const { owner: tokenProgram } = await EXAMPLE_TOKEN_PROGRAM.provider
+    .connection.getAccountInfo(escrow.mint);
+  return { escrow, mint: escrow.mint, tokenProgram };
+}
+
+type Escrow = Awaited<
+  ReturnType<typeof EXAMPLE_TOKEN_PROGRAM.account.escrow.fetch>
+>;
+
+export type Token2022Extensions = IdlTypes<ExampleToken>["token2022Extensions"];
+
+export type Metadata = IdlTypes<ExampleToken>["metadata"];
+
+export const METAPLEX_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+);
+
+export const findMetaplexMetadata = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), METAPLEX_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    METAPLEX_PROGRAM_ID,
+  )[0];
+
+export const findEscrow = (msgHash: Buffer) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("ESCROW"), msgHash],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findPermitNonce = (owner: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("PERMIT_NONCE"), owner.toBuffer()],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+export const findAttestation = (recipient: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("ATTESTATION"), recipient.toBuffer()],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
+/** Key replacing the message hash in the escrow seeds of a batch recipient. */
+export const batchEscrowKey = (msgHash: Buffer, index: number) =>
+  createHash("sha256").update(msgHash).update(Buffer.from([index])).digest();
+
+export const findReceipt = (msgHash: Buffer) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("RECEIPT"), msgHash],
+    EXAMPLE_TOKEN_PROGRAM.programId,
+  )[0];
+
 export type InitializeInput = {
   payer: Keypair;
   admin: PublicKey;
   decimals: number;
+  token2022?: Token2022Extensions;
+  metadata?: Metadata;
+  freezable?: boolean;
+  /** Chain id of the Solana cluster, bound into permits. */
+  chainId?: BN;
 };
 
 export async function initialize(
@@ -34,12 +284,62 @@ export async function initialize(
     payer,
     admin,
     decimals,
+    token2022,
+    metadata,
+    freezable,
+    chainId,
   }: InitializeInput,
 ): Promise<{ transactionSignature: TransactionSignature }> {
+  const withMetaplex = metadata && !token2022;
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .initialize(
+      admin,
+      decimals,
+      token2022 ?? null,
+      metadata ?? null,
+      freezable ?? false,
+      chainId ?? SOLANA_CHAIN_ID,
+    )
+    .accounts({
+      payer: payer.publicKey,
+      tokenProgram: token2022 ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID,
+      metaplexMetadata: withMetaplex ? findMetaplexMetadata(EXA_MINT) : null,
+      metaplexProgram: withMetaplex ? METAPLEX_PROGRAM_ID : null,
+    })
+    .signers([payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type InitializeLockReleaseInput = {
+  payer: Keypair;
+  admin: PublicKey;
+  mint: PublicKey;
+  chainId?: BN;
+};
+
+export async function initializeLockRelease(
+  {
+    payer,
+    admin,
+    mint,
+    chainId,
+  }: InitializeLockReleaseInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { owner: tokenProgram } = await EXAMPLE_TOKEN_PROGRAM.provider
+    .connection.getAccountInfo(mint);
   const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
-    .initialize(admin, decimals)
+    .initializeLockRelease(admin, chainId ?? SOLANA_CHAIN_ID)
     .accounts({
+      mint,
+      vault: getAssociatedTokenAddressSync(
+        mint,
+        EXAMPLE_TOKEN_CONFIG,
+        true,
+        tokenProgram,
+      ),
       payer: payer.publicKey,
+      tokenProgram,
     })
     .signers([payer])
     .rpc();
@@ -47,14 +347,14 @@ export async function initialize(
 }
 
 export type MintInput = {
-  admin: Keypair;
+  authority: Keypair;
   amount: BN;
   owner: PublicKey;
 };
 
 export async function mint(
   {
-    admin,
+    authority,
     amount,
     owner,
   }: MintInput,
@@ -62,23 +362,24 @@ export async function mint(
   const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
     .mint(amount)
     .accounts({
-      admin: admin.publicKey,
-      destinationAta: getAssociatedTokenAddressSync(EXA_MINT, owner),
+      authority: authority.publicKey,
+      destinationAta: await findExaAta(owner),
+      tokenProgram: await fetchExaTokenProgram(),
     })
-    .signers([admin])
+    .signers([authority])
     .rpc();
   return { transactionSignature };
 }
 
 export type RegisterExtensionInput = {
-  admin: Keypair;
+  authority: Keypair;
   payer: Keypair;
   ipfsCid: string;
 };
 
 export async function registerExtension(
   {
-    admin,
+    authority,
     payer,
     ipfsCid,
   }: RegisterExtensionInput,
@@ -89,20 +390,37 @@ export async function registerExtension(
       config: EXAMPLE_TOKEN_CONFIG,
       payer: payer.publicKey,
       extension: findExtension(EXAMPLE_TOKEN_PROGRAM.programId),
-      admin: admin.publicKey,
+      authority: authority.publicKey,
     })
-    .signers([payer, admin])
+    .signers([payer, authority])
     .rpc();
   return { transactionSignature };
 }
 
+export type ProposalCommitment = IdlTypes<ExampleToken>["proposalCommitment"];
+
+export type Recipient = IdlTypes<ExampleToken>["recipient"];
+
+/** Parses a 0x-prefixed EVM address or a base58 Solana address. */
+export function parseRecipient(address: string): Recipient {
+  if (address.startsWith("0x")) {
+    const bytes = Buffer.from(address.slice(2), "hex");
+    return { evm: { address: Array.from(bytes) } };
+  }
+  return { solana: { address: new PublicKey(address) } };
+}
+
 export type BridgeInput = {
-  destination: Destination;
-  to: Buffer;
+  destChainId: BN;
+  to: Recipient;
   amount: BN;
   uipFee: BN;
   customGasLimit: BN;
   sender: Keypair;
+  referrer?: PublicKey;
+  commitment?: ProposalCommitment;
+  /** Extra accounts of the transfer hook of the EXA mint, if any. */
+  hookAccounts?: AccountMeta[];
 };
 
 export async function bridge(
@@ -111,19 +429,116 @@ export async function bridge(
     to,
     amount,
     customGasLimit,
-    destination,
+    destChainId,
     sender,
+    referrer,
+    commitment,
+    hookAccounts,
   }: BridgeInput,
 ): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram, vault } = await fetchBridgedMint();
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .bridge(
+      destChainId,
+      to,
+      amount,
+      uipFee,
+      customGasLimit,
+      referrer ?? null,
+      commitment ?? { confirmed: {} },
+    )
+    .accounts({
+      sender: sender.publicKey,
+      endpointConfig: ENDPOINT_CONFIG,
+      utsConnector: await fetchUtsConnector(),
+      config: EXAMPLE_TOKEN_CONFIG,
+      origin: findOrigin(destChainId),
+      userUsage: findUserUsage(destChainId, sender.publicKey),
+      outboundTransfer: await fetchNextOutboundTransfer(),
+      tokenAccount: getAssociatedTokenAddressSync(
+        mint,
+        sender.publicKey,
+        true,
+        tokenProgram,
+      ),
+      exaMint: mint,
+      vault,
+      treasury: getAssociatedTokenAddressSync(
+        mint,
+        TREASURY_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      referrerTokenAccount: referrer
+        ? getAssociatedTokenAddressSync(mint, referrer, true, tokenProgram)
+        : null,
+      tokenProgram,
+    })
+    .remainingAccounts(hookAccounts ?? [])
+    .signers([sender])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type BatchRecipient = IdlTypes<ExampleToken>["batchRecipient"];
+
+export type BridgeBatchInput = {
+  destChainId: BN;
+  recipients: BatchRecipient[];
+  uipFee: BN;
+  customGasLimit: BN;
+  sender: Keypair;
+  commitment?: ProposalCommitment;
+  /** Extra accounts of the transfer hook of the EXA mint, if any. */
+  hookAccounts?: AccountMeta[];
+};
+
+export async function bridgeBatch(
+  {
+    uipFee,
+    recipients,
+    customGasLimit,
+    destChainId,
+    sender,
+    commitment,
+    hookAccounts,
+  }: BridgeBatchInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram, vault } = await fetchBridgedMint();
   const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
-    .bridge(destination, to, amount, uipFee, customGasLimit)
+    .bridgeBatch(
+      destChainId,
+      recipients,
+      uipFee,
+      customGasLimit,
+      commitment ?? { confirmed: {} },
+    )
     .accounts({
       sender: sender.publicKey,
       endpointConfig: ENDPOINT_CONFIG,
       utsConnector: await fetchUtsConnector(),
       config: EXAMPLE_TOKEN_CONFIG,
-      tokenAccount: getAssociatedTokenAddressSync(EXA_MINT, sender.publicKey),
+      origin: findOrigin(destChainId),
+      userUsage: findUserUsage(destChainId, sender.publicKey),
+      outboundTransfer: null,
+      tokenAccount: getAssociatedTokenAddressSync(
+        mint,
+        sender.publicKey,
+        true,
+        tokenProgram,
+      ),
+      exaMint: mint,
+      vault,
+      treasury: getAssociatedTokenAddressSync(
+        mint,
+        TREASURY_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      referrerTokenAccount: null,
+      tokenProgram,
     })
+    .remainingAccounts(hookAccounts ?? [])
     .signers([sender])
     .rpc();
   return { transactionSignature };
@@ -147,3 +562,1037 @@ export async function updateAdmin(
     .rpc();
   return { transactionSignature };
 }
+
+export type SetOriginInput = {
+  authority: Keypair;
+  payer: Keypair;
+  chainId: BN;
+  address: Buffer;
+  enabled: boolean;
+  decimals: number;
+};
+
+export async function setOrigin(
+  {
+    authority,
+    payer,
+    chainId,
+    address,
+    enabled,
+    decimals,
+  }: SetOriginInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setOrigin(chainId, Array.from(address), enabled, decimals)
+    .accounts({
+      authority: authority.publicKey,
+      payer: payer.publicKey,
+    })
+    .signers([authority, payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type RemoveOriginInput = {
+  authority: Keypair;
+  chainId: BN;
+};
+
+export async function removeOrigin(
+  { authority, chainId }: RemoveOriginInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .removeOrigin(chainId)
+    .accounts({
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type Limits = {
+  outboundCapacity: BN;
+  outboundWindow: BN;
+  inboundCapacity: BN;
+  inboundWindow: BN;
+  minAmount: BN;
+  maxAmount: BN;
+  userDailyCap: BN;
+};
+
+export type SetLimitsInput = {
+  authority: Keypair;
+  chainId: BN;
+  limits: Limits;
+};
+
+export async function setLimits(
+  { authority, chainId, limits }: SetLimitsInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setLimits(chainId, limits)
+    .accounts({
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type SetRefundableInput = {
+  authority: Keypair;
+  chainId: BN;
+  refundable: boolean;
+  refundWindow: BN;
+};
+
+export async function setRefundable(
+  { authority, chainId, refundable, refundWindow }: SetRefundableInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setRefundable(chainId, refundable, refundWindow)
+    .accounts({
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type CloseOutboundTransferInput = {
+  sender: Keypair;
+  nonce: BN;
+};
+
+export async function closeOutboundTransfer(
+  { sender, nonce }: CloseOutboundTransferInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .closeOutboundTransfer(nonce)
+    .accounts({
+      sender: sender.publicKey,
+    })
+    .signers([sender])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type ReleaseQueuedTransferInput = {
+  payer: Keypair;
+  msgHash: Buffer;
+};
+
+export async function releaseQueuedTransfer(
+  { payer, msgHash }: ReleaseQueuedTransferInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const queuedTransfer = await EXAMPLE_TOKEN_PROGRAM.account.queuedTransfer
+    .fetch(findQueuedTransfer(msgHash));
+  const { mint, tokenProgram, vault } = await fetchBridgedMint();
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .releaseQueuedTransfer(Array.from(msgHash))
+    .accounts({
+      payer: payer.publicKey,
+      config: EXAMPLE_TOKEN_CONFIG,
+      queuedTransfer: findQueuedTransfer(msgHash),
+      origin: findOrigin(queuedTransfer.srcChainId),
+      rentPayer: queuedTransfer.rentPayer,
+      tokenAccount: getAssociatedTokenAddressSync(
+        mint,
+        queuedTransfer.to,
+        true,
+        tokenProgram,
+      ),
+      exaMint: mint,
+      vault,
+      escrowVault: getAssociatedTokenAddressSync(
+        mint,
+        ESCROW_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      tokenProgram,
+      to: queuedTransfer.to,
+      attestation: findAttestation(queuedTransfer.to),
+    })
+    .signers([payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export async function releaseQueuedAssetTransfer(
+  { payer, msgHash }: ReleaseQueuedTransferInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const queuedTransfer = await EXAMPLE_TOKEN_PROGRAM.account.queuedTransfer
+    .fetch(findQueuedTransfer(msgHash));
+  const assetId = queuedTransfer.assetId;
+  const { mint, tokenProgram, vault } = await fetchAssetMint(assetId);
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .releaseQueuedAssetTransfer(Array.from(msgHash))
+    .accounts({
+      payer: payer.publicKey,
+      config: EXAMPLE_TOKEN_CONFIG,
+      queuedTransfer: findQueuedTransfer(msgHash),
+      asset: findAsset(assetId),
+      origin: findAssetOrigin(queuedTransfer.srcChainId, assetId),
+      rentPayer: queuedTransfer.rentPayer,
+      tokenAccount: getAssociatedTokenAddressSync(
+        mint,
+        queuedTransfer.to,
+        true,
+        tokenProgram,
+      ),
+      mint,
+      vault,
+      escrowVault: getAssociatedTokenAddressSync(
+        mint,
+        ESCROW_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      tokenProgram,
+      to: queuedTransfer.to,
+      attestation: findAttestation(queuedTransfer.to),
+    })
+    .signers([payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type CancelQueuedTransferInput = {
+  authority: Keypair;
+  msgHash: Buffer;
+};
+
+export async function cancelQueuedTransfer(
+  { authority, msgHash }: CancelQueuedTransferInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const queuedTransfer = await EXAMPLE_TOKEN_PROGRAM.account.queuedTransfer
+    .fetch(findQueuedTransfer(msgHash));
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .cancelQueuedTransfer(Array.from(msgHash))
+    .accounts({
+      authority: authority.publicKey,
+      rentPayer: queuedTransfer.rentPayer,
+    })
+    .signers([authority])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type SetSettlementDelayInput = {
+  admin: Keypair;
+  threshold: BN;
+  delay: BN;
+};
+
+export async function setSettlementDelay(
+  { admin, threshold, delay }: SetSettlementDelayInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setSettlementDelay(threshold, delay)
+    .accountsStrict({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type PauseTarget = IdlTypes<ExampleToken>["pauseTarget"];
+
+export type SetGuardianInput = {
+  admin: Keypair;
+  guardian: PublicKey;
+};
+
+export async function setGuardian(
+  { admin, guardian }: SetGuardianInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setGuardian(guardian)
+    .accountsStrict({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type PauseInput = {
+  authority: Keypair;
+  target: PauseTarget;
+};
+
+export async function pause(
+  { authority, target }: PauseInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .pause(target)
+    .accounts({
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type UnpauseInput = {
+  admin: Keypair;
+  target: PauseTarget;
+};
+
+export async function unpause(
+  { admin, target }: UnpauseInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .unpause(target)
+    .accounts({
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type SetFeeInput = {
+  admin: Keypair;
+  feeBps: number;
+  referrerShareBps: number;
+};
+
+export async function setFee(
+  { admin, feeBps, referrerShareBps }: SetFeeInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram } = await fetchBridgedMint();
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setFee(feeBps, referrerShareBps)
+    .accounts({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+      treasury: getAssociatedTokenAddressSync(
+        mint,
+        TREASURY_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      exaMint: mint,
+      tokenProgram,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type WithdrawFeesInput = {
+  admin: Keypair;
+  destination: PublicKey;
+  amount: BN;
+  /** Extra accounts of the transfer hook of the EXA mint, if any. */
+  hookAccounts?: AccountMeta[];
+};
+
+export async function withdrawFees(
+  { admin, destination, amount, hookAccounts }: WithdrawFeesInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram } = await fetchBridgedMint();
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .withdrawFees(amount)
+    .accounts({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+      exaMint: mint,
+      destination,
+      tokenProgram,
+    })
+    .remainingAccounts(hookAccounts ?? [])
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type ClaimInput = {
+  recipient: Keypair;
+  msgHash: Buffer;
+  destination: PublicKey;
+  /** Extra accounts of the transfer hook of the EXA mint, if any. */
+  hookAccounts?: AccountMeta[];
+};
+
+export async function claim(
+  { recipient, msgHash, destination, hookAccounts }: ClaimInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { escrow, mint, tokenProgram } = await fetchEscrowMint(msgHash);
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .claim(Array.from(msgHash))
+    .accounts({
+      recipient: recipient.publicKey,
+      rentPayer: escrow.rentPayer,
+      escrowVault: getAssociatedTokenAddressSync(
+        mint,
+        ESCROW_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      exaMint: mint,
+      destination,
+      tokenProgram,
+      attestation: findAttestation(recipient.publicKey),
+    })
+    .remainingAccounts(hookAccounts ?? [])
+    .signers([recipient])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type RecoverEscrowInput = {
+  admin: Keypair;
+  msgHash: Buffer;
+  destination: PublicKey;
+  /** Extra accounts of the transfer hook of the EXA mint, if any. */
+  hookAccounts?: AccountMeta[];
+};
+
+export async function recoverEscrow(
+  { admin, msgHash, destination, hookAccounts }: RecoverEscrowInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { escrow, mint, tokenProgram } = await fetchEscrowMint(msgHash);
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .recoverEscrow(Array.from(msgHash))
+    .accounts({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+      rentPayer: escrow.rentPayer,
+      escrowVault: getAssociatedTokenAddressSync(
+        mint,
+        ESCROW_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      exaMint: mint,
+      destination,
+      tokenProgram,
+    })
+    .remainingAccounts(hookAccounts ?? [])
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type Role = IdlTypes<ExampleToken>["role"];
+
+export type RoleInput = {
+  admin: Keypair;
+  role: Role;
+  account: PublicKey;
+};
+
+export async function grantRole(
+  { admin, role, account }: RoleInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .grantRole(role, account)
+    .accounts({
+      admin: admin.publicKey,
+      payer: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export async function revokeRole(
+  { admin, role, account }: RoleInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .revokeRole(role, account)
+    .accounts({
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export async function reconcileSupply(
+  chainIds: BN[],
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint } = await fetchBridgedMint();
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .reconcileSupply()
+    .accounts({
+      exaMint: mint,
+    })
+    .remainingAccounts(
+      chainIds.map((chainId) => ({
+        pubkey: findOrigin(chainId),
+        isSigner: false,
+        isWritable: false,
+      })),
+    )
+    .rpc();
+  return { transactionSignature };
+}
+
+export type UpdateMetadataInput = {
+  admin: Keypair;
+  metadata: Metadata;
+};
+
+export async function updateMetadata(
+  { admin, metadata }: UpdateMetadataInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram } = await fetchBridgedMint();
+  const withMetaplex = tokenProgram.equals(TOKEN_PROGRAM_ID);
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .updateMetadata(metadata)
+    .accounts({
+      admin: admin.publicKey,
+      payer: admin.publicKey,
+      exaMint: mint,
+      tokenProgram,
+      metaplexMetadata: withMetaplex ? findMetaplexMetadata(mint) : null,
+      metaplexProgram: withMetaplex ? METAPLEX_PROGRAM_ID : null,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type SetFinalizedThresholdInput = {
+  admin: Keypair;
+  threshold: BN;
+};
+
+export async function setFinalizedThreshold(
+  { admin, threshold }: SetFinalizedThresholdInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setFinalizedThreshold(threshold)
+    .accountsStrict({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type Permit = IdlTypes<ExampleToken>["permit"];
+
+export function permitMessage(
+  permit: Permit,
+  chainId: BN = SOLANA_CHAIN_ID,
+): Buffer {
+  return Buffer.concat([
+    Buffer.from("EXA_PERMIT"),
+    EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+    chainId.toArrayLike(Buffer, "le", 16),
+    EXAMPLE_TOKEN_PROGRAM.coder.types.encode("permit", permit),
+  ]);
+}
+
+/** Signs the permit off-chain with the ed25519 key of the token owner. */
+export function signPermit(
+  permit: Permit,
+  owner: Keypair,
+  chainId?: BN,
+): Buffer {
+  // PKCS#8 DER prefix of an ed25519 private key seed.
+  const pkcs8Prefix = Buffer.from("302e020100300506032b657004220420", "hex");
+  const privateKey = createPrivateKey({
+    key: Buffer.concat([pkcs8Prefix, owner.secretKey.subarray(0, 32)]),
+    format: "der",
+    type: "pkcs8",
+  });
+  return sign(null, permitMessage(permit, chainId), privateKey);
+}
+
+export async function fetchPermitNonce(owner: PublicKey): Promise<BN> {
+  const permitNonce = await EXAMPLE_TOKEN_PROGRAM.account.permitNonce
+    .fetchNullable(findPermitNonce(owner));
+  return permitNonce?.nonce ?? new BN(0);
+}
+
+export type ApprovePermitInput = {
+  owner: Keypair;
+  amount: BN;
+};
+
+/**
+ * Approves the permit authority as a delegate of the owner's token account,
+ * which lets relayers bridge up to `amount` tokens with signed permits.
+ */
+export async function approvePermit(
+  { owner, amount }: ApprovePermitInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram } = await fetchBridgedMint();
+  const transactionSignature = await approve(
+    EXAMPLE_TOKEN_PROGRAM.provider.connection,
+    owner,
+    getAssociatedTokenAddressSync(mint, owner.publicKey, true, tokenProgram),
+    PERMIT_AUTHORITY,
+    owner,
+    BigInt(amount.toString()),
+    [],
+    undefined,
+    tokenProgram,
+  );
+  return { transactionSignature };
+}
+
+export type BridgeWithPermitInput = {
+  permit: Permit;
+  owner: PublicKey;
+  signature: Buffer;
+  relayer: Keypair;
+  uipFee: BN;
+  /** Chain id the permit was signed for, if not the current cluster. */
+  chainId?: BN;
+  /** Extra accounts of the transfer hook of the EXA mint, if any. */
+  hookAccounts?: AccountMeta[];
+};
+
+export async function bridgeWithPermit(
+  {
+    permit,
+    owner,
+    signature,
+    relayer,
+    uipFee,
+    chainId,
+    hookAccounts,
+  }: BridgeWithPermitInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram, vault } = await fetchBridgedMint();
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .bridgeWithPermit(permit, uipFee)
+    .accounts({
+      owner,
+      relayer: relayer.publicKey,
+      endpointConfig: ENDPOINT_CONFIG,
+      utsConnector: await fetchUtsConnector(),
+      config: EXAMPLE_TOKEN_CONFIG,
+      origin: findOrigin(permit.destChainId),
+      userUsage: findUserUsage(permit.destChainId, owner),
+      outboundTransfer: await fetchNextOutboundTransfer(),
+      tokenAccount: getAssociatedTokenAddressSync(
+        mint,
+        owner,
+        true,
+        tokenProgram,
+      ),
+      exaMint: mint,
+      vault,
+      treasury: getAssociatedTokenAddressSync(
+        mint,
+        TREASURY_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      tokenProgram,
+    })
+    .preInstructions([
+      Ed25519Program.createInstructionWithPublicKey({
+        publicKey: owner.toBytes(),
+        message: permitMessage(permit, chainId),
+        signature,
+      }),
+    ])
+    .remainingAccounts(hookAccounts ?? [])
+    .signers([relayer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type FreezeInput = {
+  admin: Keypair;
+  tokenAccount: PublicKey;
+};
+
+export async function freeze(
+  { admin, tokenAccount }: FreezeInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram } = await fetchBridgedMint();
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .freeze()
+    .accounts({
+      admin: admin.publicKey,
+      exaMint: mint,
+      tokenAccount,
+      tokenProgram,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export async function thaw(
+  { admin, tokenAccount }: FreezeInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram } = await fetchBridgedMint();
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .thaw()
+    .accounts({
+      admin: admin.publicKey,
+      exaMint: mint,
+      tokenAccount,
+      tokenProgram,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type BlockInput = {
+  admin: Keypair;
+  address: Buffer;
+};
+
+export async function block(
+  { admin, address }: BlockInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .block(Array.from(address))
+    .accounts({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export async function unblock(
+  { admin, address }: BlockInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .unblock(Array.from(address))
+    .accounts({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type SetAttestationAuthorityInput = {
+  admin: Keypair;
+  authority: PublicKey | null;
+};
+
+export async function setAttestationAuthority(
+  { admin, authority }: SetAttestationAuthorityInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setAttestationAuthority(authority)
+    .accountsStrict({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type AttestInput = {
+  authority: Keypair;
+  recipient: PublicKey;
+};
+
+export async function attest(
+  { authority, recipient }: AttestInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .attest(recipient)
+    .accounts({
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+  return { transactionSignature };
+}
+
+export async function revokeAttestation(
+  { authority, recipient }: AttestInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const attestation = await EXAMPLE_TOKEN_PROGRAM.account.attestation.fetch(
+    findAttestation(recipient),
+  );
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .revokeAttestation(recipient)
+    .accounts({
+      authority: authority.publicKey,
+      issuer: attestation.issuer,
+    })
+    .signers([authority])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type RegisterAssetInput = {
+  admin: Keypair;
+  payer: Keypair;
+  assetId: BN;
+  decimals: number;
+};
+
+export async function registerAsset(
+  { admin, payer, assetId, decimals }: RegisterAssetInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const mint = findAssetMint(assetId);
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .registerAsset(assetId, decimals)
+    .accounts({
+      admin: admin.publicKey,
+      payer: payer.publicKey,
+      treasury: getAssociatedTokenAddressSync(mint, TREASURY_AUTHORITY, true),
+    })
+    .signers([admin, payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type RegisterAssetLockReleaseInput = {
+  admin: Keypair;
+  payer: Keypair;
+  assetId: BN;
+  mint: PublicKey;
+};
+
+export async function registerAssetLockRelease(
+  { admin, payer, assetId, mint }: RegisterAssetLockReleaseInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { owner: tokenProgram } = await EXAMPLE_TOKEN_PROGRAM.provider
+    .connection.getAccountInfo(mint);
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .registerAssetLockRelease(assetId)
+    .accounts({
+      admin: admin.publicKey,
+      payer: payer.publicKey,
+      mint,
+      vault: getAssociatedTokenAddressSync(
+        mint,
+        findAsset(assetId),
+        true,
+        tokenProgram,
+      ),
+      treasury: getAssociatedTokenAddressSync(
+        mint,
+        TREASURY_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      tokenProgram,
+    })
+    .signers([admin, payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type SetAssetOriginInput = SetOriginInput & {
+  assetId: BN;
+  /** Mint of the asset on the remote chain if it's locked there. */
+  remoteMint?: PublicKey;
+  remoteToken2022?: boolean;
+};
+
+export async function setAssetOrigin(
+  {
+    authority,
+    payer,
+    assetId,
+    chainId,
+    address,
+    enabled,
+    decimals,
+    remoteMint,
+    remoteToken2022,
+  }: SetAssetOriginInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setAssetOrigin(
+      assetId,
+      chainId,
+      Array.from(address),
+      enabled,
+      decimals,
+      remoteMint ?? null,
+      remoteToken2022 ?? false,
+    )
+    .accounts({
+      authority: authority.publicKey,
+      payer: payer.publicKey,
+    })
+    .signers([authority, payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type SetAssetLimitsInput = SetLimitsInput & { assetId: BN };
+
+export async function setAssetLimits(
+  { authority, assetId, chainId, limits }: SetAssetLimitsInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setAssetLimits(assetId, chainId, limits)
+    .accounts({
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type BridgeAssetInput =
+  & Omit<BridgeInput, "referrer">
+  & { assetId: BN };
+
+export async function bridgeAsset(
+  {
+    assetId,
+    uipFee,
+    to,
+    amount,
+    customGasLimit,
+    destChainId,
+    sender,
+    commitment,
+  }: BridgeAssetInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const { mint, tokenProgram, vault } = await fetchAssetMint(assetId);
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .bridgeAsset(
+      assetId,
+      destChainId,
+      to,
+      amount,
+      uipFee,
+      customGasLimit,
+      commitment ?? { confirmed: {} },
+    )
+    .accounts({
+      sender: sender.publicKey,
+      endpointConfig: ENDPOINT_CONFIG,
+      utsConnector: await fetchUtsConnector(),
+      origin: findAssetOrigin(destChainId, assetId),
+      userUsage: findAssetUserUsage(destChainId, sender.publicKey, assetId),
+      tokenAccount: getAssociatedTokenAddressSync(
+        mint,
+        sender.publicKey,
+        true,
+        tokenProgram,
+      ),
+      mint,
+      vault,
+      treasury: getAssociatedTokenAddressSync(
+        mint,
+        TREASURY_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      tokenProgram,
+    })
+    .signers([sender])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type SetForwardGasLimitInput = {
+  admin: Keypair;
+  gasLimit: BN | null;
+};
+
+export async function setForwardGasLimit(
+  { admin, gasLimit }: SetForwardGasLimitInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .setForwardGasLimit(gasLimit)
+    .accountsStrict({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type WithdrawForwardFeesInput = {
+  admin: Keypair;
+  destination: PublicKey;
+  amount: BN;
+};
+
+export async function withdrawForwardFees(
+  { admin, destination, amount }: WithdrawForwardFeesInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .withdrawForwardFees(amount)
+    .accounts({
+      config: EXAMPLE_TOKEN_CONFIG,
+      admin: admin.publicKey,
+      destination,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type ForwardInput = {
+  payer: Keypair;
+  msgHash: Buffer;
+  uipFee: BN;
+};
+
+export async function forward(
+  { payer, msgHash, uipFee }: ForwardInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const pendingForward = await EXAMPLE_TOKEN_PROGRAM.account.pendingForward
+    .fetch(findPendingForward(msgHash));
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .forward(Array.from(msgHash), uipFee)
+    .accounts({
+      payer: payer.publicKey,
+      origin: findOrigin(pendingForward.destChainId),
+      rentPayer: pendingForward.rentPayer,
+      endpointConfig: ENDPOINT_CONFIG,
+      utsConnector: await fetchUtsConnector(),
+    })
+    .signers([payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type CancelForwardInput = {
+  authority: Keypair;
+  msgHash: Buffer;
+};
+
+export async function cancelForward(
+  { authority, msgHash }: CancelForwardInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const pendingForward = await EXAMPLE_TOKEN_PROGRAM.account.pendingForward
+    .fetch(findPendingForward(msgHash));
+  const { mint, tokenProgram, vault } = await fetchBridgedMint();
+  const transactionSignature = await EXAMPLE_TOKEN_PROGRAM.methods
+    .cancelForward(Array.from(msgHash))
+    .accounts({
+      authority: authority.publicKey,
+      rentPayer: pendingForward.rentPayer,
+      exaMint: mint,
+      vault,
+      escrowVault: getAssociatedTokenAddressSync(
+        mint,
+        ESCROW_AUTHORITY,
+        true,
+        tokenProgram,
+      ),
+      tokenProgram,
+    })
+    .signers([authority])
+    .rpc();
+  return { transactionSignature };
+}
@@ -20,6 +20,7 @@ export const SOLANA_DEVNET_CHAIN_ID = new BN("100000000000000000000");
 export const SEPOLIA_CHAIN_ID = new BN("11155111");
 export const POLYGON_AMOY_CHAIN_ID = new BN("80002");
 export const POLYGON_CHAIN_ID = new BN("137");
+export const MANTLE_SEPOLIA_CHAIN_ID = new BN("5003");
 export const EIB_CHAIN_ID = new BN("33033");
 export const TEIB_CHAIN_ID = new BN("33133");
 
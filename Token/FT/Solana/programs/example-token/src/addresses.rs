@@ -1,8 +0,0 @@
-use hex_literal::hex;
-
-pub const POLYGON_AMOY_ADDRESS: [u8; 32] =
-    hex!("000000000000000000000000bFf2654A596081E49FE1d27463265f23c9D5Ba19");
-pub const MANTLE_SEPOLIA_ADDRESS: [u8; 32] =
-    hex!("000000000000000000000000A44f5Cb1940297d85812BcC46184E10C8EA333E0");
-pub const TEIB_ADDRESS: [u8; 32] =
-    hex!("000000000000000000000000dec46354B960168a3602818Abe82f007c800D33a");
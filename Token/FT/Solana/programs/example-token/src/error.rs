@@ -10,4 +10,164 @@ pub enum ExampleTokenError {
     /// 6001 0x1771
     #[msg("Destination smart contract is not allowed")]
     DestinationSmartContractNotAllowed,
+
+    /// 6002 0x1772
+    #[msg("Destination chain is unknown")]
+    UnknownDestinationChain,
+
+    /// 6003 0x1773
+    #[msg("Amount has dust that cannot be represented on the destination chain")]
+    AmountHasDust,
+
+    /// 6004 0x1774
+    #[msg("Amount does not fit into the token representation")]
+    AmountOverflow,
+
+    /// 6005 0x1775
+    #[msg("Amount is below the minimum")]
+    AmountBelowMinimum,
+
+    /// 6006 0x1776
+    #[msg("Amount is above the maximum")]
+    AmountAboveMaximum,
+
+    /// 6007 0x1777
+    #[msg("Outbound rate limit exceeded")]
+    OutboundRateLimitExceeded,
+
+    /// 6008 0x1778
+    #[msg("Inbound rate limit exceeded")]
+    InboundRateLimitExceeded,
+
+    /// 6009 0x1779
+    #[msg("User daily limit exceeded")]
+    UserDailyLimitExceeded,
+
+    /// 6010 0x177a
+    #[msg("Outbound bridging is paused")]
+    OutboundPaused,
+
+    /// 6011 0x177b
+    #[msg("Inbound bridging is paused")]
+    InboundPaused,
+
+    /// 6012 0x177c
+    #[msg("Route is paused")]
+    RoutePaused,
+
+    /// 6013 0x177d
+    #[msg("Signer is neither the guardian nor a pauser")]
+    NotGuardian,
+
+    /// 6014 0x177e
+    #[msg("Too many paused routes")]
+    TooManyPausedRoutes,
+
+    /// 6015 0x177f
+    #[msg("Vault balance is insufficient")]
+    InsufficientVaultBalance,
+
+    /// 6016 0x1780
+    #[msg("Instruction is not supported in the lock/release mode")]
+    UnsupportedInLockReleaseMode,
+
+    /// 6017 0x1781
+    #[msg("Basis point value exceeds 10000")]
+    InvalidBps,
+
+    /// 6018 0x1782
+    #[msg("Signer is not the recipient of the escrowed transfer")]
+    NotEscrowRecipient,
+
+    /// 6019 0x1783
+    #[msg("Accounts do not match the call in the payload")]
+    InvalidCallAccounts,
+
+    /// 6020 0x1784
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+
+    /// 6021 0x1785
+    #[msg("Too many holders of the role")]
+    TooManyRoleHolders,
+
+    /// 6022 0x1786
+    #[msg("Number of batch recipients is out of bounds")]
+    InvalidBatchSize,
+
+    /// 6023 0x1787
+    #[msg("Recipient is not a valid address on the destination chain")]
+    InvalidRecipient,
+
+    /// 6024 0x1788
+    #[msg("Permit is not signed by the token owner")]
+    InvalidPermitSignature,
+
+    /// 6025 0x1789
+    #[msg("Permit deadline has passed")]
+    PermitExpired,
+
+    /// 6026 0x178a
+    #[msg("Permit nonce is not the next one of the owner")]
+    InvalidPermitNonce,
+
+    /// 6027 0x178b
+    #[msg("Address is blocklisted")]
+    Blocked,
+
+    /// 6028 0x178c
+    #[msg("Too many blocklisted addresses")]
+    TooManyBlocked,
+
+    /// 6029 0x178d
+    #[msg("Signer is not the attestation authority")]
+    NotAttestationAuthority,
+
+    /// 6030 0x178e
+    #[msg("Recipient lacks a valid attestation")]
+    MissingAttestation,
+
+    /// 6031 0x178f
+    #[msg("Asset id 0 is reserved for EXA")]
+    InvalidAssetId,
+
+    /// 6032 0x1790
+    #[msg("Forwarding transfers is disabled")]
+    ForwardingDisabled,
+
+    /// 6033 0x1791
+    #[msg("Forward fee vault cannot cover the UIP fee")]
+    ForwardFeeVaultDepleted,
+
+    /// 6034 0x1792
+    #[msg("Settlement delay has not elapsed")]
+    SettlementDelayNotElapsed,
+
+    /// 6035 0x1793
+    #[msg("Refund does not match the outbound transfer")]
+    InvalidRefund,
+
+    /// 6036 0x1794
+    #[msg("Refund exceeds the amount of the outbound transfer")]
+    RefundExceedsTransfer,
+
+    /// 6037 0x1795
+    #[msg("Queued transfer is of another asset")]
+    QueuedAssetMismatch,
+
+    /// 6038 0x1796
+    #[msg("Refund window has not elapsed")]
+    RefundWindowNotElapsed,
+
+    /// 6039 0x1797
+    #[msg("Payload format is only decoded by Solana deployments")]
+    SolanaOnlyPayload,
+
+    /// 6040 0x1798
+    #[msg("Mints with a transfer hook can't be locked")]
+    TransferHookNotSupported,
+
+    /// 6041 0x1799
+    #[msg("Chain id isn't a Solana chain")]
+    NotSolanaChain,
 }
@@ -0,0 +1,131 @@
+//! Example token events.
+
+use anchor_lang::prelude::*;
+
+/// Emitted when tokens are sent to another chain.
+#[event]
+pub struct BridgeTransfer {
+    /// Sender of the tokens.
+    pub sender: Pubkey,
+    /// Identifier of the bridged asset, 0 for EXA.
+    pub asset_id: u64,
+    /// Identifier of the destination chain.
+    pub dest_chain_id: u128,
+    /// Recipient of the tokens as encoded in the payload for the destination
+    /// chain.
+    pub to: Vec<u8>,
+    /// Amount of tokens bridged after the protocol fee, in local decimals.
+    pub amount: u64,
+    /// Protocol fee charged, including the referrer share.
+    pub fee: u64,
+    /// Referrer that received a share of the fee, if any.
+    pub referrer: Option<Pubkey>,
+    /// Share of the fee paid to the referrer.
+    pub referrer_fee: u64,
+}
+
+/// Emitted for each chain on supply reconciliation. The amounts are in local
+/// decimals.
+#[event]
+pub struct SupplyReconciled {
+    /// Identifier of the remote chain.
+    pub chain_id: u128,
+    /// Total amount of tokens sent to the remote chain.
+    pub total_outbound: u128,
+    /// Total amount of tokens received from the remote chain.
+    pub total_inbound: u128,
+    /// Amount of tokens sent to the remote chain and not received back.
+    pub outstanding: i128,
+}
+
+/// Emitted when an incoming transfer routed through Solana is sent to its final
+/// destination chain.
+#[event]
+pub struct TransferForwarded {
+    /// Hash of the incoming message.
+    pub msg_hash: [u8; 32],
+    /// Identifier of the source chain.
+    pub src_chain_id: u128,
+    /// Identifier of the final destination chain.
+    pub dest_chain_id: u128,
+    /// Original sender of the tokens as encoded in the payload.
+    pub from: Vec<u8>,
+    /// Recipient of the tokens as encoded in the payload for the destination
+    /// chain.
+    pub to: Vec<u8>,
+    /// Amount of tokens forwarded, in local decimals.
+    pub amount: u64,
+    /// UIP fee paid from the forward fee vault.
+    pub uip_fee: u64,
+}
+
+/// Emitted when a queued incoming transfer is cancelled. The amount is in local
+/// decimals.
+#[event]
+pub struct TransferCancelled {
+    /// Hash of the incoming message.
+    pub msg_hash: [u8; 32],
+    /// Identifier of the source chain.
+    pub src_chain_id: u128,
+    /// Recipient of the tokens.
+    pub to: Pubkey,
+    /// Amount of tokens that were not minted.
+    pub amount: u64,
+    /// Guardian or pauser that cancelled the transfer.
+    pub authority: Pubkey,
+}
+
+/// Emitted when a refundable incoming transfer that could not be delivered is
+/// bounced back to its source chain.
+#[event]
+pub struct TransferBounced {
+    /// Hash of the incoming message.
+    pub msg_hash: [u8; 32],
+    /// Identifier of the source chain, which receives the refund.
+    pub src_chain_id: u128,
+    /// Nonce of the transfer on the source chain.
+    pub nonce: u64,
+    /// Original sender of the tokens as encoded in the payload.
+    pub from: Vec<u8>,
+    /// Amount of tokens refunded, in local decimals.
+    pub amount: u64,
+    /// UIP fee paid from the forward fee vault.
+    pub uip_fee: u64,
+}
+
+/// Emitted when an outgoing transfer that could not be delivered on the
+/// destination chain is refunded to its sender.
+#[event]
+pub struct TransferRefunded {
+    /// Hash of the incoming refund message.
+    pub msg_hash: [u8; 32],
+    /// Identifier of the destination chain of the transfer.
+    pub dest_chain_id: u128,
+    /// Nonce of the transfer.
+    pub nonce: u64,
+    /// Sender of the transfer, who got the tokens back.
+    pub sender: Pubkey,
+    /// Amount of tokens refunded, in local decimals.
+    pub amount: u64,
+}
+
+/// Emitted when a transfer that could not be forwarded or bounced is escrowed
+/// instead.
+#[event]
+pub struct ForwardCancelled {
+    /// Hash of the incoming message.
+    pub msg_hash: [u8; 32],
+    /// Identifier of the source chain.
+    pub src_chain_id: u128,
+    /// Identifier of the final destination chain, which is the source chain
+    /// for a bounced transfer.
+    pub dest_chain_id: u128,
+    /// Original sender of the tokens as encoded in the payload.
+    pub from: Vec<u8>,
+    /// Recipient of the tokens as encoded in the payload.
+    pub to: Vec<u8>,
+    /// Amount of tokens escrowed, in local decimals.
+    pub amount: u64,
+    /// Admin or guardian that cancelled the forward.
+    pub authority: Pubkey,
+}
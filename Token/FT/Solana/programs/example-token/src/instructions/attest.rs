@@ -0,0 +1,68 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct Attest<'info> {
+    #[account(
+        seeds = [b"CONFIG"],
+        bump,
+        constraint = config.attestation_authority == Some(authority.key())
+            @ ExampleTokenError::NotAttestationAuthority,
+    )]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 8 + Attestation::space(),
+        payer = authority,
+        seeds = [b"ATTESTATION", recipient.as_ref()],
+        bump
+    )]
+    attestation: Account<'info, Attestation>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct RevokeAttestation<'info> {
+    #[account(
+        seeds = [b"CONFIG"],
+        bump,
+        constraint = config.attestation_authority == Some(authority.key())
+            @ ExampleTokenError::NotAttestationAuthority,
+    )]
+    config: Account<'info, ExampleTokenConfig>,
+    authority: Signer<'info>,
+    #[account(
+        mut,
+        close = issuer,
+        has_one = issuer,
+        seeds = [b"ATTESTATION", recipient.as_ref()],
+        bump
+    )]
+    attestation: Account<'info, Attestation>,
+    /// CHECK: it's checked to be the authority that issued the attestation
+    #[account(mut)]
+    issuer: AccountInfo<'info>,
+}
+
+/// Attests that `recipient` can receive bridged tokens. Can be called by the
+/// attestation authority.
+pub fn attest(ctx: Context<Attest>, recipient: Pubkey) -> Result<()> {
+    let attestation = &mut ctx.accounts.attestation;
+
+    attestation.issuer = ctx.accounts.authority.key();
+
+    msg!("Attested {}", recipient);
+
+    Ok(())
+}
+
+/// Revokes the attestation of `recipient`, returning its rent to the issuer.
+pub fn revoke_attestation(_ctx: Context<RevokeAttestation>, recipient: Pubkey) -> Result<()> {
+    msg!("Revoked the attestation of {}", recipient);
+
+    Ok(())
+}
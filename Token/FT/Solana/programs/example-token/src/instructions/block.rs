@@ -0,0 +1,57 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Block<'info> {
+    #[account(has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(mut)]
+    admin: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 8 + Blocklist::space(),
+        payer = admin,
+        seeds = [b"BLOCKLIST"],
+        bump
+    )]
+    blocklist: Account<'info, Blocklist>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unblock<'info> {
+    #[account(has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+    #[account(mut, seeds = [b"BLOCKLIST"], bump)]
+    blocklist: Account<'info, Blocklist>,
+}
+
+/// Blocks `address` in the payload format from sending and receiving bridged
+/// tokens.
+pub fn block(ctx: Context<Block>, address: [u8; 32]) -> Result<()> {
+    let blocklist = &mut ctx.accounts.blocklist;
+
+    if !blocklist.contains(&address) {
+        require!(
+            blocklist.addresses.len() < Blocklist::MAX_ADDRESSES,
+            ExampleTokenError::TooManyBlocked
+        );
+        blocklist.addresses.push(address);
+    }
+
+    msg!("Blocked {}", hex::encode(address));
+
+    Ok(())
+}
+
+/// Removes `address` from the blocklist.
+pub fn unblock(ctx: Context<Unblock>, address: [u8; 32]) -> Result<()> {
+    let blocklist = &mut ctx.accounts.blocklist;
+
+    blocklist.addresses.retain(|blocked| *blocked != address);
+
+    msg!("Unblocked {}", hex::encode(address));
+
+    Ok(())
+}
@@ -1,24 +1,63 @@
-use crate::{addresses::*, error::*, state::*};
-use alloy_sol_types::{
-    sol_data::{Bytes, Uint},
-    SolType,
+use crate::{
+    error::*,
+    events::*,
+    payload::{Transfer, TransferBatch, MAX_BATCH_RECIPIENTS},
+    state::*,
+    utils::{bps_of, debit, find_ata, is_solana_chain, to_remote_amount, transfer},
 };
-use anchor_lang::prelude::*;
-use solana_invoke::invoke;
-use spl_token::instruction::burn;
-use uip_solana_sdk::{chains::*, Commitment, UipEndpoint};
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+use uip_solana_sdk::{Commitment, UipEndpoint};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
 
 #[derive(Accounts)]
+#[instruction(dest_chain_id: u128)]
 pub struct Bridge<'info> {
+    #[account(mut)]
     config: Account<'info, ExampleTokenConfig>,
+    /// CHECK: it's derived from the destination chain id and deserialized in
+    /// the handler
+    #[account(mut, seeds = [&b"ORIGIN"[..], &dest_chain_id.to_le_bytes()], bump)]
+    origin: AccountInfo<'info>,
     #[account(mut)]
     sender: Signer<'info>,
+    /// Required if the origin has a per-wallet daily cap.
+    #[account(
+        init_if_needed,
+        space = 8 + UserUsage::space(),
+        payer = sender,
+        seeds = [&b"USER_USAGE"[..], &dest_chain_id.to_le_bytes(), sender.key.as_ref()],
+        bump
+    )]
+    user_usage: Option<Account<'info, UserUsage>>,
+    /// CHECK: it's checked to be the outbound transfer PDA of the next nonce
+    /// and created in the handler, required if the origin is refundable
+    #[account(mut)]
+    outbound_transfer: Option<AccountInfo<'info>>,
     /// CHECK: checked in CPI
     #[account(mut)]
     token_account: AccountInfo<'info>,
-    /// CHECK: it's checked to be the EXA mint
-    #[account(mut, seeds = [b"EXA_MINT"], bump)]
+    /// CHECK: it's checked to be the bridged mint
+    #[account(mut, address = config.mint)]
     exa_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the config ATA in the lock/release mode
+    #[account(mut)]
+    vault: Option<AccountInfo<'info>>,
+    /// CHECK: it's derived from the treasury seeds
+    #[account(seeds = [b"TREASURY"], bump)]
+    treasury_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the treasury authority ATA
+    #[account(
+        mut,
+        address = find_ata(treasury_authority.key, exa_mint.key, exa_mint.owner)
+    )]
+    treasury: AccountInfo<'info>,
+    /// CHECK: it's checked to be the referrer ATA if a referrer is set
+    #[account(mut)]
+    referrer_token_account: Option<AccountInfo<'info>>,
     /// CHECK: checked in the CPI
     endpoint_config: AccountInfo<'info>,
     /// CHECK: checked in the CPI
@@ -27,94 +66,518 @@ pub struct Bridge<'info> {
     /// CHECK: checked in CPI
     #[account(seeds = [b"UIP_SIGNER"], bump)]
     program_signer: AccountInfo<'info>,
-    /// CHECK: it's checked to be the SPL token program
-    #[account(address = spl_token::ID)]
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
     token_program: AccountInfo<'info>,
     system_program: Program<'info, System>,
     uip_program: Program<'info, UipEndpoint>,
+    /// CHECK: it's derived from the blocklist seeds and deserialized in the
+    /// handler
+    #[account(seeds = [b"BLOCKLIST"], bump)]
+    blocklist: AccountInfo<'info>,
 }
 
-/// Different destination chains for sending messages.
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub enum Destination {
-    SolanaMainnet,
-    SolanaDevnet,
-    PolygonAmoy,
-    MantleSepolia,
-    Teib,
+/// Commitment the source transaction must reach before the transfer is
+/// processed, corresponds to EVM `blockFinalizationOption`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProposalCommitment {
+    /// Corresponds to the EVM Fast finalization option.
+    Confirmed,
+    /// Corresponds to the EVM Standard finalization option.
+    Finalized,
 }
 
-pub fn bridge(
-    ctx: Context<Bridge>,
-    destination: Destination,
-    to: Vec<u8>,
+/// Recipient of the tokens on the destination chain.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Recipient {
+    /// An EVM address.
+    Evm { address: [u8; 20] },
+    /// A Solana wallet, whose ATA receives the tokens.
+    Solana { address: Pubkey },
+}
+
+impl Recipient {
+    /// Encodes the recipient in the format expected on the chain `chain_id`,
+    /// failing if it's a zero address or not an address of that chain.
+    pub fn encode(&self, chain_id: u128) -> Result<Vec<u8>> {
+        let is_solana = is_solana_chain(chain_id);
+        match self {
+            Recipient::Evm { address } if !is_solana && *address != [0; 20] => {
+                // ABI-encoded as `address`, left-padded to 32 bytes.
+                let mut encoded = vec![0; 12];
+                encoded.extend_from_slice(address);
+                Ok(encoded)
+            }
+            Recipient::Solana { address } if is_solana && *address != Pubkey::default() => {
+                Ok(address.to_bytes().to_vec())
+            }
+            _ => err!(ExampleTokenError::InvalidRecipient),
+        }
+    }
+
+    /// Decodes a recipient `encoded` in the format expected on the chain
+    /// `chain_id`, failing where `encode` would.
+    pub fn decode(encoded: &[u8], chain_id: u128) -> Result<Self> {
+        let is_solana = is_solana_chain(chain_id);
+        let encoded = <[u8; 32]>::try_from(encoded)
+            .map_err(|_| error!(ExampleTokenError::InvalidRecipient))?;
+        let recipient = match encoded.split_at(12) {
+            (padding, address) if !is_solana && padding == [0; 12] => Recipient::Evm {
+                address: address.try_into().unwrap(),
+            },
+            _ => Recipient::Solana {
+                address: Pubkey::new_from_array(encoded),
+            },
+        };
+        recipient.encode(chain_id)?;
+
+        Ok(recipient)
+    }
+}
+
+/// A recipient of a batch transfer.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchRecipient {
+    /// Recipient of the tokens on the destination chain.
+    pub to: Recipient,
+    /// Amount of tokens sent to the recipient before the protocol fee.
+    pub amount: u64,
+}
+
+impl From<ProposalCommitment> for Commitment {
+    fn from(commitment: ProposalCommitment) -> Self {
+        match commitment {
+            ProposalCommitment::Confirmed => Commitment::Confirmed,
+            ProposalCommitment::Finalized => Commitment::Finalized,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn bridge<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Bridge<'info>>,
+    dest_chain_id: u128,
+    to: Recipient,
     amount: u64,
     uip_fee: u64,
     custom_gas_limit: u128,
+    referrer: Option<Pubkey>,
+    commitment: ProposalCommitment,
 ) -> Result<()> {
+    let config = &mut ctx.accounts.config;
     let sender = &ctx.accounts.sender;
     let token_account = &ctx.accounts.token_account;
     let exa_mint = &ctx.accounts.exa_mint;
+    let origin_info = &ctx.accounts.origin;
+
+    let mut origin = load_origin(config, origin_info)?;
+    let to = to.encode(origin.chain_id)?;
+    check_not_blocked(&ctx.accounts.blocklist, &[sender.key.as_ref(), &to])?;
+    check_amount(&origin, amount)?;
+    consume_limits(&mut origin, ctx.accounts.user_usage.as_mut(), amount)?;
+    let commitment = effective_commitment(config, amount, commitment);
+
+    let fee = bps_of(amount, config.fee_bps);
+    let referrer_fee = match referrer {
+        Some(referrer) => {
+            let referrer_fee = bps_of(fee, config.referrer_share_bps);
+            let referrer_token_account = ctx
+                .accounts
+                .referrer_token_account
+                .as_ref()
+                .ok_or(ErrorCode::ConstraintAccountIsNone)?;
+            require_keys_eq!(
+                referrer_token_account.key(),
+                find_ata(&referrer, exa_mint.key, exa_mint.owner),
+                ErrorCode::ConstraintAddress
+            );
+            if referrer_fee != 0 {
+                transfer(
+                    exa_mint,
+                    token_account,
+                    referrer_token_account,
+                    &sender.to_account_info(),
+                    config.decimals,
+                    referrer_fee,
+                    &[],
+                    ctx.remaining_accounts,
+                )?;
+            }
+            referrer_fee
+        }
+        None => 0,
+    };
+    if fee > referrer_fee {
+        transfer(
+            exa_mint,
+            token_account,
+            &ctx.accounts.treasury,
+            &sender.to_account_info(),
+            config.decimals,
+            fee - referrer_fee,
+            &[],
+            ctx.remaining_accounts,
+        )?;
+    }
 
-    let ix = burn(
-        &spl_token::ID,
-        token_account.key,
-        exa_mint.key,
-        sender.key,
+    let amount = debit(
+        config,
+        exa_mint,
+        ctx.accounts.vault.as_ref(),
+        token_account,
+        &sender.to_account_info(),
+        amount - fee,
         &[],
+    )?;
+    origin.total_outbound += u128::from(amount);
+    origin.try_serialize(&mut &mut origin_info.try_borrow_mut_data()?[..])?;
+    let remote_amount = to_remote_amount(amount, config.decimals, origin.decimals)?;
+    let nonce = record_outbound(
+        config,
+        &origin,
+        ctx.accounts.outbound_transfer.as_ref(),
+        sender.as_ref(),
+        ctx.accounts.system_program.as_ref(),
+        sender.key(),
         amount,
     )?;
-    invoke(
-        &ix,
-        &[
-            token_account.to_account_info(),
-            exa_mint.to_account_info(),
-            sender.to_account_info(),
-        ],
+
+    let payload = Transfer {
+        from: sender.key.to_bytes().to_vec(),
+        to: to.clone(),
+        amount: remote_amount,
+        call: None,
+        nonce,
+    }
+    .encode();
+
+    propose(
+        ctx.accounts.propose_accounts(ctx.bumps.program_signer),
+        &origin,
+        &payload,
+        uip_fee,
+        custom_gas_limit,
+        commitment,
     )?;
 
-    let payload = <(Bytes, Bytes, Uint<256>)>::abi_encode_params(&(
-        sender.key.to_bytes(),
+    emit!(BridgeTransfer {
+        sender: ctx.accounts.sender.key(),
+        asset_id: 0,
+        dest_chain_id,
         to,
-        ruint::Uint::<256, 4>::from(amount),
-    ));
+        amount,
+        fee,
+        referrer,
+        referrer_fee,
+    });
 
-    match destination {
-        Destination::SolanaMainnet => {
-            #[cfg(not(feature = "mainnet"))]
-            return err!(ExampleTokenError::DestinationSmartContractNotAllowed);
-        }
-        Destination::SolanaDevnet
-        | Destination::PolygonAmoy
-        | Destination::MantleSepolia
-        | Destination::Teib => {
-            #[cfg(feature = "mainnet")]
-            return err!(ExampleTokenError::DestinationSmartContractNotAllowed);
+    Ok(())
+}
+
+pub fn bridge_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Bridge<'info>>,
+    dest_chain_id: u128,
+    recipients: Vec<BatchRecipient>,
+    uip_fee: u64,
+    custom_gas_limit: u128,
+    commitment: ProposalCommitment,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let sender = &ctx.accounts.sender;
+    let token_account = &ctx.accounts.token_account;
+    let exa_mint = &ctx.accounts.exa_mint;
+    let origin_info = &ctx.accounts.origin;
+
+    require!(
+        !recipients.is_empty() && recipients.len() <= MAX_BATCH_RECIPIENTS,
+        ExampleTokenError::InvalidBatchSize
+    );
+    require!(
+        is_solana_chain(dest_chain_id),
+        ExampleTokenError::SolanaOnlyPayload
+    );
+
+    let mut origin = load_origin(config, origin_info)?;
+    let mut total: u64 = 0;
+    let mut encoded_recipients = Vec::with_capacity(recipients.len());
+    for recipient in &recipients {
+        encoded_recipients.push(recipient.to.encode(origin.chain_id)?);
+        check_amount(&origin, recipient.amount)?;
+        total = total
+            .checked_add(recipient.amount)
+            .ok_or(ExampleTokenError::AmountOverflow)?;
+    }
+    let mut addresses: Vec<&[u8]> = vec![sender.key.as_ref()];
+    addresses.extend(encoded_recipients.iter().map(Vec::as_slice));
+    check_not_blocked(&ctx.accounts.blocklist, &addresses)?;
+    consume_limits(&mut origin, ctx.accounts.user_usage.as_mut(), total)?;
+    let commitment = effective_commitment(config, total, commitment);
+
+    let fees: Vec<u64> = recipients
+        .iter()
+        .map(|recipient| bps_of(recipient.amount, config.fee_bps))
+        .collect();
+    let total_fee: u64 = fees.iter().sum();
+    if total_fee != 0 {
+        transfer(
+            exa_mint,
+            token_account,
+            &ctx.accounts.treasury,
+            &sender.to_account_info(),
+            config.decimals,
+            total_fee,
+            &[],
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    // Each amount is debited separately, so that a transfer fee charged by the
+    // mint is attributed to its recipient.
+    let mut transfers = Vec::with_capacity(recipients.len());
+    let mut amounts = Vec::with_capacity(recipients.len());
+    for ((recipient, to), fee) in recipients.iter().zip(&encoded_recipients).zip(&fees) {
+        let amount = debit(
+            config,
+            exa_mint,
+            ctx.accounts.vault.as_ref(),
+            token_account,
+            &sender.to_account_info(),
+            recipient.amount - fee,
+            &[],
+        )?;
+        origin.total_outbound += u128::from(amount);
+        let remote_amount = to_remote_amount(amount, config.decimals, origin.decimals)?;
+        transfers.push((to.clone(), remote_amount));
+        amounts.push(amount);
+    }
+    origin.try_serialize(&mut &mut origin_info.try_borrow_mut_data()?[..])?;
+
+    let payload = TransferBatch {
+        from: sender.key.to_bytes().to_vec(),
+        transfers,
+    }
+    .encode();
+
+    propose(
+        ctx.accounts.propose_accounts(ctx.bumps.program_signer),
+        &origin,
+        &payload,
+        uip_fee,
+        custom_gas_limit,
+        commitment,
+    )?;
+
+    for ((to, fee), amount) in encoded_recipients.into_iter().zip(fees).zip(amounts) {
+        emit!(BridgeTransfer {
+            sender: ctx.accounts.sender.key(),
+            asset_id: 0,
+            dest_chain_id,
+            to,
+            amount,
+            fee,
+            referrer: None,
+            referrer_fee: 0,
+        });
+    }
+
+    Ok(())
+}
+
+/// Deserializes the origin of the destination chain, checking that bridging to
+/// it is allowed.
+pub(super) fn load_origin(
+    config: &ExampleTokenConfig,
+    origin_info: &AccountInfo,
+) -> Result<Origin> {
+    require!(!config.outbound_paused, ExampleTokenError::OutboundPaused);
+    require!(
+        !origin_info.data_is_empty(),
+        ExampleTokenError::UnknownDestinationChain
+    );
+    let origin = Origin::try_deserialize(&mut &origin_info.try_borrow_data()?[..])?;
+    require!(
+        origin.address != [0; 32],
+        ExampleTokenError::UnknownDestinationChain
+    );
+    require!(
+        origin.enabled,
+        ExampleTokenError::DestinationSmartContractNotAllowed
+    );
+    require!(
+        !config.is_route_paused(origin.chain_id),
+        ExampleTokenError::RoutePaused
+    );
+
+    Ok(origin)
+}
+
+/// Checks that none of `addresses`, in the payload format, is blocklisted.
+pub(super) fn check_not_blocked(blocklist: &AccountInfo, addresses: &[&[u8]]) -> Result<()> {
+    let blocklist = Blocklist::load(blocklist)?;
+    require!(
+        !addresses.iter().any(|address| blocklist.contains(address)),
+        ExampleTokenError::Blocked
+    );
+
+    Ok(())
+}
+
+/// Checks that a single transfer is within the bounds set for the origin.
+pub(super) fn check_amount(origin: &Origin, amount: u64) -> Result<()> {
+    require!(
+        amount >= origin.min_amount,
+        ExampleTokenError::AmountBelowMinimum
+    );
+    require!(
+        origin.max_amount == 0 || amount <= origin.max_amount,
+        ExampleTokenError::AmountAboveMaximum
+    );
+
+    Ok(())
+}
+
+/// Consumes `amount` from the outbound rate limit of the origin and the daily
+/// cap of the sender.
+pub(super) fn consume_limits(
+    origin: &mut Origin,
+    user_usage: Option<&mut Account<UserUsage>>,
+    amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        origin.outbound_limit.try_consume(amount, now),
+        ExampleTokenError::OutboundRateLimitExceeded
+    );
+
+    if origin.user_daily_cap != 0 {
+        let user_usage = user_usage.ok_or(ErrorCode::ConstraintAccountIsNone)?;
+        let day = now.div_euclid(SECONDS_PER_DAY);
+        if user_usage.day != day {
+            user_usage.day = day;
+            user_usage.amount = 0;
         }
+        user_usage.amount = user_usage
+            .amount
+            .checked_add(amount)
+            .filter(|&used| used <= origin.user_daily_cap)
+            .ok_or(ExampleTokenError::UserDailyLimitExceeded)?;
     }
 
-    let (dest_chain_id, dest_addr) = match destination {
-        Destination::SolanaMainnet => (SOLANA_MAINNET_CHAIN_ID, &crate::ID.to_bytes()),
-        Destination::SolanaDevnet => (SOLANA_DEVNET_CHAIN_ID, &crate::ID.to_bytes()),
-        Destination::PolygonAmoy => (POLYGON_AMOY_CHAIN_ID, &POLYGON_AMOY_ADDRESS),
-        Destination::MantleSepolia => (MANTLE_SEPOLIA_CHAIN_ID, &MANTLE_SEPOLIA_ADDRESS),
-        Destination::Teib => (TEIB_CHAIN_ID, &TEIB_ADDRESS),
-    };
+    Ok(())
+}
 
+/// Returns the finalized commitment if `amount` reaches the threshold set by
+/// the admin, or the requested one otherwise.
+pub(super) fn effective_commitment(
+    config: &ExampleTokenConfig,
+    amount: u64,
+    commitment: ProposalCommitment,
+) -> ProposalCommitment {
+    if config.finalized_threshold != 0 && amount >= config.finalized_threshold {
+        ProposalCommitment::Finalized
+    } else {
+        commitment
+    }
+}
+
+/// Records a transfer of `amount` by `sender` to a refundable origin under the
+/// next nonce, which is returned to be carried in the payload. Returns `None`
+/// if the origin isn't refundable.
+pub(super) fn record_outbound<'info>(
+    config: &mut ExampleTokenConfig,
+    origin: &Origin,
+    outbound_transfer: Option<&AccountInfo<'info>>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    sender: Pubkey,
+    amount: u64,
+) -> Result<Option<u64>> {
+    if !origin.refundable {
+        return Ok(None);
+    }
+
+    let outbound_transfer = outbound_transfer.ok_or(ErrorCode::ConstraintAccountIsNone)?;
+    let nonce = config.next_transfer_nonce;
+    let nonce_bytes = nonce.to_le_bytes();
+    let (key, bump) = Pubkey::find_program_address(&[b"OUTBOUND", &nonce_bytes], &crate::ID);
+    require_keys_eq!(outbound_transfer.key(), key, ErrorCode::ConstraintSeeds);
+
+    let rent = Rent::get()?;
+    let space = 8 + OutboundTransfer::space();
+    let outbound_seeds: &[&[&[u8]]] = &[&[b"OUTBOUND", &nonce_bytes, &[bump]]];
+    let ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        CreateAccount {
+            from: payer.clone(),
+            to: outbound_transfer.clone(),
+        },
+        outbound_seeds,
+    );
+    create_account(ctx, rent.minimum_balance(space), space as u64, &crate::ID)?;
+
+    OutboundTransfer {
+        dest_chain_id: origin.chain_id,
+        sender,
+        amount,
+        closable_after: Clock::get()?
+            .unix_timestamp
+            .saturating_add_unsigned(origin.refund_window),
+    }
+    .try_serialize(&mut &mut outbound_transfer.try_borrow_mut_data()?[..])?;
+    config.next_transfer_nonce += 1;
+
+    msg!("Recorded the transfer under nonce {}", nonce);
+
+    Ok(Some(nonce))
+}
+
+/// Accounts passed to the UIP endpoint to propose a message.
+pub(super) struct ProposeAccounts<'a, 'info> {
+    pub(super) payer: &'a AccountInfo<'info>,
+    pub(super) endpoint_config: &'a AccountInfo<'info>,
+    pub(super) uts_connector: &'a AccountInfo<'info>,
+    pub(super) program_signer: &'a AccountInfo<'info>,
+    pub(super) program_signer_bump: u8,
+    pub(super) system_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> Bridge<'info> {
+    fn propose_accounts(&self, program_signer_bump: u8) -> ProposeAccounts<'_, 'info> {
+        ProposeAccounts {
+            payer: self.sender.as_ref(),
+            endpoint_config: &self.endpoint_config,
+            uts_connector: &self.uts_connector,
+            program_signer: &self.program_signer,
+            program_signer_bump,
+            system_program: self.system_program.as_ref(),
+        }
+    }
+}
+
+/// Proposes `payload` to the destination contract of the origin, paying
+/// `uip_fee` to the endpoint.
+pub(super) fn propose(
+    accounts: ProposeAccounts,
+    origin: &Origin,
+    payload: &[u8],
+    uip_fee: u64,
+    custom_gas_limit: u128,
+    commitment: ProposalCommitment,
+) -> Result<()> {
     UipEndpoint::propose()
-        .payer(ctx.accounts.sender.to_account_info())
-        .endpoint_config(ctx.accounts.endpoint_config.to_account_info())
-        .uts_connector(ctx.accounts.uts_connector.to_account_info())
-        .program_signer(ctx.accounts.program_signer.to_account_info())
-        .system_program(ctx.accounts.system_program.to_account_info())
-        .program_signer_bump(ctx.bumps.program_signer)
+        .payer(accounts.payer.clone())
+        .endpoint_config(accounts.endpoint_config.clone())
+        .uts_connector(accounts.uts_connector.clone())
+        .program_signer(accounts.program_signer.clone())
+        .system_program(accounts.system_program.clone())
+        .program_signer_bump(accounts.program_signer_bump)
         .sender(&crate::ID)
         .total_fee(uip_fee)
-        .dest_chain_id(dest_chain_id)
-        .dest_addr(dest_addr)
-        .payload(&payload)
+        .dest_chain_id(origin.chain_id)
+        .dest_addr(&origin.address)
+        .payload(payload)
         .custom_gas_limit(custom_gas_limit)
-        .proposal_commitment(Commitment::Confirmed)
+        .proposal_commitment(commitment.into())
         .call()?;
 
     Ok(())
@@ -0,0 +1,180 @@
+use crate::{
+    error::*,
+    events::*,
+    payload::AssetTransfer,
+    state::*,
+    utils::{bps_of, debit, find_ata, is_solana_chain, to_remote_amount, transfer},
+};
+use anchor_lang::prelude::*;
+use uip_solana_sdk::UipEndpoint;
+
+use super::{
+    bridge::{
+        check_amount, check_not_blocked, consume_limits, load_origin, propose, ProposeAccounts,
+    },
+    ProposalCommitment, Recipient,
+};
+
+#[derive(Accounts)]
+#[instruction(asset_id: u64, dest_chain_id: u128)]
+pub struct BridgeAsset<'info> {
+    #[account(seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(mut, seeds = [&b"ASSET"[..], &asset_id.to_le_bytes()], bump)]
+    asset: Account<'info, Asset>,
+    /// CHECK: it's derived from the destination chain and asset ids and
+    /// deserialized in the handler
+    #[account(
+        mut,
+        seeds = [&b"ORIGIN"[..], &dest_chain_id.to_le_bytes(), &asset_id.to_le_bytes()],
+        bump
+    )]
+    origin: AccountInfo<'info>,
+    #[account(mut)]
+    sender: Signer<'info>,
+    /// Required if the origin has a per-wallet daily cap.
+    #[account(
+        init_if_needed,
+        space = 8 + UserUsage::space(),
+        payer = sender,
+        seeds = [
+            &b"USER_USAGE"[..],
+            &dest_chain_id.to_le_bytes(),
+            sender.key.as_ref(),
+            &asset_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    user_usage: Option<Account<'info, UserUsage>>,
+    /// CHECK: checked in CPI
+    #[account(mut)]
+    token_account: AccountInfo<'info>,
+    /// CHECK: it's checked to be the mint of the asset
+    #[account(mut, address = asset.mint)]
+    mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the asset ATA in the lock/release mode
+    #[account(mut)]
+    vault: Option<AccountInfo<'info>>,
+    /// CHECK: it's derived from the treasury seeds
+    #[account(seeds = [b"TREASURY"], bump)]
+    treasury_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the treasury authority ATA
+    #[account(
+        mut,
+        address = find_ata(treasury_authority.key, mint.key, mint.owner)
+    )]
+    treasury: AccountInfo<'info>,
+    /// CHECK: checked in the CPI
+    endpoint_config: AccountInfo<'info>,
+    /// CHECK: checked in the CPI
+    #[account(mut)]
+    uts_connector: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    #[account(seeds = [b"UIP_SIGNER"], bump)]
+    program_signer: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the mint
+    #[account(address = *mint.owner)]
+    token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+    uip_program: Program<'info, UipEndpoint>,
+    /// CHECK: it's derived from the blocklist seeds and deserialized in the
+    /// handler
+    #[account(seeds = [b"BLOCKLIST"], bump)]
+    blocklist: AccountInfo<'info>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn bridge_asset(
+    ctx: Context<BridgeAsset>,
+    asset_id: u64,
+    dest_chain_id: u128,
+    to: Recipient,
+    amount: u64,
+    uip_fee: u64,
+    custom_gas_limit: u128,
+    commitment: ProposalCommitment,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let asset = &mut ctx.accounts.asset;
+    let sender = &ctx.accounts.sender;
+    let token_account = &ctx.accounts.token_account;
+    let mint = &ctx.accounts.mint;
+    let origin_info = &ctx.accounts.origin;
+
+    require!(
+        is_solana_chain(dest_chain_id),
+        ExampleTokenError::SolanaOnlyPayload
+    );
+
+    let mut origin = load_origin(config, origin_info)?;
+    let to = to.encode(origin.chain_id)?;
+    check_not_blocked(&ctx.accounts.blocklist, &[sender.key.as_ref(), &to])?;
+    check_amount(&origin, amount)?;
+    consume_limits(&mut origin, ctx.accounts.user_usage.as_mut(), amount)?;
+
+    let fee = bps_of(amount, config.fee_bps);
+    if fee != 0 {
+        transfer(
+            mint,
+            token_account,
+            &ctx.accounts.treasury,
+            &sender.to_account_info(),
+            asset.decimals,
+            fee,
+            &[],
+            &[],
+        )?;
+    }
+
+    let amount = debit(
+        asset,
+        mint,
+        ctx.accounts.vault.as_ref(),
+        token_account,
+        &sender.to_account_info(),
+        amount - fee,
+        &[],
+    )?;
+    origin.total_outbound += u128::from(amount);
+    origin.try_serialize(&mut &mut origin_info.try_borrow_mut_data()?[..])?;
+    let remote_amount = to_remote_amount(amount, asset.decimals, origin.decimals)?;
+
+    let payload = AssetTransfer {
+        asset_id,
+        from: sender.key.to_bytes().to_vec(),
+        to: to.clone(),
+        amount: remote_amount,
+        mint: origin.remote_mint.map(|mint| mint.to_bytes()),
+        token_2022: origin.remote_token_2022,
+    }
+    .encode();
+
+    propose(
+        ProposeAccounts {
+            payer: sender.as_ref(),
+            endpoint_config: &ctx.accounts.endpoint_config,
+            uts_connector: &ctx.accounts.uts_connector,
+            program_signer: &ctx.accounts.program_signer,
+            program_signer_bump: ctx.bumps.program_signer,
+            system_program: ctx.accounts.system_program.as_ref(),
+        },
+        &origin,
+        &payload,
+        uip_fee,
+        custom_gas_limit,
+        commitment,
+    )?;
+
+    emit!(BridgeTransfer {
+        sender: sender.key(),
+        asset_id,
+        dest_chain_id,
+        to,
+        amount,
+        fee,
+        referrer: None,
+        referrer_fee: 0,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use super::bridge::{
+    check_amount, check_not_blocked, consume_limits, effective_commitment, load_origin, propose,
+    record_outbound, ProposeAccounts,
+};
+use crate::{
+    error::*,
+    events::*,
+    instructions::{ProposalCommitment, Recipient},
+    payload::Transfer,
+    state::*,
+    utils::{bps_of, debit, find_ata, to_remote_amount, transfer, verify_ed25519},
+};
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use uip_solana_sdk::UipEndpoint;
+
+#[derive(Accounts)]
+#[instruction(permit: Permit)]
+pub struct BridgeWithPermit<'info> {
+    #[account(mut)]
+    config: Account<'info, ExampleTokenConfig>,
+    /// CHECK: it's derived from the destination chain id and deserialized in
+    /// the handler
+    #[account(mut, seeds = [&b"ORIGIN"[..], &permit.dest_chain_id.to_le_bytes()], bump)]
+    origin: AccountInfo<'info>,
+    /// CHECK: it's checked to have signed the permit
+    owner: AccountInfo<'info>,
+    /// Submits the permit and pays the rent and the UIP fee.
+    #[account(mut)]
+    relayer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 8 + PermitNonce::space(),
+        payer = relayer,
+        seeds = [b"PERMIT_NONCE", owner.key.as_ref()],
+        bump
+    )]
+    permit_nonce: Account<'info, PermitNonce>,
+    /// Required if the origin has a per-wallet daily cap.
+    #[account(
+        init_if_needed,
+        space = 8 + UserUsage::space(),
+        payer = relayer,
+        seeds = [&b"USER_USAGE"[..], &permit.dest_chain_id.to_le_bytes(), owner.key.as_ref()],
+        bump
+    )]
+    user_usage: Option<Account<'info, UserUsage>>,
+    /// CHECK: it's checked to be the outbound transfer PDA of the next nonce
+    /// and created in the handler, required if the origin is refundable
+    #[account(mut)]
+    outbound_transfer: Option<AccountInfo<'info>>,
+    /// CHECK: it's checked to be the owner ATA
+    #[account(mut, address = find_ata(owner.key, exa_mint.key, exa_mint.owner))]
+    token_account: AccountInfo<'info>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(mut, address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the config ATA in the lock/release mode
+    #[account(mut)]
+    vault: Option<AccountInfo<'info>>,
+    /// CHECK: it's derived from the permit seeds, the owner approves it as a
+    /// delegate of the token account
+    #[account(seeds = [b"PERMIT"], bump)]
+    permit_authority: AccountInfo<'info>,
+    /// CHECK: it's derived from the treasury seeds
+    #[account(seeds = [b"TREASURY"], bump)]
+    treasury_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the treasury authority ATA
+    #[account(
+        mut,
+        address = find_ata(treasury_authority.key, exa_mint.key, exa_mint.owner)
+    )]
+    treasury: AccountInfo<'info>,
+    /// CHECK: it's checked to be the instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    instructions: AccountInfo<'info>,
+    /// CHECK: checked in the CPI
+    endpoint_config: AccountInfo<'info>,
+    /// CHECK: checked in the CPI
+    #[account(mut)]
+    uts_connector: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    #[account(seeds = [b"UIP_SIGNER"], bump)]
+    program_signer: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+    uip_program: Program<'info, UipEndpoint>,
+    /// CHECK: it's derived from the blocklist seeds and deserialized in the
+    /// handler
+    #[account(seeds = [b"BLOCKLIST"], bump)]
+    blocklist: AccountInfo<'info>,
+}
+
+/// Authorization to bridge tokens signed off-chain by their owner.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Permit {
+    /// Identifier of the destination chain.
+    pub dest_chain_id: u128,
+    /// Recipient of the tokens on the destination chain.
+    pub to: Recipient,
+    /// Amount of tokens bridged before the protocol fee.
+    pub amount: u64,
+    /// Nonce of the owner, incremented with every permit used.
+    pub nonce: u64,
+    /// Unix timestamp after which the permit can't be used.
+    pub deadline: i64,
+    /// Custom gas limit of the message on the destination chain, so that the
+    /// relayer can't pick one too low for the delivery.
+    pub custom_gas_limit: u128,
+    /// Commitment the source transaction must reach, raised to finalized for
+    /// transfers above the finalized threshold.
+    pub commitment: ProposalCommitment,
+}
+
+impl Permit {
+    /// Returns the message signed by the owner, which is the Borsh-serialized
+    /// permit prefixed with `EXA_PERMIT`, the program id and the little-endian
+    /// `chain_id` of the cluster, so that it can't be replayed on another
+    /// cluster where the program is deployed at the same address.
+    pub fn message(&self, chain_id: u128) -> Result<Vec<u8>> {
+        let mut message = b"EXA_PERMIT".to_vec();
+        message.extend_from_slice(crate::ID.as_ref());
+        message.extend_from_slice(&chain_id.to_le_bytes());
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+pub fn bridge_with_permit<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BridgeWithPermit<'info>>,
+    permit: Permit,
+    uip_fee: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let owner = &ctx.accounts.owner;
+    let permit_nonce = &mut ctx.accounts.permit_nonce;
+    let token_account = &ctx.accounts.token_account;
+    let exa_mint = &ctx.accounts.exa_mint;
+    let permit_authority = &ctx.accounts.permit_authority;
+    let origin_info = &ctx.accounts.origin;
+
+    verify_ed25519(
+        &ctx.accounts.instructions,
+        owner.key,
+        &permit.message(config.chain_id)?,
+    )?;
+    require!(
+        Clock::get()?.unix_timestamp <= permit.deadline,
+        ExampleTokenError::PermitExpired
+    );
+    require_eq!(
+        permit.nonce,
+        permit_nonce.nonce,
+        ExampleTokenError::InvalidPermitNonce
+    );
+    permit_nonce.nonce += 1;
+
+    let mut origin = load_origin(config, origin_info)?;
+    let to = permit.to.encode(origin.chain_id)?;
+    check_not_blocked(&ctx.accounts.blocklist, &[owner.key.as_ref(), &to])?;
+    check_amount(&origin, permit.amount)?;
+    consume_limits(&mut origin, ctx.accounts.user_usage.as_mut(), permit.amount)?;
+    let commitment = effective_commitment(config, permit.amount, permit.commitment);
+
+    let permit_seeds: &[&[&[u8]]] = &[&[b"PERMIT", &[ctx.bumps.permit_authority]]];
+    let fee = bps_of(permit.amount, config.fee_bps);
+    if fee != 0 {
+        transfer(
+            exa_mint,
+            token_account,
+            &ctx.accounts.treasury,
+            permit_authority,
+            config.decimals,
+            fee,
+            permit_seeds,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    let amount = debit(
+        config,
+        exa_mint,
+        ctx.accounts.vault.as_ref(),
+        token_account,
+        permit_authority,
+        permit.amount - fee,
+        permit_seeds,
+    )?;
+    origin.total_outbound += u128::from(amount);
+    origin.try_serialize(&mut &mut origin_info.try_borrow_mut_data()?[..])?;
+    let remote_amount = to_remote_amount(amount, config.decimals, origin.decimals)?;
+    let nonce = record_outbound(
+        config,
+        &origin,
+        ctx.accounts.outbound_transfer.as_ref(),
+        ctx.accounts.relayer.as_ref(),
+        ctx.accounts.system_program.as_ref(),
+        owner.key(),
+        amount,
+    )?;
+
+    let payload = Transfer {
+        from: owner.key.to_bytes().to_vec(),
+        to: to.clone(),
+        amount: remote_amount,
+        call: None,
+        nonce,
+    }
+    .encode();
+
+    propose(
+        ProposeAccounts {
+            payer: ctx.accounts.relayer.as_ref(),
+            endpoint_config: &ctx.accounts.endpoint_config,
+            uts_connector: &ctx.accounts.uts_connector,
+            program_signer: &ctx.accounts.program_signer,
+            program_signer_bump: ctx.bumps.program_signer,
+            system_program: ctx.accounts.system_program.as_ref(),
+        },
+        &origin,
+        &payload,
+        uip_fee,
+        permit.custom_gas_limit,
+        commitment,
+    )?;
+
+    emit!(BridgeTransfer {
+        sender: owner.key(),
+        asset_id: 0,
+        dest_chain_id: permit.dest_chain_id,
+        to,
+        amount,
+        fee,
+        referrer: None,
+        referrer_fee: 0,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::{
+    error::*,
+    events::*,
+    state::*,
+    utils::{create_ata_if_needed, credit, find_ata},
+};
+use anchor_lang::prelude::*;
+
+use super::execute::create_escrow;
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32])]
+pub struct CancelForward<'info> {
+    #[account(
+        mut,
+        seeds = [b"CONFIG"],
+        bump,
+        constraint = authority.key() == config.admin || authority.key() == config.guardian
+            @ ExampleTokenError::NotGuardian,
+    )]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        seeds = [&b"FORWARD"[..], &msg_hash],
+        bump
+    )]
+    pending_forward: Account<'info, PendingForward>,
+    #[account(mut, seeds = [&b"RECEIPT"[..], &msg_hash], bump)]
+    receipt: Account<'info, Receipt>,
+    /// CHECK: it's checked to be the account that paid for the pending forward
+    #[account(mut)]
+    rent_payer: AccountInfo<'info>,
+    /// CHECK: it's derived from the message hash and created in the handler
+    #[account(mut, seeds = [&b"ESCROW"[..], &msg_hash], bump)]
+    escrow: AccountInfo<'info>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(mut, address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the config ATA in the lock/release mode
+    #[account(mut)]
+    vault: Option<AccountInfo<'info>>,
+    /// CHECK: it's derived from the escrow seeds
+    #[account(seeds = [b"ESCROW"], bump)]
+    escrow_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the escrow authority ATA
+    #[account(
+        mut,
+        address = find_ata(escrow_authority.key, exa_mint.key, exa_mint.owner)
+    )]
+    escrow_vault: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Escrows the tokens of a transfer stuck awaiting `forward`, e.g. because the
+/// final destination chain was removed or its limits can't be met. The escrow
+/// of a bounced transfer keeps its recipient, while that of a forwarded one
+/// has no recipient on Solana, so that only the admin can recover it. Can be
+/// called by the admin or the guardian.
+pub fn cancel_forward(ctx: Context<CancelForward>, msg_hash: [u8; 32]) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let authority = &ctx.accounts.authority;
+    let pending_forward = &ctx.accounts.pending_forward;
+    let exa_mint = &ctx.accounts.exa_mint;
+    let escrow_vault = &ctx.accounts.escrow_vault;
+
+    let to = match pending_forward.refund_nonce {
+        Some(_) => pending_forward.to.clone(),
+        None => Vec::new(),
+    };
+    create_escrow(
+        authority,
+        &ctx.accounts.escrow,
+        &ctx.accounts.system_program,
+        &[b"ESCROW", &msg_hash, &[ctx.bumps.escrow]],
+        Escrow {
+            src_chain_id: pending_forward.src_chain_id,
+            mint: exa_mint.key(),
+            from: pending_forward.from.clone(),
+            to,
+            amount: pending_forward.amount,
+            rent_payer: authority.key(),
+            claimable_after: Clock::get()?.unix_timestamp,
+        },
+    )?;
+    ctx.accounts.receipt.status = ReceiptStatus::Escrowed;
+
+    create_ata_if_needed(
+        authority,
+        escrow_vault,
+        &ctx.accounts.escrow_authority,
+        exa_mint,
+    )?;
+    credit(
+        config,
+        &[b"CONFIG", &[ctx.bumps.config]],
+        exa_mint,
+        ctx.accounts.vault.as_ref(),
+        escrow_vault,
+        pending_forward.amount,
+    )?;
+
+    emit!(ForwardCancelled {
+        msg_hash,
+        src_chain_id: pending_forward.src_chain_id,
+        dest_chain_id: pending_forward.dest_chain_id,
+        from: pending_forward.from.clone(),
+        to: pending_forward.to.clone(),
+        amount: pending_forward.amount,
+        authority: authority.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::{error::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32])]
+pub struct CancelQueuedTransfer<'info> {
+    #[account(
+        seeds = [b"CONFIG"],
+        bump,
+        constraint = authority.key() == config.guardian
+            || roles.has_role(Role::Pauser, authority.key) @ ExampleTokenError::NotGuardian,
+    )]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(seeds = [b"ROLES"], bump)]
+    roles: Account<'info, Roles>,
+    authority: Signer<'info>,
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        seeds = [&b"QUEUED"[..], &msg_hash],
+        bump
+    )]
+    queued_transfer: Account<'info, QueuedTransfer>,
+    #[account(mut, seeds = [&b"RECEIPT"[..], &queued_transfer.msg_hash], bump)]
+    receipt: Account<'info, Receipt>,
+    /// CHECK: it's checked to be the account that paid for the queued transfer
+    #[account(mut)]
+    rent_payer: AccountInfo<'info>,
+}
+
+/// Cancels a suspicious queued transfer, so that its tokens are never minted.
+/// A queued transfer of a batch is cancelled by its batch key in place of the
+/// message hash. Can be called by the guardian or a pauser.
+pub fn cancel_queued_transfer(
+    ctx: Context<CancelQueuedTransfer>,
+    msg_hash: [u8; 32],
+) -> Result<()> {
+    let queued_transfer = &ctx.accounts.queued_transfer;
+
+    ctx.accounts.receipt.status = ReceiptStatus::Cancelled;
+
+    emit!(TransferCancelled {
+        msg_hash,
+        src_chain_id: queued_transfer.src_chain_id,
+        to: queued_transfer.to,
+        amount: queued_transfer.amount,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
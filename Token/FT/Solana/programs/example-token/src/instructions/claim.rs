@@ -0,0 +1,100 @@
+use crate::{
+    error::*,
+    state::*,
+    utils::{find_ata, mint_decimals, transfer},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32])]
+pub struct Claim<'info> {
+    recipient: Signer<'info>,
+    #[account(seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        seeds = [&b"ESCROW"[..], &msg_hash],
+        bump,
+        constraint = escrow.recipient() == Some(recipient.key())
+            @ ExampleTokenError::NotEscrowRecipient,
+    )]
+    escrow: Account<'info, Escrow>,
+    /// CHECK: it's checked to be the account that paid for the escrow
+    #[account(mut)]
+    rent_payer: AccountInfo<'info>,
+    /// CHECK: it's derived from the escrow seeds
+    #[account(seeds = [b"ESCROW"], bump)]
+    escrow_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the escrow authority ATA
+    #[account(
+        mut,
+        address = find_ata(escrow_authority.key, exa_mint.key, exa_mint.owner)
+    )]
+    escrow_vault: AccountInfo<'info>,
+    /// CHECK: it's checked to be the mint of the escrowed tokens
+    #[account(address = escrow.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    #[account(mut)]
+    destination: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's derived from the blocklist seeds and deserialized in the
+    /// handler
+    #[account(seeds = [b"BLOCKLIST"], bump)]
+    blocklist: AccountInfo<'info>,
+    /// CHECK: it's checked to be the attestation PDA of the recipient if
+    /// attestations are required
+    attestation: AccountInfo<'info>,
+}
+
+/// Transfers the tokens of an undelivered incoming transfer to a token
+/// account chosen by its recipient, unless inbound transfers from the source
+/// chain are paused, the sender or the recipient is blocklisted or the
+/// recipient lacks a required attestation.
+pub fn claim<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Claim<'info>>,
+    _msg_hash: [u8; 32],
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let escrow = &ctx.accounts.escrow;
+    let recipient = ctx.accounts.recipient.key();
+
+    require!(!config.inbound_paused, ExampleTokenError::InboundPaused);
+    require!(
+        !config.is_route_paused(escrow.src_chain_id),
+        ExampleTokenError::RoutePaused
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= escrow.claimable_after,
+        ExampleTokenError::SettlementDelayNotElapsed
+    );
+
+    let blocklist = Blocklist::load(&ctx.accounts.blocklist)?;
+    require!(
+        !blocklist.contains(&escrow.from) && !blocklist.contains(recipient.as_ref()),
+        ExampleTokenError::Blocked
+    );
+    require!(
+        Attestation::is_satisfied(config, &ctx.accounts.attestation, &recipient)?,
+        ExampleTokenError::MissingAttestation
+    );
+
+    transfer(
+        &ctx.accounts.exa_mint,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.destination,
+        &ctx.accounts.escrow_authority,
+        mint_decimals(&ctx.accounts.exa_mint)?,
+        escrow.amount,
+        &[&[b"ESCROW", &[ctx.bumps.escrow_authority]]],
+        ctx.remaining_accounts,
+    )?;
+
+    msg!("{} claimed {} escrowed tokens", recipient, escrow.amount);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CloseOutboundTransfer<'info> {
+    #[account(mut)]
+    sender: Signer<'info>,
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender,
+        seeds = [&b"OUTBOUND"[..], &nonce.to_le_bytes()],
+        bump
+    )]
+    outbound_transfer: Account<'info, OutboundTransfer>,
+}
+
+/// Closes the record of a delivered outgoing transfer, returning its rent to
+/// the sender. The transfer can't be refunded anymore. Deliveries aren't
+/// reported back, so the record can only be closed once the refund window of
+/// the destination chain has elapsed, while a refund closes it right away.
+pub fn close_outbound_transfer(ctx: Context<CloseOutboundTransfer>, _nonce: u64) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.outbound_transfer.closable_after,
+        ExampleTokenError::RefundWindowNotElapsed
+    );
+
+    Ok(())
+}
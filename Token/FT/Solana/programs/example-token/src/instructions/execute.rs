@@ -1,13 +1,21 @@
-use crate::{addresses::*, error::*, state::*, utils::find_ata};
-use alloy_sol_types::{
-    sol_data::{Bytes, Uint},
-    SolType,
+use crate::{
+    error::*,
+    events::*,
+    payload::{AssetTransfer, Call, Refund, Transfer, TransferBatch, TransferForward},
+    state::*,
+    utils::{
+        create_ata_if_needed, credit, find_ata, is_deliverable, round_to_remote, to_local_amount,
+    },
 };
-use anchor_lang::prelude::*;
-use solana_invoke::{invoke, invoke_signed};
-use spl_associated_token_account::instruction::create_associated_token_account;
-use spl_token::instruction::mint_to;
-use uip_solana_sdk::{chains::*, parse_uip_message, route_instruction, MessageDataRef};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+    system_program::{create_account, CreateAccount},
+};
+use ruint::aliases::U256;
+use uip_solana_sdk::{parse_uip_message, route_instruction, MessageDataRef};
+
+use super::bridge::{load_origin, Recipient};
 
 #[derive(Accounts)]
 pub struct Execute<'info> {
@@ -21,122 +29,1434 @@ pub fn execute<'info>(ctx: Context<'_, '_, 'info, 'info, Execute>) -> Result<()>
         payload,
         sender_addr,
         src_chain_id,
+        msg_hash,
         ..
     } = parse_uip_message(&ctx.accounts.uip_msg, &uip_msg_data, &crate::ID)?;
 
-    #[cfg(not(feature = "mainnet"))]
-    let allowed_origins = [
-        (&SOLANA_DEVNET_CHAIN_ID, &crate::ID.to_bytes()[..]),
-        (&POLYGON_AMOY_CHAIN_ID, &POLYGON_AMOY_ADDRESS),
-        (&MANTLE_SEPOLIA_CHAIN_ID, &MANTLE_SEPOLIA_ADDRESS),
-        (&TEIB_CHAIN_ID, &TEIB_ADDRESS),
-    ];
-    #[cfg(feature = "mainnet")]
-    let allowed_origins = [(&SOLANA_MAINNET_CHAIN_ID, &crate::ID.to_bytes()[..])];
-    require!(
-        allowed_origins.contains(&(&src_chain_id, sender_addr)),
-        ExampleTokenError::SenderSmartContractNotAllowed
-    );
-
     msg!("CCM instruction: ReceiveMessage");
 
-    let (from, to, amount) = <(Bytes, Bytes, Uint<256>)>::abi_decode_params(payload, true)
-        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    if let Some(batch) = TransferBatch::decode(payload) {
+        route_batch_instruction(
+            ctx.remaining_accounts,
+            BridgeMintBatchIxData {
+                src_chain_id,
+                msg_hash: *msg_hash,
+                from_len: batch.from.len() as _,
+            },
+            BridgeMintBatchParams {
+                msg_hash,
+                sender_addr,
+                batch: &batch,
+            },
+        )?;
+
+        return Ok(());
+    }
+
+    if let Some(transfer) = AssetTransfer::decode(payload) {
+        route_instruction(
+            &crate::ID,
+            bridge_mint_asset,
+            ctx.remaining_accounts,
+            BridgeMintAssetIxData {
+                src_chain_id,
+                msg_hash: *msg_hash,
+                asset_id: transfer.asset_id,
+                from_len: transfer.from.len() as _,
+                to_len: transfer.to.len() as _,
+            },
+            BridgeMintAssetParams {
+                msg_hash,
+                sender_addr,
+                transfer: &transfer,
+            },
+        )?;
+
+        return Ok(());
+    }
+
+    if let Some(transfer) = TransferForward::decode(payload) {
+        route_instruction(
+            &crate::ID,
+            bridge_forward,
+            ctx.remaining_accounts,
+            BridgeForwardIxData {
+                src_chain_id,
+                msg_hash: *msg_hash,
+                dest_chain_id: transfer.dest_chain_id,
+                from_len: transfer.from.len() as _,
+                to_len: transfer.to.len() as _,
+            },
+            BridgeForwardParams {
+                sender_addr,
+                transfer: &transfer,
+            },
+        )?;
+
+        return Ok(());
+    }
+
+    if let Some(refund) = Refund::decode(payload) {
+        route_instruction(
+            &crate::ID,
+            bridge_refund,
+            ctx.remaining_accounts,
+            BridgeRefundIxData {
+                src_chain_id,
+                msg_hash: *msg_hash,
+                nonce: refund.nonce,
+                from_len: refund.from.len() as _,
+            },
+            BridgeRefundParams {
+                msg_hash,
+                sender_addr,
+                refund: &refund,
+            },
+        )?;
 
-    let amount = amount
-        .try_into()
-        .map_err(|_| ProgramError::InvalidInstructionData)?;
-    let to: Pubkey = (&to as &[u8])
-        .try_into()
-        .map_err(|_| ProgramError::InvalidInstructionData)?;
+        return Ok(());
+    }
+
+    let transfer = Transfer::decode(payload).ok_or(ProgramError::InvalidInstructionData)?;
 
     route_instruction(
         &crate::ID,
         bridge_mint,
         ctx.remaining_accounts,
-        (),
-        BridgeMintParams { to, amount },
+        BridgeMintIxData {
+            src_chain_id,
+            msg_hash: *msg_hash,
+            from_len: transfer.from.len() as _,
+            to_len: transfer.to.len() as _,
+        },
+        BridgeMintParams {
+            msg_hash,
+            sender_addr,
+            from: &transfer.from,
+            to: &transfer.to,
+            amount: transfer.amount,
+            call: transfer.call.as_ref(),
+            nonce: transfer.nonce,
+        },
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ix_data: BridgeMintIxData)]
+struct BridgeMint<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut, seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        mut,
+        seeds = [&b"ORIGIN"[..], &ix_data.src_chain_id.to_le_bytes()],
+        bump,
+    )]
+    origin: Account<'info, Origin>,
+    /// CHECK: it's derived from the message hash and only created if the
+    /// transfer exceeds the inbound limit
+    #[account(mut, seeds = [&b"QUEUED"[..], &ix_data.msg_hash], bump)]
+    queued_transfer: AccountInfo<'info>,
+    /// CHECK: it's derived from the message hash and only created if the
+    /// transfer cannot be delivered
+    #[account(mut, seeds = [&b"ESCROW"[..], &ix_data.msg_hash], bump)]
+    escrow: AccountInfo<'info>,
+    /// CHECK: it's derived from the message hash and only created if a
+    /// refundable transfer cannot be delivered
+    #[account(mut, seeds = [&b"FORWARD"[..], &ix_data.msg_hash], bump)]
+    pending_forward: AccountInfo<'info>,
+    #[account(
+        init,
+        space = 8 + Receipt::space(ix_data.from_len as _, ix_data.to_len as _),
+        payer = payer,
+        seeds = [&b"RECEIPT"[..], &ix_data.msg_hash],
+        bump,
+    )]
+    receipt: Account<'info, Receipt>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(mut, address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the config ATA in the lock/release mode
+    #[account(mut)]
+    vault: Option<AccountInfo<'info>>,
+    /// CHECK: it's derived from the escrow seeds
+    #[account(seeds = [b"ESCROW"], bump)]
+    escrow_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the escrow authority ATA
+    #[account(
+        mut,
+        address = find_ata(escrow_authority.key, exa_mint.key, exa_mint.owner)
+    )]
+    escrow_vault: AccountInfo<'info>,
+    /// CHECK: it's checked to be the ATA of `to`, or of the caller PDA for a
+    /// call, if `to` is valid, its writability is checked in CPI
+    token_account: AccountInfo<'info>,
+    /// CHECK: it's checked in CPI, it's the caller PDA for a call
+    to: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+    /// CHECK: it's derived from the blocklist seeds and deserialized in the
+    /// handler
+    #[account(seeds = [b"BLOCKLIST"], bump)]
+    blocklist: AccountInfo<'info>,
+    /// CHECK: it's checked to be the attestation PDA of the recipient if
+    /// attestations are required
+    attestation: AccountInfo<'info>,
+}
+
+/// Data for use in the anchor `instruction` attribute.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct BridgeMintIxData {
+    src_chain_id: u128,
+    msg_hash: [u8; 32],
+    from_len: u64,
+    to_len: u64,
+}
+
+/// Input for the `bridge_mint` function.
+struct BridgeMintParams<'a> {
+    msg_hash: &'a [u8; 32],
+    sender_addr: &'a [u8],
+    from: &'a [u8],
+    to: &'a [u8],
+    amount: U256,
+    call: Option<&'a Call>,
+    nonce: Option<u64>,
+}
+
+fn bridge_mint(ctx: Context<BridgeMint>, params: BridgeMintParams) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let config = &mut ctx.accounts.config;
+    let origin = &mut ctx.accounts.origin;
+    let queued_transfer = &ctx.accounts.queued_transfer;
+    let exa_mint = &ctx.accounts.exa_mint;
+    let token_account = &ctx.accounts.token_account;
+    let to = &ctx.accounts.to;
+    let system_program = &ctx.accounts.system_program;
+    let receipt = &mut ctx.accounts.receipt;
+
+    check_sender(config, origin, params.sender_addr)?;
+
+    let clock = Clock::get()?;
+    receipt.src_chain_id = origin.chain_id;
+    receipt.sender = params.from.to_vec();
+    receipt.recipient = params.to.to_vec();
+    receipt.slot = clock.slot;
+
+    let Some(amount) = to_local_amount(params.amount, config.decimals, origin.decimals) else {
+        return reject_transfer(receipt, params.amount);
+    };
+    receipt.amount = amount;
+    receipt.status = ReceiptStatus::Delivered;
+
+    // The tokens of a transfer with a call go to the caller PDA, while `to`
+    // has to pass the compliance checks and gets them if they're not delivered.
+    let caller = params
+        .call
+        .map(|_| find_caller(origin.chain_id, params.from).0);
+
+    // The accounts of a valid recipient are checked before its
+    // deliverability, so that an executor can't force the escrow by passing
+    // other ones.
+    let recipient = Pubkey::try_from(params.to).ok();
+    if let Some(recipient) = recipient {
+        let owner = caller.unwrap_or(recipient);
+        require!(
+            to.key() == owner
+                && token_account.key() == find_ata(&owner, exa_mint.key, exa_mint.owner),
+            ErrorCode::ConstraintAddress
+        );
+    }
+
+    let blocklist = Blocklist::load(&ctx.accounts.blocklist)?;
+    let recipient = match recipient {
+        Some(recipient)
+            if is_deliverable(to, token_account, exa_mint)
+                && is_compliant(
+                    config,
+                    &blocklist,
+                    &ctx.accounts.attestation,
+                    params.from,
+                    &recipient,
+                )? =>
+        {
+            Some(recipient)
+        }
+        _ => None,
+    };
+    let delayed = config.is_delayed(amount);
+    let Some(recipient) = recipient else {
+        // A refundable transfer is bounced back to the source chain instead,
+        // which is proposed by `forward` like a forwarded transfer.
+        if let (Some(nonce), Some(_)) = (params.nonce, config.forward_gas_limit) {
+            let pending_forward = &ctx.accounts.pending_forward;
+            let data = PendingForward {
+                src_chain_id: origin.chain_id,
+                dest_chain_id: origin.chain_id,
+                from: params.from.to_vec(),
+                to: params.to.to_vec(),
+                amount,
+                rent_payer: payer.key(),
+                refund_nonce: Some(nonce),
+            };
+
+            let rent = Rent::get()?;
+            let space = 8 + PendingForward::space(data.from.len(), data.to.len());
+            let forward_seeds: &[&[&[u8]]] =
+                &[&[b"FORWARD", params.msg_hash, &[ctx.bumps.pending_forward]]];
+            let ctx2 = CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: pending_forward.to_account_info(),
+                },
+                forward_seeds,
+            );
+            create_account(ctx2, rent.minimum_balance(space), space as u64, &crate::ID)?;
+            data.try_serialize(&mut &mut pending_forward.try_borrow_mut_data()?[..])?;
+            origin.total_inbound += u128::from(amount);
+            receipt.status = ReceiptStatus::Bounced;
+
+            msg!(
+                "Bouncing {} tokens for {} back to {} with nonce {}",
+                amount,
+                hex::encode(params.to),
+                hex::encode(params.from),
+                nonce
+            );
+
+            return Ok(());
+        }
+
+        // An escrowed transfer consumes the inbound limit right away, as it
+        // isn't released later, and can only be claimed once the settlement
+        // delay of a transfer above the threshold has elapsed.
+        require!(
+            origin
+                .inbound_limit
+                .try_consume_capped(amount, clock.unix_timestamp),
+            ExampleTokenError::InboundRateLimitExceeded
+        );
+        let claimable_after = match delayed {
+            true => clock
+                .unix_timestamp
+                .saturating_add_unsigned(config.settlement_delay),
+            false => clock.unix_timestamp,
+        };
+
+        let escrow = &ctx.accounts.escrow;
+        let escrow_vault = &ctx.accounts.escrow_vault;
+
+        create_escrow(
+            payer,
+            escrow,
+            system_program,
+            &[b"ESCROW", params.msg_hash, &[ctx.bumps.escrow]],
+            Escrow {
+                src_chain_id: origin.chain_id,
+                mint: exa_mint.key(),
+                from: params.from.to_vec(),
+                to: params.to.to_vec(),
+                amount,
+                rent_payer: payer.key(),
+                claimable_after,
+            },
+        )?;
+        receipt.status = ReceiptStatus::Escrowed;
+
+        create_ata_if_needed(
+            payer,
+            escrow_vault,
+            &ctx.accounts.escrow_authority,
+            exa_mint,
+        )?;
+        credit(
+            config,
+            &[b"CONFIG", &[ctx.bumps.config]],
+            exa_mint,
+            ctx.accounts.vault.as_ref(),
+            escrow_vault,
+            amount,
+        )?;
+        origin.total_inbound += u128::from(amount);
+
+        msg!(
+            "Escrowed {} tokens for {} from {}",
+            amount,
+            hex::encode(params.to),
+            hex::encode(params.from)
+        );
+        if params.call.is_some() {
+            msg!("Skipped the call as the tokens were not delivered");
+        }
+
+        return Ok(());
+    };
+
+    let owner = caller.unwrap_or(recipient);
+
+    // A transfer above the settlement threshold is queued without consuming
+    // the inbound limit, which is consumed on release.
+    if delayed
+        || !origin
+            .inbound_limit
+            .try_consume(amount, clock.unix_timestamp)
+    {
+        // A queued transfer is released without the call, so a transfer with
+        // a call exceeding the inbound limit fails and the whole message is
+        // retried later instead. One above the settlement threshold would fail
+        // until the threshold is raised, so it's queued for `to` and the call
+        // is skipped.
+        if params.call.is_some() && !delayed {
+            return err!(ExampleTokenError::InboundRateLimitExceeded);
+        }
+
+        let release_after = match delayed {
+            true => clock
+                .unix_timestamp
+                .saturating_add_unsigned(config.settlement_delay),
+            false => clock.unix_timestamp,
+        };
+        create_queued_transfer(
+            payer,
+            queued_transfer,
+            system_program,
+            &[b"QUEUED", params.msg_hash, &[ctx.bumps.queued_transfer]],
+            QueuedTransfer {
+                msg_hash: *params.msg_hash,
+                src_chain_id: origin.chain_id,
+                asset_id: None,
+                from: params.from.to_vec(),
+                to: recipient,
+                amount,
+                rent_payer: payer.key(),
+                release_after,
+            },
+        )?;
+        receipt.status = ReceiptStatus::Queued;
+
+        msg!(
+            "Queued {} tokens for {} from {} until {}",
+            amount,
+            recipient,
+            hex::encode(params.from),
+            release_after
+        );
+        if params.call.is_some() {
+            msg!("Skipped the call as the tokens were queued");
+        }
+
+        return Ok(());
+    }
+
+    create_ata_if_needed(payer, token_account, to, exa_mint)?;
+    credit(
+        config,
+        &[b"CONFIG", &[ctx.bumps.config]],
+        exa_mint,
+        ctx.accounts.vault.as_ref(),
+        token_account,
+        amount,
     )?;
+    origin.total_inbound += u128::from(amount);
 
     msg!(
         "{} received {} tokens from {}",
-        to,
+        owner,
         amount,
-        hex::encode(from)
+        hex::encode(params.from)
     );
 
+    if let Some(call) = params.call {
+        invoke_call(call, origin.chain_id, params.from, ctx.remaining_accounts)?;
+    }
+
     Ok(())
 }
 
 #[derive(Accounts)]
-struct BridgeMint<'info> {
+#[instruction(ix_data: BridgeMintBatchIxData)]
+struct BridgeMintBatch<'info> {
     #[account(mut)]
     payer: Signer<'info>,
-    #[account(seeds = [b"CONFIG"], bump)]
+    #[account(mut, seeds = [b"CONFIG"], bump)]
     config: Account<'info, ExampleTokenConfig>,
-    /// CHECK: it's checked to be the EXA mint
-    #[account(mut, seeds = [b"EXA_MINT"], bump)]
+    #[account(
+        mut,
+        seeds = [&b"ORIGIN"[..], &ix_data.src_chain_id.to_le_bytes()],
+        bump,
+    )]
+    origin: Account<'info, Origin>,
+    #[account(
+        init,
+        space = 8 + Receipt::space(ix_data.from_len as _, 0),
+        payer = payer,
+        seeds = [&b"RECEIPT"[..], &ix_data.msg_hash],
+        bump,
+    )]
+    receipt: Account<'info, Receipt>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(mut, address = config.mint)]
     exa_mint: AccountInfo<'info>,
-    /// CHECK: it's checked to be the `to` ATA
+    /// CHECK: it's checked to be the config ATA in the lock/release mode
     #[account(mut)]
+    vault: Option<AccountInfo<'info>>,
+    /// CHECK: it's derived from the escrow seeds
+    #[account(seeds = [b"ESCROW"], bump)]
+    escrow_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the escrow authority ATA
+    #[account(
+        mut,
+        address = find_ata(escrow_authority.key, exa_mint.key, exa_mint.owner)
+    )]
+    escrow_vault: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+    /// CHECK: it's derived from the blocklist seeds and deserialized in the
+    /// handler
+    #[account(seeds = [b"BLOCKLIST"], bump)]
+    blocklist: AccountInfo<'info>,
+}
+
+/// Data for use in the anchor `instruction` attribute.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct BridgeMintBatchIxData {
+    src_chain_id: u128,
+    msg_hash: [u8; 32],
+    from_len: u64,
+}
+
+/// Input for the `bridge_mint_batch` function.
+struct BridgeMintBatchParams<'a> {
+    msg_hash: &'a [u8; 32],
+    sender_addr: &'a [u8],
+    batch: &'a TransferBatch,
+}
+
+/// Same as `route_instruction` for `bridge_mint_batch`, which needs the
+/// remaining accounts to share the lifetime of the instruction accounts to
+/// pass them together in CPIs.
+fn route_batch_instruction<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    ix_data: BridgeMintBatchIxData,
+    params: BridgeMintBatchParams,
+) -> Result<()> {
+    let data = ix_data.try_to_vec()?;
+    let remaining_accounts = &mut &accounts[..];
+    let mut bumps = Default::default();
+    let mut accounts = BridgeMintBatch::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &data,
+        &mut bumps,
+        &mut Default::default(),
+    )?;
+    bridge_mint_batch(
+        Context::new(&crate::ID, &mut accounts, remaining_accounts, bumps),
+        params,
+    )?;
+    accounts.exit(&crate::ID)
+}
+
+/// Delivers the tokens of a batch transfer. The remaining accounts are
+/// expected to be the escrow, the queued transfer, the token account, the
+/// recipient and its attestation for each transfer of the batch. A batch
+/// exceeding the inbound limit fails so that the whole message is retried
+/// later, while a batch whose total is above the settlement threshold is
+/// queued transfer by transfer.
+fn bridge_mint_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BridgeMintBatch<'info>>,
+    params: BridgeMintBatchParams,
+) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let config = &mut ctx.accounts.config;
+    let origin = &mut ctx.accounts.origin;
+    let exa_mint = &ctx.accounts.exa_mint;
+    let escrow_vault = &ctx.accounts.escrow_vault;
+    let system_program = &ctx.accounts.system_program;
+    let receipt = &mut ctx.accounts.receipt;
+    let transfers = &params.batch.transfers;
+
+    check_sender(config, origin, params.sender_addr)?;
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        5 * transfers.len(),
+        ErrorCode::AccountNotEnoughKeys
+    );
+    let blocklist = Blocklist::load(&ctx.accounts.blocklist)?;
+
+    let clock = Clock::get()?;
+    receipt.src_chain_id = origin.chain_id;
+    receipt.sender = params.batch.from.clone();
+    receipt.recipient = Vec::new();
+    receipt.slot = clock.slot;
+
+    // The whole batch is rejected if any amount or the total doesn't fit, so
+    // that the recipients are treated alike.
+    let amounts = transfers
+        .iter()
+        .map(|(_, amount)| to_local_amount(*amount, config.decimals, origin.decimals))
+        .collect::<Option<Vec<_>>>();
+    let Some((amounts, total)) = amounts.and_then(|amounts| {
+        let total = amounts
+            .iter()
+            .try_fold(0u64, |total, &amount| total.checked_add(amount))?;
+        Some((amounts, total))
+    }) else {
+        let total = transfers.iter().fold(U256::ZERO, |total, (_, amount)| {
+            total.saturating_add(*amount)
+        });
+        return reject_transfer(receipt, total);
+    };
+    receipt.amount = total;
+    receipt.status = ReceiptStatus::Delivered;
+
+    // The threshold applies to the total, so that it can't be avoided by
+    // splitting a transfer across recipients, and the inbound limit of the
+    // queued transfers is consumed on release.
+    let delayed = config.is_delayed(total);
+    require!(
+        delayed
+            || origin
+                .inbound_limit
+                .try_consume(total, clock.unix_timestamp),
+        ExampleTokenError::InboundRateLimitExceeded
+    );
+
+    let mut credited = 0;
+    for (index, (((to_bytes, _), amount), accounts)) in transfers
+        .iter()
+        .zip(amounts)
+        .zip(ctx.remaining_accounts.chunks_exact(5))
+        .enumerate()
+    {
+        let [escrow, queued_transfer, token_account, to, attestation] = accounts else {
+            unreachable!();
+        };
+        let key = Escrow::batch_key(params.msg_hash, index as u8);
+
+        let recipient = Pubkey::try_from(&to_bytes[..]).ok();
+        if let Some(recipient) = recipient {
+            require!(
+                to.key() == recipient
+                    && token_account.key() == find_ata(&recipient, exa_mint.key, exa_mint.owner),
+                ErrorCode::ConstraintAddress
+            );
+        }
+
+        let recipient = match recipient {
+            Some(recipient)
+                if is_deliverable(to, token_account, exa_mint)
+                    && is_compliant(
+                        config,
+                        &blocklist,
+                        attestation,
+                        &params.batch.from,
+                        &recipient,
+                    )? =>
+            {
+                Some(recipient)
+            }
+            _ => None,
+        };
+        if let Some(recipient) = recipient {
+            if delayed {
+                let (queued_key, queued_bump) =
+                    Pubkey::find_program_address(&[b"QUEUED", &key], &crate::ID);
+                require_keys_eq!(
+                    queued_transfer.key(),
+                    queued_key,
+                    ErrorCode::ConstraintSeeds
+                );
+
+                let release_after = clock
+                    .unix_timestamp
+                    .saturating_add_unsigned(config.settlement_delay);
+                create_queued_transfer(
+                    payer,
+                    queued_transfer,
+                    system_program,
+                    &[b"QUEUED", &key, &[queued_bump]],
+                    QueuedTransfer {
+                        msg_hash: *params.msg_hash,
+                        src_chain_id: origin.chain_id,
+                        asset_id: None,
+                        from: params.batch.from.clone(),
+                        to: recipient,
+                        amount,
+                        rent_payer: payer.key(),
+                        release_after,
+                    },
+                )?;
+                receipt.status = ReceiptStatus::Queued;
+
+                msg!(
+                    "Queued {} tokens for {} from {} until {} under {}",
+                    amount,
+                    recipient,
+                    hex::encode(&params.batch.from),
+                    release_after,
+                    hex::encode(key)
+                );
+                continue;
+            }
+
+            create_ata_if_needed(payer, token_account, to, exa_mint)?;
+            credit(
+                config,
+                &[b"CONFIG", &[ctx.bumps.config]],
+                exa_mint,
+                ctx.accounts.vault.as_ref(),
+                token_account,
+                amount,
+            )?;
+            credited += amount;
+
+            msg!(
+                "{} received {} tokens from {}",
+                recipient,
+                amount,
+                hex::encode(&params.batch.from)
+            );
+            continue;
+        }
+
+        // The escrowed transfers of a delayed batch consume the inbound limit
+        // right away, as they aren't released later.
+        let claimable_after = match delayed {
+            true => {
+                require!(
+                    origin
+                        .inbound_limit
+                        .try_consume_capped(amount, clock.unix_timestamp),
+                    ExampleTokenError::InboundRateLimitExceeded
+                );
+                clock
+                    .unix_timestamp
+                    .saturating_add_unsigned(config.settlement_delay)
+            }
+            false => clock.unix_timestamp,
+        };
+
+        let (escrow_key, escrow_bump) =
+            Pubkey::find_program_address(&[b"ESCROW", &key], &crate::ID);
+        require_keys_eq!(escrow.key(), escrow_key, ErrorCode::ConstraintSeeds);
+
+        create_escrow(
+            payer,
+            escrow,
+            system_program,
+            &[b"ESCROW", &key, &[escrow_bump]],
+            Escrow {
+                src_chain_id: origin.chain_id,
+                mint: exa_mint.key(),
+                from: params.batch.from.clone(),
+                to: to_bytes.clone(),
+                amount,
+                rent_payer: payer.key(),
+                claimable_after,
+            },
+        )?;
+        // A queued transfer outweighs an escrowed one in the receipt, as it
+        // can still be cancelled.
+        if receipt.status != ReceiptStatus::Queued {
+            receipt.status = ReceiptStatus::Escrowed;
+        }
+
+        create_ata_if_needed(
+            payer,
+            escrow_vault,
+            &ctx.accounts.escrow_authority,
+            exa_mint,
+        )?;
+        credit(
+            config,
+            &[b"CONFIG", &[ctx.bumps.config]],
+            exa_mint,
+            ctx.accounts.vault.as_ref(),
+            escrow_vault,
+            amount,
+        )?;
+        credited += amount;
+
+        msg!(
+            "Escrowed {} tokens for {} from {} under {}",
+            amount,
+            hex::encode(to_bytes),
+            hex::encode(&params.batch.from),
+            hex::encode(key)
+        );
+    }
+    origin.total_inbound += u128::from(credited);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ix_data: BridgeMintAssetIxData)]
+struct BridgeMintAsset<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        mut,
+        seeds = [&b"ASSET"[..], &ix_data.asset_id.to_le_bytes()],
+        bump,
+    )]
+    asset: Account<'info, Asset>,
+    #[account(
+        mut,
+        seeds = [
+            &b"ORIGIN"[..],
+            &ix_data.src_chain_id.to_le_bytes(),
+            &ix_data.asset_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    origin: Account<'info, Origin>,
+    /// CHECK: it's derived from the message hash and only created if the
+    /// transfer is above the settlement threshold
+    #[account(mut, seeds = [&b"QUEUED"[..], &ix_data.msg_hash], bump)]
+    queued_transfer: AccountInfo<'info>,
+    /// CHECK: it's derived from the message hash and only created if the
+    /// transfer cannot be delivered
+    #[account(mut, seeds = [&b"ESCROW"[..], &ix_data.msg_hash], bump)]
+    escrow: AccountInfo<'info>,
+    #[account(
+        init,
+        space = 8 + Receipt::space(ix_data.from_len as _, ix_data.to_len as _),
+        payer = payer,
+        seeds = [&b"RECEIPT"[..], &ix_data.msg_hash],
+        bump,
+    )]
+    receipt: Account<'info, Receipt>,
+    /// CHECK: it's checked to be the mint of the asset
+    #[account(mut, address = asset.mint)]
+    mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the asset ATA in the lock/release mode
+    #[account(mut)]
+    vault: Option<AccountInfo<'info>>,
+    /// CHECK: it's derived from the escrow seeds
+    #[account(seeds = [b"ESCROW"], bump)]
+    escrow_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the escrow authority ATA
+    #[account(
+        mut,
+        address = find_ata(escrow_authority.key, mint.key, mint.owner)
+    )]
+    escrow_vault: AccountInfo<'info>,
+    /// CHECK: it's checked to be the `to` ATA if `to` is valid, its
+    /// writability is checked in CPI
     token_account: AccountInfo<'info>,
     /// CHECK: it's checked in CPI
     to: AccountInfo<'info>,
-    /// CHECK: it's checked to be the SPL token program
-    #[account(address = spl_token::ID)]
+    /// CHECK: it's checked to be the token program owning the mint
+    #[account(address = *mint.owner)]
     token_program: AccountInfo<'info>,
     /// CHECK: it's checked to be the SPL associated token program
     #[account(address = spl_associated_token_account::ID)]
     associated_token_program: AccountInfo<'info>,
     system_program: Program<'info, System>,
+    /// CHECK: it's derived from the blocklist seeds and deserialized in the
+    /// handler
+    #[account(seeds = [b"BLOCKLIST"], bump)]
+    blocklist: AccountInfo<'info>,
+    /// CHECK: it's checked to be the attestation PDA of the recipient if
+    /// attestations are required
+    attestation: AccountInfo<'info>,
 }
 
-struct BridgeMintParams {
-    to: Pubkey,
-    amount: u64,
+/// Data for use in the anchor `instruction` attribute.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct BridgeMintAssetIxData {
+    src_chain_id: u128,
+    msg_hash: [u8; 32],
+    asset_id: u64,
+    from_len: u64,
+    to_len: u64,
 }
 
-fn bridge_mint(ctx: Context<BridgeMint>, params: BridgeMintParams) -> Result<()> {
+/// Input for the `bridge_mint_asset` function.
+struct BridgeMintAssetParams<'a> {
+    msg_hash: &'a [u8; 32],
+    sender_addr: &'a [u8],
+    transfer: &'a AssetTransfer,
+}
+
+/// Delivers the tokens of a registered asset. A transfer exceeding the
+/// inbound limit fails so that the message is retried later, while a transfer
+/// above the settlement threshold, compared in the base units of the asset, is
+/// queued.
+fn bridge_mint_asset(ctx: Context<BridgeMintAsset>, params: BridgeMintAssetParams) -> Result<()> {
     let payer = &ctx.accounts.payer;
     let config = &ctx.accounts.config;
-    let exa_mint = &ctx.accounts.exa_mint;
+    let asset = &mut ctx.accounts.asset;
+    let origin = &mut ctx.accounts.origin;
+    let mint = &ctx.accounts.mint;
     let token_account = &ctx.accounts.token_account;
     let to = &ctx.accounts.to;
+    let receipt = &mut ctx.accounts.receipt;
+    let transfer = params.transfer;
 
+    check_sender(config, origin, params.sender_addr)?;
+
+    let clock = Clock::get()?;
+    receipt.src_chain_id = origin.chain_id;
+    receipt.sender = transfer.from.clone();
+    receipt.recipient = transfer.to.clone();
+    receipt.slot = clock.slot;
+
+    let Some(amount) = to_local_amount(transfer.amount, asset.decimals, origin.decimals) else {
+        return reject_transfer(receipt, transfer.amount);
+    };
+    receipt.amount = amount;
+    receipt.status = ReceiptStatus::Delivered;
+
+    let asset_seeds: &[&[u8]] = &[b"ASSET", &asset.asset_id.to_le_bytes(), &[ctx.bumps.asset]];
+    // The accounts of a valid recipient are checked before its
+    // deliverability, so that an executor can't force the escrow by passing
+    // other ones.
+    let recipient = Pubkey::try_from(&transfer.to[..]).ok();
+    if let Some(recipient) = recipient {
+        require!(
+            to.key() == recipient
+                && token_account.key() == find_ata(&recipient, mint.key, mint.owner),
+            ErrorCode::ConstraintAddress
+        );
+    }
+
+    let blocklist = Blocklist::load(&ctx.accounts.blocklist)?;
+    let recipient = match recipient {
+        Some(recipient)
+            if is_deliverable(to, token_account, mint)
+                && is_compliant(
+                    config,
+                    &blocklist,
+                    &ctx.accounts.attestation,
+                    &transfer.from,
+                    &recipient,
+                )? =>
+        {
+            Some(recipient)
+        }
+        _ => None,
+    };
+
+    // A transfer above the settlement threshold is queued without consuming
+    // the inbound limit, which is consumed on release.
+    let delayed = config.is_delayed(amount);
+    if let Some(recipient) = recipient.filter(|_| delayed) {
+        let release_after = clock
+            .unix_timestamp
+            .saturating_add_unsigned(config.settlement_delay);
+        create_queued_transfer(
+            payer,
+            &ctx.accounts.queued_transfer,
+            &ctx.accounts.system_program,
+            &[b"QUEUED", params.msg_hash, &[ctx.bumps.queued_transfer]],
+            QueuedTransfer {
+                msg_hash: *params.msg_hash,
+                src_chain_id: origin.chain_id,
+                asset_id: Some(transfer.asset_id),
+                from: transfer.from.clone(),
+                to: recipient,
+                amount,
+                rent_payer: payer.key(),
+                release_after,
+            },
+        )?;
+        receipt.status = ReceiptStatus::Queued;
+
+        msg!(
+            "Queued {} of asset {} for {} from {} until {}",
+            amount,
+            transfer.asset_id,
+            recipient,
+            hex::encode(&transfer.from),
+            release_after
+        );
+
+        return Ok(());
+    }
+
+    // An escrowed transfer consumes the inbound limit right away, as it isn't
+    // released later, and can only be claimed once the settlement delay of a
+    // transfer above the threshold has elapsed.
     require!(
-        token_account.key() == find_ata(&params.to, exa_mint.key),
-        ErrorCode::ConstraintAddress
+        origin
+            .inbound_limit
+            .try_consume(amount, clock.unix_timestamp),
+        ExampleTokenError::InboundRateLimitExceeded
     );
+    origin.total_inbound += u128::from(amount);
+
+    let Some(recipient) = recipient else {
+        let escrow_vault = &ctx.accounts.escrow_vault;
+        let claimable_after = match delayed {
+            true => clock
+                .unix_timestamp
+                .saturating_add_unsigned(config.settlement_delay),
+            false => clock.unix_timestamp,
+        };
 
-    if token_account.data_is_empty() {
-        let ix = create_associated_token_account(payer.key, to.key, exa_mint.key, &spl_token::ID);
-        invoke(
-            &ix,
-            &[
-                payer.to_account_info(),
-                token_account.to_account_info(),
-                to.to_account_info(),
-                exa_mint.to_account_info(),
-            ],
+        create_escrow(
+            payer,
+            &ctx.accounts.escrow,
+            &ctx.accounts.system_program,
+            &[b"ESCROW", params.msg_hash, &[ctx.bumps.escrow]],
+            Escrow {
+                src_chain_id: origin.chain_id,
+                mint: mint.key(),
+                from: transfer.from.clone(),
+                to: transfer.to.clone(),
+                amount,
+                rent_payer: payer.key(),
+                claimable_after,
+            },
         )?;
+        receipt.status = ReceiptStatus::Escrowed;
+
+        create_ata_if_needed(payer, escrow_vault, &ctx.accounts.escrow_authority, mint)?;
+        credit(
+            asset,
+            asset_seeds,
+            mint,
+            ctx.accounts.vault.as_ref(),
+            escrow_vault,
+            amount,
+        )?;
+
+        msg!(
+            "Escrowed {} of asset {} for {} from {}",
+            amount,
+            transfer.asset_id,
+            hex::encode(&transfer.to),
+            hex::encode(&transfer.from)
+        );
+
+        return Ok(());
+    };
+
+    create_ata_if_needed(payer, token_account, to, mint)?;
+    credit(
+        asset,
+        asset_seeds,
+        mint,
+        ctx.accounts.vault.as_ref(),
+        token_account,
+        amount,
+    )?;
+
+    msg!(
+        "{} received {} of asset {} from {}",
+        recipient,
+        amount,
+        transfer.asset_id,
+        hex::encode(&transfer.from)
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ix_data: BridgeForwardIxData)]
+struct BridgeForward<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        mut,
+        seeds = [&b"ORIGIN"[..], &ix_data.src_chain_id.to_le_bytes()],
+        bump,
+    )]
+    origin: Account<'info, Origin>,
+    /// CHECK: it's derived from the final destination chain id and
+    /// deserialized in the handler
+    #[account(seeds = [&b"ORIGIN"[..], &ix_data.dest_chain_id.to_le_bytes()], bump)]
+    dest_origin: AccountInfo<'info>,
+    #[account(
+        init,
+        space = 8 + PendingForward::space(ix_data.from_len as _, ix_data.to_len as _),
+        payer = payer,
+        seeds = [&b"FORWARD"[..], &ix_data.msg_hash],
+        bump,
+    )]
+    pending_forward: Account<'info, PendingForward>,
+    #[account(
+        init,
+        space = 8 + Receipt::space(ix_data.from_len as _, ix_data.to_len as _),
+        payer = payer,
+        seeds = [&b"RECEIPT"[..], &ix_data.msg_hash],
+        bump,
+    )]
+    receipt: Account<'info, Receipt>,
+    system_program: Program<'info, System>,
+}
+
+/// Data for use in the anchor `instruction` attribute.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct BridgeForwardIxData {
+    src_chain_id: u128,
+    msg_hash: [u8; 32],
+    dest_chain_id: u128,
+    from_len: u64,
+    to_len: u64,
+}
+
+/// Input for the `bridge_forward` function.
+struct BridgeForwardParams<'a> {
+    sender_addr: &'a [u8],
+    transfer: &'a TransferForward,
+}
+
+/// Accepts a transfer routed through Solana, recording it to be proposed to
+/// the final destination chain by `forward`. The tokens are neither minted nor
+/// released, as they leave Solana right away. A transfer exceeding the inbound
+/// limit or that can't be forwarded fails so that the message is retried
+/// later.
+fn bridge_forward(ctx: Context<BridgeForward>, params: BridgeForwardParams) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let origin = &mut ctx.accounts.origin;
+    let receipt = &mut ctx.accounts.receipt;
+    let transfer = params.transfer;
+
+    check_sender(config, origin, params.sender_addr)?;
+    require!(
+        config.forward_gas_limit.is_some(),
+        ExampleTokenError::ForwardingDisabled
+    );
+
+    let clock = Clock::get()?;
+    receipt.src_chain_id = origin.chain_id;
+    receipt.sender = transfer.from.clone();
+    receipt.recipient = transfer.to.clone();
+    receipt.slot = clock.slot;
+
+    let Some(received) = to_local_amount(transfer.amount, config.decimals, origin.decimals) else {
+        return reject_transfer(receipt, transfer.amount);
+    };
+
+    require!(
+        origin
+            .inbound_limit
+            .try_consume(received, clock.unix_timestamp),
+        ExampleTokenError::InboundRateLimitExceeded
+    );
+    origin.total_inbound += u128::from(received);
+
+    // The dust that can't be represented on the final destination chain is
+    // dropped, and the recipient checked, as the forward would be stuck
+    // otherwise.
+    let dest_origin = load_origin(config, &ctx.accounts.dest_origin)?;
+    Recipient::decode(&transfer.to, transfer.dest_chain_id)?;
+    let amount = round_to_remote(received, config.decimals, dest_origin.decimals);
+    if amount < received {
+        msg!("Dropped {} tokens of dust", received - amount);
     }
+    receipt.amount = amount;
+    receipt.status = ReceiptStatus::Forwarded;
+
+    let pending_forward = &mut ctx.accounts.pending_forward;
+    pending_forward.src_chain_id = origin.chain_id;
+    pending_forward.dest_chain_id = transfer.dest_chain_id;
+    pending_forward.from = transfer.from.clone();
+    pending_forward.to = transfer.to.clone();
+    pending_forward.amount = amount;
+    pending_forward.rent_payer = ctx.accounts.payer.key();
+
+    msg!(
+        "Accepted {} tokens from {} to forward to {} on chain {}",
+        amount,
+        hex::encode(&transfer.from),
+        hex::encode(&transfer.to),
+        transfer.dest_chain_id
+    );
 
-    let ix = mint_to(
-        &spl_token::ID,
-        exa_mint.key,
-        token_account.key,
-        &config.key(),
-        &[],
-        params.amount,
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ix_data: BridgeRefundIxData)]
+struct BridgeRefund<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut, seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        mut,
+        seeds = [&b"ORIGIN"[..], &ix_data.src_chain_id.to_le_bytes()],
+        bump,
+    )]
+    origin: Account<'info, Origin>,
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender @ ExampleTokenError::InvalidRefund,
+        seeds = [&b"OUTBOUND"[..], &ix_data.nonce.to_le_bytes()],
+        bump,
+    )]
+    outbound_transfer: Account<'info, OutboundTransfer>,
+    #[account(
+        init,
+        space = 8 + Receipt::space(ix_data.from_len as _, ix_data.from_len as _),
+        payer = payer,
+        seeds = [&b"RECEIPT"[..], &ix_data.msg_hash],
+        bump,
+    )]
+    receipt: Account<'info, Receipt>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(mut, address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the config ATA in the lock/release mode
+    #[account(mut)]
+    vault: Option<AccountInfo<'info>>,
+    /// CHECK: it's checked to be the sender ATA
+    #[account(mut, address = find_ata(sender.key, exa_mint.key, exa_mint.owner))]
+    token_account: AccountInfo<'info>,
+    /// CHECK: it's checked to be the sender of the outbound transfer
+    #[account(mut)]
+    sender: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Data for use in the anchor `instruction` attribute.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct BridgeRefundIxData {
+    src_chain_id: u128,
+    msg_hash: [u8; 32],
+    nonce: u64,
+    from_len: u64,
+}
+
+/// Input for the `bridge_refund` function.
+struct BridgeRefundParams<'a> {
+    msg_hash: &'a [u8; 32],
+    sender_addr: &'a [u8],
+    refund: &'a Refund,
+}
+
+/// Returns the tokens of an outgoing transfer that could not be delivered on
+/// the destination chain to its sender, closing the record of the transfer so
+/// that it's refunded at most once. The refund isn't subject to the inbound
+/// limit, as it's bounded by the amount sent.
+fn bridge_refund(ctx: Context<BridgeRefund>, params: BridgeRefundParams) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let config = &mut ctx.accounts.config;
+    let origin = &mut ctx.accounts.origin;
+    let outbound_transfer = &ctx.accounts.outbound_transfer;
+    let exa_mint = &ctx.accounts.exa_mint;
+    let token_account = &ctx.accounts.token_account;
+    let sender = &ctx.accounts.sender;
+    let receipt = &mut ctx.accounts.receipt;
+    let refund = params.refund;
+
+    check_sender(config, origin, params.sender_addr)?;
+    require!(
+        outbound_transfer.dest_chain_id == origin.chain_id
+            && refund.from[..] == sender.key.to_bytes(),
+        ExampleTokenError::InvalidRefund
+    );
+
+    let amount = to_local_amount(refund.amount, config.decimals, origin.decimals)
+        .filter(|&amount| amount <= outbound_transfer.amount)
+        .ok_or(ExampleTokenError::RefundExceedsTransfer)?;
+
+    create_ata_if_needed(payer, token_account, sender, exa_mint)?;
+    credit(
+        config,
+        &[b"CONFIG", &[ctx.bumps.config]],
+        exa_mint,
+        ctx.accounts.vault.as_ref(),
+        token_account,
+        amount,
     )?;
+    origin.total_inbound += u128::from(amount);
+
+    receipt.src_chain_id = origin.chain_id;
+    receipt.sender = refund.from.clone();
+    receipt.recipient = refund.from.clone();
+    receipt.amount = amount;
+    receipt.slot = Clock::get()?.slot;
+    receipt.status = ReceiptStatus::Refunded;
+
+    emit!(TransferRefunded {
+        msg_hash: *params.msg_hash,
+        dest_chain_id: origin.chain_id,
+        nonce: refund.nonce,
+        sender: sender.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Records an incoming transfer of `amount` remote units that doesn't fit into
+/// `u64` locally as rejected, so that the message is executed without
+/// delivering anything rather than failing for good.
+fn reject_transfer(receipt: &mut Receipt, amount: U256) -> Result<()> {
+    receipt.amount = 0;
+    receipt.status = ReceiptStatus::Rejected;
+
+    msg!("Rejected {} remote units that don't fit locally", amount);
+
+    Ok(())
+}
+
+/// Checks that inbound transfers are allowed from the sender contract.
+fn check_sender(config: &ExampleTokenConfig, origin: &Origin, sender_addr: &[u8]) -> Result<()> {
+    require!(!config.inbound_paused, ExampleTokenError::InboundPaused);
+    require!(
+        origin.enabled && origin.address[..] == *sender_addr,
+        ExampleTokenError::SenderSmartContractNotAllowed
+    );
+    require!(
+        !config.is_route_paused(origin.chain_id),
+        ExampleTokenError::RoutePaused
+    );
+
+    Ok(())
+}
+
+/// Checks that neither the sender nor the recipient is blocklisted, and that
+/// the recipient holds an attestation if required.
+pub(super) fn is_compliant(
+    config: &ExampleTokenConfig,
+    blocklist: &Blocklist,
+    attestation: &AccountInfo,
+    from: &[u8],
+    recipient: &Pubkey,
+) -> Result<bool> {
+    Ok(!blocklist.contains(from)
+        && !blocklist.contains(recipient.as_ref())
+        && Attestation::is_satisfied(config, attestation, recipient)?)
+}
+
+/// Creates the escrow account of an undelivered transfer at the PDA derived
+/// from `escrow_seeds`.
+pub(super) fn create_escrow<'info>(
+    payer: &Signer<'info>,
+    escrow: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    escrow_seeds: &[&[u8]],
+    data: Escrow,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let space = 8 + Escrow::space(data.from.len(), data.to.len());
+    let signer_seeds = [escrow_seeds];
+    let ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        CreateAccount {
+            from: payer.to_account_info(),
+            to: escrow.to_account_info(),
+        },
+        &signer_seeds,
+    );
+    create_account(ctx, rent.minimum_balance(space), space as u64, &crate::ID)?;
+
+    data.try_serialize(&mut &mut escrow.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+/// Creates the queued transfer account of a transfer awaiting release at the
+/// PDA derived from `queued_seeds`.
+fn create_queued_transfer<'info>(
+    payer: &Signer<'info>,
+    queued_transfer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    queued_seeds: &[&[u8]],
+    data: QueuedTransfer,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let space = 8 + QueuedTransfer::space(data.from.len());
+    let signer_seeds = [queued_seeds];
+    let ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        CreateAccount {
+            from: payer.to_account_info(),
+            to: queued_transfer.to_account_info(),
+        },
+        &signer_seeds,
+    );
+    create_account(ctx, rent.minimum_balance(space), space as u64, &crate::ID)?;
+
+    data.try_serialize(&mut &mut queued_transfer.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+/// Returns the caller PDA of the sender `from` on the chain `src_chain_id`,
+/// which holds the tokens delivered with a call and signs it.
+fn find_caller(src_chain_id: u128, from: &[u8]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"CALLER", &src_chain_id.to_le_bytes(), from], &crate::ID)
+}
+
+/// Invokes the program called on delivery, passing the caller PDA of the
+/// source chain and sender as a signer, so that the program can check where
+/// the call comes from and take the delivered tokens from the caller ATA. The
+/// remaining accounts are expected to be the program, the caller PDA and the
+/// call accounts.
+fn invoke_call(
+    call: &Call,
+    src_chain_id: u128,
+    from: &[u8],
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    let [program, caller, call_accounts @ ..] = accounts else {
+        return err!(ExampleTokenError::InvalidCallAccounts);
+    };
+    let (caller_key, caller_bump) = find_caller(src_chain_id, from);
+    require!(
+        program.key.to_bytes() == call.program_id
+            && *caller.key == caller_key
+            && call_accounts.len() == call.accounts.len()
+            && call_accounts
+                .iter()
+                .zip(&call.accounts)
+                .all(|(account, (key, _))| account.key.to_bytes() == *key),
+        ExampleTokenError::InvalidCallAccounts
+    );
+
+    let mut metas = vec![AccountMeta::new_readonly(caller_key, true)];
+    metas.extend(
+        call_accounts.iter().zip(&call.accounts).map(
+            |(account, (_, is_writable))| match is_writable {
+                true => AccountMeta::new(*account.key, false),
+                false => AccountMeta::new_readonly(*account.key, false),
+            },
+        ),
+    );
+    let instruction = Instruction {
+        program_id: *program.key,
+        accounts: metas,
+        data: call.data.clone(),
+    };
+
     invoke_signed(
-        &ix,
-        &[
-            exa_mint.to_account_info(),
-            token_account.to_account_info(),
-            config.to_account_info(),
-        ],
-        &[&[b"CONFIG", &[ctx.bumps.config]]],
+        &instruction,
+        accounts,
+        &[&[b"CALLER", &src_chain_id.to_le_bytes(), from, &[caller_bump]]],
     )?;
 
     Ok(())
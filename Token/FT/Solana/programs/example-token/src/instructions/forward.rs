@@ -0,0 +1,176 @@
+use crate::{
+    error::*,
+    events::*,
+    payload::{Refund, Transfer},
+    state::*,
+    utils::to_remote_amount,
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer as SystemTransfer},
+};
+use uip_solana_sdk::UipEndpoint;
+
+use super::{
+    bridge::{
+        check_amount, check_not_blocked, effective_commitment, load_origin, propose,
+        ProposeAccounts,
+    },
+    ProposalCommitment,
+};
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32])]
+pub struct Forward<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        seeds = [&b"FORWARD"[..], &msg_hash],
+        bump
+    )]
+    pending_forward: Account<'info, PendingForward>,
+    /// CHECK: it's derived from the final destination chain id and
+    /// deserialized in the handler
+    #[account(
+        mut,
+        seeds = [&b"ORIGIN"[..], &pending_forward.dest_chain_id.to_le_bytes()],
+        bump
+    )]
+    origin: AccountInfo<'info>,
+    /// CHECK: it's checked to be the account that paid for the pending forward
+    #[account(mut)]
+    rent_payer: AccountInfo<'info>,
+    #[account(mut, seeds = [b"FORWARD_FEE_VAULT"], bump)]
+    fee_vault: SystemAccount<'info>,
+    /// CHECK: checked in the CPI
+    endpoint_config: AccountInfo<'info>,
+    /// CHECK: checked in the CPI
+    #[account(mut)]
+    uts_connector: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    #[account(seeds = [b"UIP_SIGNER"], bump)]
+    program_signer: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+    uip_program: Program<'info, UipEndpoint>,
+    /// CHECK: it's derived from the blocklist seeds and deserialized in the
+    /// handler
+    #[account(seeds = [b"BLOCKLIST"], bump)]
+    blocklist: AccountInfo<'info>,
+}
+
+/// Proposes a transfer routed through Solana to its final destination chain,
+/// preserving the original sender and recipient, or the refund of a bounced
+/// transfer to its source chain. The UIP fee chosen by the payer is paid from
+/// the forward fee vault, reimbursing the payer that proposes the message, so
+/// it's bounded by the vault balance. Can be called by anyone.
+pub fn forward(ctx: Context<Forward>, msg_hash: [u8; 32], uip_fee: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let pending_forward = &ctx.accounts.pending_forward;
+    let origin_info = &ctx.accounts.origin;
+    let payer = &ctx.accounts.payer;
+    let fee_vault = &ctx.accounts.fee_vault;
+    let system_program = &ctx.accounts.system_program;
+    let amount = pending_forward.amount;
+
+    let custom_gas_limit = config
+        .forward_gas_limit
+        .ok_or(ExampleTokenError::ForwardingDisabled)?;
+    let mut origin = load_origin(config, origin_info)?;
+    // A refund returns the tokens to their sender on the source chain, so it
+    // isn't held back by the checks of a new transfer.
+    if pending_forward.refund_nonce.is_none() {
+        check_not_blocked(
+            &ctx.accounts.blocklist,
+            &[&pending_forward.from, &pending_forward.to],
+        )?;
+        check_amount(&origin, amount)?;
+    }
+    require!(
+        origin
+            .outbound_limit
+            .try_consume(amount, Clock::get()?.unix_timestamp),
+        ExampleTokenError::OutboundRateLimitExceeded
+    );
+    origin.total_outbound += u128::from(amount);
+    origin.try_serialize(&mut &mut origin_info.try_borrow_mut_data()?[..])?;
+    let commitment = effective_commitment(config, amount, ProposalCommitment::Confirmed);
+    let remote_amount = to_remote_amount(amount, config.decimals, origin.decimals)?;
+
+    let payload = match pending_forward.refund_nonce {
+        Some(nonce) => Refund {
+            nonce,
+            from: pending_forward.from.clone(),
+            amount: remote_amount,
+        }
+        .encode(),
+        None => Transfer {
+            from: pending_forward.from.clone(),
+            to: pending_forward.to.clone(),
+            amount: remote_amount,
+            call: None,
+            nonce: None,
+        }
+        .encode(),
+    };
+
+    // The vault is a system account, which must stay rent-exempt.
+    let reserve = Rent::get()?.minimum_balance(0);
+    require_gte!(
+        fee_vault.lamports(),
+        uip_fee + reserve,
+        ExampleTokenError::ForwardFeeVaultDepleted
+    );
+    let vault_seeds: &[&[&[u8]]] = &[&[b"FORWARD_FEE_VAULT", &[ctx.bumps.fee_vault]]];
+    let ctx2 = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        SystemTransfer {
+            from: fee_vault.to_account_info(),
+            to: payer.to_account_info(),
+        },
+        vault_seeds,
+    );
+    system_program::transfer(ctx2, uip_fee)?;
+
+    propose(
+        ProposeAccounts {
+            payer: payer.as_ref(),
+            endpoint_config: &ctx.accounts.endpoint_config,
+            uts_connector: &ctx.accounts.uts_connector,
+            program_signer: &ctx.accounts.program_signer,
+            program_signer_bump: ctx.bumps.program_signer,
+            system_program: system_program.as_ref(),
+        },
+        &origin,
+        &payload,
+        uip_fee,
+        custom_gas_limit,
+        commitment,
+    )?;
+
+    match pending_forward.refund_nonce {
+        Some(nonce) => emit!(TransferBounced {
+            msg_hash,
+            src_chain_id: origin.chain_id,
+            nonce,
+            from: pending_forward.from.clone(),
+            amount,
+            uip_fee,
+        }),
+        None => emit!(TransferForwarded {
+            msg_hash,
+            src_chain_id: pending_forward.src_chain_id,
+            dest_chain_id: origin.chain_id,
+            from: pending_forward.from.clone(),
+            to: pending_forward.to.clone(),
+            amount,
+            uip_fee,
+        }),
+    }
+
+    Ok(())
+}
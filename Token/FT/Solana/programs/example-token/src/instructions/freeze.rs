@@ -0,0 +1,71 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+use solana_invoke::invoke_signed;
+use spl_token_2022::instruction::{freeze_account, thaw_account};
+
+#[derive(Accounts)]
+pub struct Freeze<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    #[account(mut)]
+    token_account: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+}
+
+/// Freezes `token_account`, which requires the EXA mint to be created with
+/// the config as its freeze authority.
+pub fn freeze(ctx: Context<Freeze>) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let ix = freeze_account(
+        accounts.token_program.key,
+        accounts.token_account.key,
+        accounts.exa_mint.key,
+        &accounts.config.key(),
+        &[],
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            accounts.token_account.to_account_info(),
+            accounts.exa_mint.to_account_info(),
+            accounts.config.to_account_info(),
+        ],
+        &[&[b"CONFIG", &[ctx.bumps.config]]],
+    )?;
+
+    msg!("Froze {}", accounts.token_account.key());
+
+    Ok(())
+}
+
+/// Thaws a frozen `token_account`.
+pub fn thaw(ctx: Context<Freeze>) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let ix = thaw_account(
+        accounts.token_program.key,
+        accounts.token_account.key,
+        accounts.exa_mint.key,
+        &accounts.config.key(),
+        &[],
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            accounts.token_account.to_account_info(),
+            accounts.exa_mint.to_account_info(),
+            accounts.config.to_account_info(),
+        ],
+        &[&[b"CONFIG", &[ctx.bumps.config]]],
+    )?;
+
+    msg!("Thawed {}", accounts.token_account.key());
+
+    Ok(())
+}
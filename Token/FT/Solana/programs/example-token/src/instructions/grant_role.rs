@@ -0,0 +1,58 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        init_if_needed,
+        space = 8 + Roles::space(),
+        payer = payer,
+        seeds = [b"ROLES"],
+        bump
+    )]
+    roles: Account<'info, Roles>,
+    admin: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(mut, seeds = [b"ROLES"], bump)]
+    roles: Account<'info, Roles>,
+    admin: Signer<'info>,
+}
+
+/// Grants `role` to `account`.
+pub fn grant_role(ctx: Context<GrantRole>, role: Role, account: Pubkey) -> Result<()> {
+    let holders = ctx.accounts.roles.holders_mut(role);
+
+    if !holders.contains(&account) {
+        require!(
+            holders.len() < Roles::MAX_HOLDERS,
+            ExampleTokenError::TooManyRoleHolders
+        );
+        holders.push(account);
+    }
+
+    msg!("Granted {:?} to {}", role, account);
+
+    Ok(())
+}
+
+/// Revokes `role` from `account`.
+pub fn revoke_role(ctx: Context<RevokeRole>, role: Role, account: Pubkey) -> Result<()> {
+    ctx.accounts
+        .roles
+        .holders_mut(role)
+        .retain(|key| *key != account);
+
+    msg!("Revoked {:?} from {}", role, account);
+
+    Ok(())
+}
@@ -1,13 +1,25 @@
-use crate::state::*;
+use crate::{error::*, metaplex, state::*, utils::is_solana_chain};
 use anchor_lang::{
     prelude::*,
     system_program::{create_account, CreateAccount},
 };
-use solana_invoke::invoke;
-use spl_token::{instruction::initialize_mint2, solana_program::program_pack::Pack};
+use solana_invoke::{invoke, invoke_signed};
+use spl_token::solana_program::program_pack::Pack;
+use spl_token_2022::{
+    extension::{metadata_pointer, transfer_fee, transfer_hook, ExtensionType},
+    instruction::initialize_mint2,
+};
+use spl_token_metadata_interface::state::TokenMetadata;
 
 #[derive(Accounts)]
-#[instruction(admin: Pubkey)]
+#[instruction(
+    admin: Pubkey,
+    decimals: u8,
+    token_2022: Option<Token2022Extensions>,
+    metadata: Option<Metadata>,
+    freezable: bool,
+    chain_id: u128,
+)]
 pub struct Initialize<'info> {
     #[account(
         init,
@@ -17,27 +29,110 @@ pub struct Initialize<'info> {
         bump
     )]
     config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        init,
+        space = 8 + Roles::space(),
+        payer = payer,
+        seeds = [b"ROLES"],
+        bump
+    )]
+    roles: Account<'info, Roles>,
     /// CHECK: it's derived from the EXA mint seeds
     #[account(mut, seeds = [b"EXA_MINT"], bump)]
     exa_mint: AccountInfo<'info>,
     #[account(mut)]
     payer: Signer<'info>,
-    /// CHECK: it's checked to be the SPL token program
-    #[account(address = spl_token::ID)]
+    /// CHECK: it's checked to be the SPL token program, or the Token-2022
+    /// program if its extensions are requested
+    #[account(
+        address = if token_2022.is_some() { spl_token_2022::ID } else { spl_token::ID }
+    )]
     token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the Metaplex metadata account of the EXA
+    /// mint, required if metadata is requested for an SPL token mint
+    #[account(mut, address = metaplex::find_metadata(exa_mint.key))]
+    metaplex_metadata: Option<AccountInfo<'info>>,
+    /// CHECK: it's checked to be the Metaplex token metadata program
+    #[account(address = metaplex::ID)]
+    metaplex_program: Option<AccountInfo<'info>>,
     system_program: Program<'info, System>,
 }
 
-pub fn initialize(ctx: Context<Initialize>, admin: Pubkey, decimals: u8) -> Result<()> {
+/// Parameters of the Token-2022 extensions enabled on the EXA mint. The
+/// metadata pointer always points to the mint itself. The extra accounts of a
+/// transfer hook are passed as the remaining accounts of the instructions
+/// transferring EXA, while bridging in and out only mints and burns it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Token2022Extensions {
+    /// Transfer fee in basis points.
+    pub transfer_fee_basis_points: u16,
+    /// Maximal transfer fee in base units.
+    pub maximum_fee: u64,
+    /// Program invoked on every transfer of EXA, if any.
+    pub transfer_hook_program_id: Option<Pubkey>,
+}
+
+/// Name, symbol and URI of the EXA mint shown by wallets.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Metadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+pub fn initialize(
+    ctx: Context<Initialize>,
+    admin: Pubkey,
+    decimals: u8,
+    token_2022: Option<Token2022Extensions>,
+    metadata: Option<Metadata>,
+    freezable: bool,
+    chain_id: u128,
+) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let exa_mint = &ctx.accounts.exa_mint;
     let payer = &ctx.accounts.payer;
+    let token_program = &ctx.accounts.token_program;
     let system_program = &ctx.accounts.system_program;
     let rent = Rent::get()?;
 
+    require!(is_solana_chain(chain_id), ExampleTokenError::NotSolanaChain);
+
     config.admin = admin;
+    config.decimals = decimals;
+    config.guardian = admin;
+    config.mint = exa_mint.key();
+    config.chain_id = chain_id;
 
-    let space = spl_token::state::Mint::LEN;
+    let roles = &mut ctx.accounts.roles;
+    for role in Role::ALL {
+        roles.holders_mut(role).push(admin);
+    }
+
+    let space = if let Some(extensions) = &token_2022 {
+        let mut extension_types = vec![
+            ExtensionType::MetadataPointer,
+            ExtensionType::TransferFeeConfig,
+        ];
+        if extensions.transfer_hook_program_id.is_some() {
+            extension_types.push(ExtensionType::TransferHook);
+        }
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extension_types)?
+    } else {
+        spl_token::state::Mint::LEN
+    };
+    // The embedded metadata is reallocated into the mint when initialized, so
+    // only its rent is paid upfront.
+    let metadata_space = match (&token_2022, &metadata) {
+        (Some(_), Some(metadata)) => TokenMetadata {
+            name: metadata.name.clone(),
+            symbol: metadata.symbol.clone(),
+            uri: metadata.uri.clone(),
+            ..Default::default()
+        }
+        .tlv_size_of()?,
+        _ => 0,
+    };
     let mint_seeds: &[&[&[u8]]] = &[&[b"EXA_MINT", &[ctx.bumps.exa_mint]]];
     let ctx2 = CpiContext::new_with_signer(
         system_program.to_account_info(),
@@ -49,13 +144,100 @@ pub fn initialize(ctx: Context<Initialize>, admin: Pubkey, decimals: u8) -> Resu
     );
     create_account(
         ctx2,
-        rent.minimum_balance(space),
+        rent.minimum_balance(space + metadata_space),
         space as u64,
-        &spl_token::ID,
+        token_program.key,
     )?;
 
-    let ix = initialize_mint2(&spl_token::ID, exa_mint.key, &config.key(), None, decimals)?;
+    if let Some(extensions) = token_2022 {
+        let mut ixs = vec![
+            metadata_pointer::instruction::initialize(
+                token_program.key,
+                exa_mint.key,
+                Some(config.key()),
+                Some(exa_mint.key()),
+            )?,
+            transfer_fee::instruction::initialize_transfer_fee_config(
+                token_program.key,
+                exa_mint.key,
+                Some(&config.key()),
+                Some(&config.key()),
+                extensions.transfer_fee_basis_points,
+                extensions.maximum_fee,
+            )?,
+        ];
+        if let Some(program_id) = extensions.transfer_hook_program_id {
+            ixs.push(transfer_hook::instruction::initialize(
+                token_program.key,
+                exa_mint.key,
+                Some(config.key()),
+                Some(program_id),
+            )?);
+        }
+        for ix in &ixs {
+            invoke(ix, &[exa_mint.to_account_info()])?;
+        }
+    }
+
+    let ix = initialize_mint2(
+        token_program.key,
+        exa_mint.key,
+        &config.key(),
+        freezable.then(|| config.key()).as_ref(),
+        decimals,
+    )?;
     invoke(&ix, &[exa_mint.to_account_info()])?;
 
+    if let Some(Metadata { name, symbol, uri }) = metadata {
+        let config_seeds: &[&[&[u8]]] = &[&[b"CONFIG", &[ctx.bumps.config]]];
+        if *token_program.key == spl_token_2022::ID {
+            let ix = spl_token_metadata_interface::instruction::initialize(
+                token_program.key,
+                exa_mint.key,
+                &config.key(),
+                exa_mint.key,
+                &config.key(),
+                name,
+                symbol,
+                uri,
+            );
+            invoke_signed(
+                &ix,
+                &[exa_mint.to_account_info(), config.to_account_info()],
+                config_seeds,
+            )?;
+        } else {
+            let metaplex_metadata = ctx
+                .accounts
+                .metaplex_metadata
+                .as_ref()
+                .ok_or(ErrorCode::ConstraintAccountIsNone)?;
+            require!(
+                ctx.accounts.metaplex_program.is_some(),
+                ErrorCode::ConstraintAccountIsNone
+            );
+            let ix = metaplex::create_metadata_account_v3(
+                metaplex_metadata.key,
+                exa_mint.key,
+                &config.key(),
+                payer.key,
+                name,
+                symbol,
+                uri,
+            )?;
+            invoke_signed(
+                &ix,
+                &[
+                    metaplex_metadata.to_account_info(),
+                    exa_mint.to_account_info(),
+                    config.to_account_info(),
+                    payer.to_account_info(),
+                    system_program.to_account_info(),
+                ],
+                config_seeds,
+            )?;
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,96 @@
+use crate::{
+    error::*,
+    state::*,
+    utils::{is_solana_chain, require_no_transfer_hook},
+};
+use anchor_lang::prelude::*;
+use solana_invoke::invoke;
+use spl_associated_token_account::instruction::create_associated_token_account;
+use spl_token_2022::extension::StateWithExtensions;
+
+#[derive(Accounts)]
+pub struct InitializeLockRelease<'info> {
+    #[account(
+        init,
+        space = 8 + ExampleTokenConfig::space(),
+        payer = payer,
+        seeds = [b"CONFIG"],
+        bump
+    )]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        init,
+        space = 8 + Roles::space(),
+        payer = payer,
+        seeds = [b"ROLES"],
+        bump
+    )]
+    roles: Account<'info, Roles>,
+    /// CHECK: it's checked to be owned by the token program
+    #[account(owner = token_program.key())]
+    mint: AccountInfo<'info>,
+    /// CHECK: it's checked in CPI to be the config ATA
+    #[account(mut)]
+    vault: AccountInfo<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    /// CHECK: it's checked to be the SPL token or Token-2022 program
+    #[account(
+        constraint = token_program.key() == spl_token::ID
+            || token_program.key() == spl_token_2022::ID
+    )]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Initializes the example token bridge contract in the lock/release mode for
+/// an existing `mint`, creating the vault the bridged tokens are locked in.
+/// The extension can't read the config, so it only derives the accounts of the
+/// mint once rebuilt with it in `EXA_LOCKED_MINT` and registered again.
+pub fn initialize_lock_release(
+    ctx: Context<InitializeLockRelease>,
+    admin: Pubkey,
+    chain_id: u128,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let mint = &ctx.accounts.mint;
+    let vault = &ctx.accounts.vault;
+    let payer = &ctx.accounts.payer;
+
+    require!(is_solana_chain(chain_id), ExampleTokenError::NotSolanaChain);
+    require_no_transfer_hook(mint)?;
+    let decimals = {
+        let data = mint.try_borrow_data()?;
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?
+            .base
+            .decimals
+    };
+
+    config.admin = admin;
+    config.decimals = decimals;
+    config.guardian = admin;
+    config.mint = mint.key();
+    config.lock_release = true;
+    config.chain_id = chain_id;
+
+    let roles = &mut ctx.accounts.roles;
+    for role in Role::ALL {
+        roles.holders_mut(role).push(admin);
+    }
+
+    let ix = create_associated_token_account(payer.key, &config.key(), mint.key, mint.owner);
+    invoke(
+        &ix,
+        &[
+            payer.to_account_info(),
+            vault.to_account_info(),
+            config.to_account_info(),
+            mint.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
@@ -1,22 +1,27 @@
-use crate::state::*;
+use crate::{error::*, state::*};
 use anchor_lang::prelude::*;
 use solana_invoke::invoke_signed;
-use spl_token::instruction::mint_to;
+use spl_token_2022::instruction::mint_to;
 
 #[derive(Accounts)]
 pub struct Mint<'info> {
     #[account(seeds = [b"CONFIG"], bump)]
     config: Account<'info, ExampleTokenConfig>,
-    #[account(address = config.admin)]
-    admin: Signer<'info>,
+    #[account(
+        seeds = [b"ROLES"],
+        bump,
+        constraint = roles.has_role(Role::Minter, authority.key) @ ExampleTokenError::MissingRole,
+    )]
+    roles: Account<'info, Roles>,
+    authority: Signer<'info>,
     /// CHECK: it's derived from the EXA mint seeds
     #[account(mut, seeds = [b"EXA_MINT"], bump)]
     exa_mint: AccountInfo<'info>,
     /// CHECK: checked in CPI
     #[account(mut)]
     destination_ata: AccountInfo<'info>,
-    /// CHECK: it's checked to be the SPL token program
-    #[account(address = spl_token::ID)]
+    /// CHECK: it's checked to be the token program owning the EXA mint
+    #[account(address = *exa_mint.owner)]
     token_program: AccountInfo<'info>,
 }
 
@@ -25,8 +30,13 @@ pub fn mint(ctx: Context<Mint>, amount: u64) -> Result<()> {
     let exa_mint = &ctx.accounts.exa_mint;
     let destination_ata = &ctx.accounts.destination_ata;
 
+    require!(
+        !config.lock_release,
+        ExampleTokenError::UnsupportedInLockReleaseMode
+    );
+
     let ix = mint_to(
-        &spl_token::ID,
+        exa_mint.owner,
         exa_mint.key,
         destination_ata.key,
         &config.key(),
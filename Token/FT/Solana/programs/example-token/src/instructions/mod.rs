@@ -1,10 +1,51 @@
 pub use self::{
-    bridge::*, execute::*, initialize::*, mint::*, register_extension::*, update_admin::*,
+    attest::*, block::*, bridge::*, bridge_asset::*, bridge_with_permit::*, cancel_forward::*,
+    cancel_queued_transfer::*, claim::*, close_outbound_transfer::*, execute::*, forward::*,
+    freeze::*, grant_role::*, initialize::*, initialize_lock_release::*, mint::*, pause::*,
+    reconcile_supply::*, recover_escrow::*, register_asset::*, register_extension::*,
+    release_queued_asset_transfer::*, release_queued_transfer::*, remove_origin::*,
+    set_asset_limits::*, set_asset_origin::*, set_attestation_authority::*, set_fee::*,
+    set_finalized_threshold::*, set_forward_gas_limit::*, set_guardian::*, set_limits::*,
+    set_origin::*, set_refundable::*, set_settlement_delay::*, update_admin::*, update_metadata::*,
+    withdraw_fees::*, withdraw_forward_fees::*,
 };
 
+mod attest;
+mod block;
 mod bridge;
+mod bridge_asset;
+mod bridge_with_permit;
+mod cancel_forward;
+mod cancel_queued_transfer;
+mod claim;
+mod close_outbound_transfer;
 mod execute;
+mod forward;
+mod freeze;
+mod grant_role;
 mod initialize;
+mod initialize_lock_release;
 mod mint;
+mod pause;
+mod reconcile_supply;
+mod recover_escrow;
+mod register_asset;
 mod register_extension;
+mod release_queued_asset_transfer;
+mod release_queued_transfer;
+mod remove_origin;
+mod set_asset_limits;
+mod set_asset_origin;
+mod set_attestation_authority;
+mod set_fee;
+mod set_finalized_threshold;
+mod set_forward_gas_limit;
+mod set_guardian;
+mod set_limits;
+mod set_origin;
+mod set_refundable;
+mod set_settlement_delay;
 mod update_admin;
+mod update_metadata;
+mod withdraw_fees;
+mod withdraw_forward_fees;
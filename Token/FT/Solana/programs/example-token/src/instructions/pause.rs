@@ -0,0 +1,74 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        mut,
+        seeds = [b"CONFIG"],
+        bump,
+        constraint = authority.key() == config.guardian
+            || roles.has_role(Role::Pauser, authority.key) @ ExampleTokenError::NotGuardian,
+    )]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(seeds = [b"ROLES"], bump)]
+    roles: Account<'info, Roles>,
+    authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    #[account(mut, seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+}
+
+/// Part of the bridge to pause or unpause.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub enum PauseTarget {
+    /// Sending tokens to all chains.
+    Outbound,
+    /// Receiving tokens from all chains.
+    Inbound,
+    /// Bridging to and from a single chain.
+    Route { chain_id: u128 },
+}
+
+/// Pauses the `target` part of the bridge. Can be called by the guardian or a
+/// pauser.
+pub fn pause(ctx: Context<Pause>, target: PauseTarget) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    match target {
+        PauseTarget::Outbound => config.outbound_paused = true,
+        PauseTarget::Inbound => config.inbound_paused = true,
+        PauseTarget::Route { chain_id } => {
+            if !config.is_route_paused(chain_id) {
+                require!(
+                    config.paused_routes.len() < ExampleTokenConfig::MAX_PAUSED_ROUTES,
+                    ExampleTokenError::TooManyPausedRoutes
+                );
+                config.paused_routes.push(chain_id);
+            }
+        }
+    }
+
+    msg!("Paused {:?}", target);
+
+    Ok(())
+}
+
+/// Unpauses the `target` part of the bridge.
+pub fn unpause(ctx: Context<Unpause>, target: PauseTarget) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    match target {
+        PauseTarget::Outbound => config.outbound_paused = false,
+        PauseTarget::Inbound => config.inbound_paused = false,
+        PauseTarget::Route { chain_id } => config.paused_routes.retain(|&id| id != chain_id),
+    }
+
+    msg!("Unpaused {:?}", target);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::{events::*, state::*};
+use anchor_lang::prelude::*;
+use spl_token_2022::{extension::StateWithExtensions, state::Mint};
+
+#[derive(Accounts)]
+pub struct ReconcileSupply<'info> {
+    #[account(seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleTokenConfig>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+}
+
+/// Logs the amount of tokens outstanding on each chain whose origin is passed
+/// in the remaining accounts, to be compared with the token supply there.
+pub fn reconcile_supply<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReconcileSupply<'info>>,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    let supply = {
+        let data = ctx.accounts.exa_mint.try_borrow_data()?;
+        StateWithExtensions::<Mint>::unpack(&data)?.base.supply
+    };
+    if config.lock_release {
+        msg!("Locked {} of {} tokens", config.vault_balance, supply);
+    } else {
+        msg!("Supply is {} tokens", supply);
+    }
+
+    for origin_info in ctx.remaining_accounts {
+        let origin = Account::<Origin>::try_from(origin_info)?;
+
+        msg!(
+            "Chain {}: sent {}, received {}, outstanding {}",
+            origin.chain_id,
+            origin.total_outbound,
+            origin.total_inbound,
+            origin.outstanding()
+        );
+        emit!(SupplyReconciled {
+            chain_id: origin.chain_id,
+            total_outbound: origin.total_outbound,
+            total_inbound: origin.total_inbound,
+            outstanding: origin.outstanding(),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::{
+    state::*,
+    utils::{find_ata, mint_decimals, transfer},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32])]
+pub struct RecoverEscrow<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        seeds = [&b"ESCROW"[..], &msg_hash],
+        bump
+    )]
+    escrow: Account<'info, Escrow>,
+    /// CHECK: it's checked to be the account that paid for the escrow
+    #[account(mut)]
+    rent_payer: AccountInfo<'info>,
+    /// CHECK: it's derived from the escrow seeds
+    #[account(seeds = [b"ESCROW"], bump)]
+    escrow_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the escrow authority ATA
+    #[account(
+        mut,
+        address = find_ata(escrow_authority.key, exa_mint.key, exa_mint.owner)
+    )]
+    escrow_vault: AccountInfo<'info>,
+    /// CHECK: it's checked to be the mint of the escrowed tokens
+    #[account(address = escrow.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the recipient ATA if the recipient is valid
+    #[account(mut)]
+    destination: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+}
+
+/// Transfers the tokens of an undelivered incoming transfer to `destination`
+/// on behalf of a recipient that cannot claim them because the address in the
+/// message is malformed. The tokens of a valid recipient can only be pushed to
+/// its ATA.
+pub fn recover_escrow<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RecoverEscrow<'info>>,
+    _msg_hash: [u8; 32],
+) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    let exa_mint = &ctx.accounts.exa_mint;
+
+    if let Some(recipient) = escrow.recipient() {
+        require_keys_eq!(
+            ctx.accounts.destination.key(),
+            find_ata(&recipient, exa_mint.key, exa_mint.owner),
+            ErrorCode::ConstraintAddress
+        );
+    }
+
+    transfer(
+        &ctx.accounts.exa_mint,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.destination,
+        &ctx.accounts.escrow_authority,
+        mint_decimals(&ctx.accounts.exa_mint)?,
+        escrow.amount,
+        &[&[b"ESCROW", &[ctx.bumps.escrow_authority]]],
+        ctx.remaining_accounts,
+    )?;
+
+    msg!(
+        "Recovered {} escrowed tokens for {} to {}",
+        escrow.amount,
+        hex::encode(&escrow.to),
+        ctx.accounts.destination.key()
+    );
+
+    Ok(())
+}
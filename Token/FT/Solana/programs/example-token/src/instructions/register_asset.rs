@@ -0,0 +1,201 @@
+use crate::{
+    error::*,
+    state::*,
+    utils::{mint_decimals, require_no_transfer_hook},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+use solana_invoke::invoke;
+use spl_associated_token_account::instruction::create_associated_token_account;
+use spl_token::solana_program::program_pack::Pack;
+use spl_token_2022::instruction::initialize_mint2;
+
+#[derive(Accounts)]
+#[instruction(asset_id: u64)]
+pub struct RegisterAsset<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        init,
+        space = 8 + Asset::space(),
+        payer = payer,
+        seeds = [&b"ASSET"[..], &asset_id.to_le_bytes()],
+        bump
+    )]
+    asset: Account<'info, Asset>,
+    /// CHECK: it's derived from the asset mint seeds
+    #[account(mut, seeds = [&b"ASSET_MINT"[..], &asset_id.to_le_bytes()], bump)]
+    mint: AccountInfo<'info>,
+    /// CHECK: it's derived from the treasury seeds
+    #[account(seeds = [b"TREASURY"], bump)]
+    treasury_authority: AccountInfo<'info>,
+    /// CHECK: it's checked in CPI to be the treasury authority ATA
+    #[account(mut)]
+    treasury: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL token program
+    #[account(address = spl_token::ID)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: u64)]
+pub struct RegisterAssetLockRelease<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        init,
+        space = 8 + Asset::space(),
+        payer = payer,
+        seeds = [&b"ASSET"[..], &asset_id.to_le_bytes()],
+        bump
+    )]
+    asset: Account<'info, Asset>,
+    /// CHECK: it's checked to be owned by the token program
+    #[account(owner = token_program.key())]
+    mint: AccountInfo<'info>,
+    /// CHECK: it's checked in CPI to be the asset ATA
+    #[account(mut)]
+    vault: AccountInfo<'info>,
+    /// CHECK: it's derived from the treasury seeds
+    #[account(seeds = [b"TREASURY"], bump)]
+    treasury_authority: AccountInfo<'info>,
+    /// CHECK: it's checked in CPI to be the treasury authority ATA
+    #[account(mut)]
+    treasury: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL token or Token-2022 program
+    #[account(
+        constraint = token_program.key() == spl_token::ID
+            || token_program.key() == spl_token_2022::ID
+    )]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Registers the asset `asset_id`, creating its SPL token mint with the asset
+/// as the mint authority and its treasury.
+pub fn register_asset(ctx: Context<RegisterAsset>, asset_id: u64, decimals: u8) -> Result<()> {
+    let asset = &mut ctx.accounts.asset;
+    let mint = &ctx.accounts.mint;
+    let payer = &ctx.accounts.payer;
+    let token_program = &ctx.accounts.token_program;
+
+    require_neq!(asset_id, 0, ExampleTokenError::InvalidAssetId);
+
+    asset.asset_id = asset_id;
+    asset.mint = mint.key();
+    asset.decimals = decimals;
+
+    let rent = Rent::get()?;
+    let space = spl_token::state::Mint::LEN;
+    let mint_seeds: &[&[&[u8]]] = &[&[b"ASSET_MINT", &asset_id.to_le_bytes(), &[ctx.bumps.mint]]];
+    let ctx2 = CpiContext::new_with_signer(
+        ctx.accounts.system_program.to_account_info(),
+        CreateAccount {
+            from: payer.to_account_info(),
+            to: mint.to_account_info(),
+        },
+        mint_seeds,
+    );
+    create_account(
+        ctx2,
+        rent.minimum_balance(space),
+        space as u64,
+        token_program.key,
+    )?;
+
+    let ix = initialize_mint2(token_program.key, mint.key, &asset.key(), None, decimals)?;
+    invoke(&ix, &[mint.to_account_info()])?;
+
+    create_treasury(
+        payer,
+        &ctx.accounts.treasury,
+        &ctx.accounts.treasury_authority,
+        mint,
+    )?;
+
+    msg!("Registered asset {} with mint {}", asset_id, mint.key());
+
+    Ok(())
+}
+
+/// Registers the asset `asset_id` in the lock/release mode for an existing
+/// `mint`, creating the vault the bridged tokens are locked in and the
+/// treasury. The extension derives the accounts from the mint carried in the
+/// incoming transfers, so until the asset origins on the other chains are set
+/// with it, incoming transfers of the asset fail.
+pub fn register_asset_lock_release(
+    ctx: Context<RegisterAssetLockRelease>,
+    asset_id: u64,
+) -> Result<()> {
+    let asset = &mut ctx.accounts.asset;
+    let mint = &ctx.accounts.mint;
+    let vault = &ctx.accounts.vault;
+    let payer = &ctx.accounts.payer;
+
+    require_neq!(asset_id, 0, ExampleTokenError::InvalidAssetId);
+    require_no_transfer_hook(mint)?;
+
+    asset.asset_id = asset_id;
+    asset.mint = mint.key();
+    asset.decimals = mint_decimals(mint)?;
+    asset.lock_release = true;
+
+    let ix = create_associated_token_account(payer.key, &asset.key(), mint.key, mint.owner);
+    invoke(
+        &ix,
+        &[
+            payer.to_account_info(),
+            vault.to_account_info(),
+            asset.to_account_info(),
+            mint.to_account_info(),
+        ],
+    )?;
+
+    create_treasury(
+        payer,
+        &ctx.accounts.treasury,
+        &ctx.accounts.treasury_authority,
+        mint,
+    )?;
+
+    msg!("Registered asset {} locking {}", asset_id, mint.key());
+
+    Ok(())
+}
+
+/// Creates the treasury collecting the protocol fees in the asset.
+fn create_treasury<'info>(
+    payer: &Signer<'info>,
+    treasury: &AccountInfo<'info>,
+    treasury_authority: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+) -> Result<()> {
+    let ix =
+        create_associated_token_account(payer.key, treasury_authority.key, mint.key, mint.owner);
+    invoke(
+        &ix,
+        &[
+            payer.to_account_info(),
+            treasury.clone(),
+            treasury_authority.clone(),
+            mint.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
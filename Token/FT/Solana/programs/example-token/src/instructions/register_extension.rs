@@ -1,4 +1,4 @@
-use crate::state::*;
+use crate::{error::*, state::*};
 use anchor_lang::prelude::*;
 use uip_solana_sdk::UipEndpoint;
 
@@ -11,8 +11,13 @@ pub struct RegisterExtension<'info> {
     /// CHECK: checked in CPI
     #[account(seeds = [b"UIP_SIGNER"], bump)]
     program_signer: AccountInfo<'info>,
-    #[account(address = config.admin)]
-    admin: Signer<'info>,
+    #[account(
+        seeds = [b"ROLES"],
+        bump,
+        constraint = roles.has_role(Role::ExtensionManager, authority.key) @ ExampleTokenError::MissingRole,
+    )]
+    roles: Account<'info, Roles>,
+    authority: Signer<'info>,
     #[account(mut)]
     payer: Signer<'info>,
     uip_program: Program<'info, UipEndpoint>,
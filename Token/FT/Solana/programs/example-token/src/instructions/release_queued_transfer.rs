@@ -0,0 +1,188 @@
+use super::execute::{create_escrow, is_compliant};
+use crate::{
+    error::*,
+    state::*,
+    utils::{create_ata_if_needed, credit, find_ata, is_deliverable},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32])]
+pub struct ReleaseQueuedTransfer<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(mut, seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        has_one = to,
+        constraint = queued_transfer.asset_id.is_none() @ ExampleTokenError::QueuedAssetMismatch,
+        seeds = [&b"QUEUED"[..], &msg_hash],
+        bump
+    )]
+    queued_transfer: Account<'info, QueuedTransfer>,
+    #[account(
+        mut,
+        seeds = [&b"ORIGIN"[..], &queued_transfer.src_chain_id.to_le_bytes()],
+        bump
+    )]
+    origin: Account<'info, Origin>,
+    /// CHECK: it's derived from the message hash and only created if the
+    /// transfer can no longer be delivered
+    #[account(mut, seeds = [&b"ESCROW"[..], &msg_hash], bump)]
+    escrow: AccountInfo<'info>,
+    /// CHECK: it's checked to be the account that paid for the queued transfer
+    #[account(mut)]
+    rent_payer: AccountInfo<'info>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(mut, address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the config ATA in the lock/release mode
+    #[account(mut)]
+    vault: Option<AccountInfo<'info>>,
+    /// CHECK: it's derived from the escrow seeds
+    #[account(seeds = [b"ESCROW"], bump)]
+    escrow_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the escrow authority ATA
+    #[account(
+        mut,
+        address = find_ata(escrow_authority.key, exa_mint.key, exa_mint.owner)
+    )]
+    escrow_vault: AccountInfo<'info>,
+    /// CHECK: it's checked to be the `to` ATA
+    #[account(mut)]
+    token_account: AccountInfo<'info>,
+    /// CHECK: it's checked to be the recipient of the queued transfer
+    to: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+    /// CHECK: it's derived from the blocklist seeds and deserialized in the
+    /// handler
+    #[account(seeds = [b"BLOCKLIST"], bump)]
+    blocklist: AccountInfo<'info>,
+    /// CHECK: it's checked to be the attestation PDA of the recipient if
+    /// attestations are required
+    attestation: AccountInfo<'info>,
+}
+
+/// Mints the tokens of a queued transfer once its settlement delay, if any, has
+/// elapsed and the inbound limit allows it. A transfer above the inbound
+/// capacity is released once the limit is fully replenished. The tokens are
+/// escrowed instead if the transfer no longer passes the compliance checks or
+/// can't be delivered. Can be called by anyone.
+pub fn release_queued_transfer(
+    ctx: Context<ReleaseQueuedTransfer>,
+    msg_hash: [u8; 32],
+) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let config = &mut ctx.accounts.config;
+    let queued_transfer = &ctx.accounts.queued_transfer;
+    let origin = &mut ctx.accounts.origin;
+    let exa_mint = &ctx.accounts.exa_mint;
+    let token_account = &ctx.accounts.token_account;
+    let to = &ctx.accounts.to;
+    let system_program = &ctx.accounts.system_program;
+
+    require!(!config.inbound_paused, ExampleTokenError::InboundPaused);
+    require!(
+        !config.is_route_paused(origin.chain_id),
+        ExampleTokenError::RoutePaused
+    );
+
+    require!(
+        token_account.key() == find_ata(to.key, exa_mint.key, exa_mint.owner),
+        ErrorCode::ConstraintAddress
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= queued_transfer.release_after,
+        ExampleTokenError::SettlementDelayNotElapsed
+    );
+    require!(
+        origin
+            .inbound_limit
+            .try_consume_capped(queued_transfer.amount, now),
+        ExampleTokenError::InboundRateLimitExceeded
+    );
+
+    // The recipient or the sender may have been blocklisted, or the recipient
+    // may have lost its attestation, since the transfer was queued.
+    let blocklist = Blocklist::load(&ctx.accounts.blocklist)?;
+    if !is_deliverable(to, token_account, exa_mint)
+        || !is_compliant(
+            config,
+            &blocklist,
+            &ctx.accounts.attestation,
+            &queued_transfer.from,
+            to.key,
+        )?
+    {
+        let escrow_vault = &ctx.accounts.escrow_vault;
+
+        create_escrow(
+            payer,
+            &ctx.accounts.escrow,
+            system_program,
+            &[b"ESCROW", &msg_hash, &[ctx.bumps.escrow]],
+            Escrow {
+                src_chain_id: origin.chain_id,
+                mint: exa_mint.key(),
+                from: queued_transfer.from.clone(),
+                to: queued_transfer.to.to_bytes().to_vec(),
+                amount: queued_transfer.amount,
+                rent_payer: payer.key(),
+                claimable_after: now,
+            },
+        )?;
+        create_ata_if_needed(
+            payer,
+            escrow_vault,
+            &ctx.accounts.escrow_authority,
+            exa_mint,
+        )?;
+        credit(
+            config,
+            &[b"CONFIG", &[ctx.bumps.config]],
+            exa_mint,
+            ctx.accounts.vault.as_ref(),
+            escrow_vault,
+            queued_transfer.amount,
+        )?;
+        origin.total_inbound += u128::from(queued_transfer.amount);
+
+        msg!(
+            "Escrowed {} queued tokens for {}",
+            queued_transfer.amount,
+            queued_transfer.to
+        );
+
+        return Ok(());
+    }
+
+    create_ata_if_needed(payer, token_account, to, exa_mint)?;
+    credit(
+        config,
+        &[b"CONFIG", &[ctx.bumps.config]],
+        exa_mint,
+        ctx.accounts.vault.as_ref(),
+        token_account,
+        queued_transfer.amount,
+    )?;
+    origin.total_inbound += u128::from(queued_transfer.amount);
+
+    msg!(
+        "{} received {} queued tokens",
+        queued_transfer.to,
+        queued_transfer.amount
+    );
+
+    Ok(())
+}
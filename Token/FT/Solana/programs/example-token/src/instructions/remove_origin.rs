@@ -0,0 +1,28 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(chain_id: u128)]
+pub struct RemoveOrigin<'info> {
+    #[account(
+        seeds = [b"ROLES"],
+        bump,
+        constraint = roles.has_role(Role::OriginManager, authority.key) @ ExampleTokenError::MissingRole,
+    )]
+    roles: Account<'info, Roles>,
+    #[account(mut, seeds = [&b"ORIGIN"[..], &chain_id.to_le_bytes()], bump)]
+    origin: Account<'info, Origin>,
+    authority: Signer<'info>,
+}
+
+/// Forgets the example token deployment known for `chain_id`, disabling the
+/// origin. The account is kept, so that its supply counters are still
+/// accounted for by `reconcile_supply` and restored if the origin is set again.
+pub fn remove_origin(ctx: Context<RemoveOrigin>, _chain_id: u128) -> Result<()> {
+    let origin = &mut ctx.accounts.origin;
+
+    origin.address = [0; 32];
+    origin.enabled = false;
+
+    Ok(())
+}
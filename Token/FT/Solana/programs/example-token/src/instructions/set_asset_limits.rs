@@ -0,0 +1,35 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+use super::Limits;
+
+#[derive(Accounts)]
+#[instruction(asset_id: u64, chain_id: u128)]
+pub struct SetAssetLimits<'info> {
+    #[account(
+        seeds = [b"ROLES"],
+        bump,
+        constraint = roles.has_role(Role::OriginManager, authority.key) @ ExampleTokenError::MissingRole,
+    )]
+    roles: Account<'info, Roles>,
+    #[account(
+        mut,
+        seeds = [&b"ORIGIN"[..], &chain_id.to_le_bytes(), &asset_id.to_le_bytes()],
+        bump
+    )]
+    origin: Account<'info, Origin>,
+    authority: Signer<'info>,
+}
+
+/// Sets the volume limits for bridging the asset `asset_id` to and from the
+/// chain `chain_id`, in the decimals of the asset.
+pub fn set_asset_limits(
+    ctx: Context<SetAssetLimits>,
+    _asset_id: u64,
+    _chain_id: u128,
+    limits: Limits,
+) -> Result<()> {
+    limits.apply(&mut ctx.accounts.origin, Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(asset_id: u64, chain_id: u128)]
+pub struct SetAssetOrigin<'info> {
+    #[account(
+        seeds = [b"ROLES"],
+        bump,
+        constraint = roles.has_role(Role::OriginManager, authority.key) @ ExampleTokenError::MissingRole,
+    )]
+    roles: Account<'info, Roles>,
+    #[account(seeds = [&b"ASSET"[..], &asset_id.to_le_bytes()], bump)]
+    asset: Account<'info, Asset>,
+    #[account(
+        init_if_needed,
+        space = 8 + Origin::space(),
+        payer = payer,
+        seeds = [&b"ORIGIN"[..], &chain_id.to_le_bytes(), &asset_id.to_le_bytes()],
+        bump
+    )]
+    origin: Account<'info, Origin>,
+    authority: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Creates or updates the deployment known for the asset `asset_id` on
+/// `chain_id`, with the mint of the asset there if it's bridged in the
+/// lock/release mode.
+#[allow(clippy::too_many_arguments)]
+pub fn set_asset_origin(
+    ctx: Context<SetAssetOrigin>,
+    _asset_id: u64,
+    chain_id: u128,
+    address: [u8; 32],
+    enabled: bool,
+    decimals: u8,
+    remote_mint: Option<Pubkey>,
+    remote_token_2022: bool,
+) -> Result<()> {
+    let origin = &mut ctx.accounts.origin;
+
+    origin.chain_id = chain_id;
+    origin.address = address;
+    origin.enabled = enabled;
+    origin.decimals = decimals;
+    origin.remote_mint = remote_mint;
+    origin.remote_token_2022 = remote_token_2022;
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAttestationAuthority<'info> {
+    #[account(mut, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+}
+
+pub fn set_attestation_authority(
+    ctx: Context<SetAttestationAuthority>,
+    authority: Option<Pubkey>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.attestation_authority = authority;
+
+    Ok(())
+}
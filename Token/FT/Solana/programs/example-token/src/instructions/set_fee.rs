@@ -0,0 +1,51 @@
+use crate::{error::*, state::*, utils::create_ata_if_needed};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    #[account(mut)]
+    admin: Signer<'info>,
+    /// CHECK: it's derived from the treasury seeds
+    #[account(seeds = [b"TREASURY"], bump)]
+    treasury_authority: AccountInfo<'info>,
+    /// CHECK: it's checked in CPI to be the treasury authority ATA
+    #[account(mut)]
+    treasury: AccountInfo<'info>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Sets the protocol fee to `fee_bps` of bridged amounts, of which the
+/// `referrer_share_bps` share goes to the referrer. Creates the treasury if
+/// needed.
+pub fn set_fee(ctx: Context<SetFee>, fee_bps: u16, referrer_share_bps: u16) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    require!(
+        fee_bps <= ExampleTokenConfig::BPS_DENOMINATOR
+            && referrer_share_bps <= ExampleTokenConfig::BPS_DENOMINATOR,
+        ExampleTokenError::InvalidBps
+    );
+
+    config.fee_bps = fee_bps;
+    config.referrer_share_bps = referrer_share_bps;
+
+    create_ata_if_needed(
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.treasury,
+        &ctx.accounts.treasury_authority,
+        &ctx.accounts.exa_mint,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFinalizedThreshold<'info> {
+    #[account(mut, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+}
+
+pub fn set_finalized_threshold(ctx: Context<SetFinalizedThreshold>, threshold: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.finalized_threshold = threshold;
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetForwardGasLimit<'info> {
+    #[account(mut, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+}
+
+pub fn set_forward_gas_limit(
+    ctx: Context<SetForwardGasLimit>,
+    gas_limit: Option<u128>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.forward_gas_limit = gas_limit;
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+}
+
+/// Sets the `guardian` that can pause the bridge and cancel queued transfers.
+pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.guardian = guardian;
+
+    Ok(())
+}
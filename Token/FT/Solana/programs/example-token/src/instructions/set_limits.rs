@@ -0,0 +1,61 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(chain_id: u128)]
+pub struct SetLimits<'info> {
+    #[account(
+        seeds = [b"ROLES"],
+        bump,
+        constraint = roles.has_role(Role::OriginManager, authority.key) @ ExampleTokenError::MissingRole,
+    )]
+    roles: Account<'info, Roles>,
+    #[account(mut, seeds = [&b"ORIGIN"[..], &chain_id.to_le_bytes()], bump)]
+    origin: Account<'info, Origin>,
+    authority: Signer<'info>,
+}
+
+/// Limits on the volume bridged to and from a remote chain.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Limits {
+    /// Maximal volume bridged to the chain within `outbound_window`, 0 if
+    /// unbounded.
+    pub outbound_capacity: u64,
+    /// Length of the outbound window in seconds.
+    pub outbound_window: u64,
+    /// Maximal volume bridged from the chain within `inbound_window`, 0 if
+    /// unbounded.
+    pub inbound_capacity: u64,
+    /// Length of the inbound window in seconds.
+    pub inbound_window: u64,
+    /// Minimal amount of a single outgoing transfer.
+    pub min_amount: u64,
+    /// Maximal amount of a single outgoing transfer, 0 if unbounded.
+    pub max_amount: u64,
+    /// Maximal amount a single wallet can bridge to the chain per day, 0 if
+    /// unbounded.
+    pub user_daily_cap: u64,
+}
+
+/// Sets the volume limits for the chain `chain_id`. The rate limit buckets
+/// are refilled.
+pub fn set_limits(ctx: Context<SetLimits>, _chain_id: u128, limits: Limits) -> Result<()> {
+    limits.apply(&mut ctx.accounts.origin, Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
+
+impl Limits {
+    /// Sets the limits of the `origin`, refilling the rate limit buckets.
+    pub(super) fn apply(&self, origin: &mut Origin, now: i64) {
+        origin
+            .outbound_limit
+            .reset(self.outbound_capacity, self.outbound_window, now);
+        origin
+            .inbound_limit
+            .reset(self.inbound_capacity, self.inbound_window, now);
+        origin.min_amount = self.min_amount;
+        origin.max_amount = self.max_amount;
+        origin.user_daily_cap = self.user_daily_cap;
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{error::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(chain_id: u128)]
+pub struct SetOrigin<'info> {
+    #[account(
+        seeds = [b"ROLES"],
+        bump,
+        constraint = roles.has_role(Role::OriginManager, authority.key) @ ExampleTokenError::MissingRole,
+    )]
+    roles: Account<'info, Roles>,
+    #[account(
+        init_if_needed,
+        space = 8 + Origin::space(),
+        payer = payer,
+        seeds = [&b"ORIGIN"[..], &chain_id.to_le_bytes()],
+        bump
+    )]
+    origin: Account<'info, Origin>,
+    authority: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Creates or updates the example token deployment known for `chain_id`.
+pub fn set_origin(
+    ctx: Context<SetOrigin>,
+    chain_id: u128,
+    address: [u8; 32],
+    enabled: bool,
+    decimals: u8,
+) -> Result<()> {
+    let origin = &mut ctx.accounts.origin;
+
+    origin.chain_id = chain_id;
+    origin.address = address;
+    origin.enabled = enabled;
+    origin.decimals = decimals;
+
+    Ok(())
+}
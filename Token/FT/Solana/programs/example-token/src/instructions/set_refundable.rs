@@ -0,0 +1,38 @@
+use crate::{error::*, state::*, utils::is_solana_chain};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(chain_id: u128)]
+pub struct SetRefundable<'info> {
+    #[account(
+        seeds = [b"ROLES"],
+        bump,
+        constraint = roles.has_role(Role::OriginManager, authority.key) @ ExampleTokenError::MissingRole,
+    )]
+    roles: Account<'info, Roles>,
+    #[account(mut, seeds = [&b"ORIGIN"[..], &chain_id.to_le_bytes()], bump)]
+    origin: Account<'info, Origin>,
+    authority: Signer<'info>,
+}
+
+/// Sets whether the chain `chain_id` bounces back transfers it can't deliver,
+/// which requires a record of each outgoing transfer to it, and the time after
+/// which the sender can close a record. The window only applies to transfers
+/// recorded afterwards. Only Solana chains decode the refundable payload.
+pub fn set_refundable(
+    ctx: Context<SetRefundable>,
+    chain_id: u128,
+    refundable: bool,
+    refund_window: u64,
+) -> Result<()> {
+    require!(
+        !refundable || is_solana_chain(chain_id),
+        ExampleTokenError::SolanaOnlyPayload
+    );
+
+    let origin = &mut ctx.accounts.origin;
+    origin.refundable = refundable;
+    origin.refund_window = refund_window;
+
+    Ok(())
+}
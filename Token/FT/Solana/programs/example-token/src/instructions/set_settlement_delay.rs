@@ -0,0 +1,22 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetSettlementDelay<'info> {
+    #[account(mut, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+}
+
+pub fn set_settlement_delay(
+    ctx: Context<SetSettlementDelay>,
+    threshold: u64,
+    delay: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.settlement_threshold = threshold;
+    config.settlement_delay = delay;
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::{error::*, instructions::Metadata, metaplex, state::*};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use solana_invoke::invoke_signed;
+use spl_token_2022::{
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+use spl_token_metadata_interface::{
+    instruction::update_field,
+    state::{Field, TokenMetadata},
+};
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(mut, address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the EXA mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the Metaplex metadata account of the EXA
+    /// mint, required for an SPL token mint
+    #[account(mut, address = metaplex::find_metadata(exa_mint.key))]
+    metaplex_metadata: Option<AccountInfo<'info>>,
+    /// CHECK: it's checked to be the Metaplex token metadata program
+    #[account(address = metaplex::ID)]
+    metaplex_program: Option<AccountInfo<'info>>,
+    system_program: Program<'info, System>,
+}
+
+/// Replaces the name, symbol and URI of the EXA mint.
+pub fn update_metadata(ctx: Context<UpdateMetadata>, metadata: Metadata) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let exa_mint = &ctx.accounts.exa_mint;
+    let token_program = &ctx.accounts.token_program;
+    let config_seeds: &[&[&[u8]]] = &[&[b"CONFIG", &[ctx.bumps.config]]];
+
+    require!(
+        !config.lock_release,
+        ExampleTokenError::UnsupportedInLockReleaseMode
+    );
+
+    let Metadata { name, symbol, uri } = metadata;
+
+    if *token_program.key != spl_token_2022::ID {
+        let metaplex_metadata = ctx
+            .accounts
+            .metaplex_metadata
+            .as_ref()
+            .ok_or(ErrorCode::ConstraintAccountIsNone)?;
+        require!(
+            ctx.accounts.metaplex_program.is_some(),
+            ErrorCode::ConstraintAccountIsNone
+        );
+        let ix = metaplex::update_metadata_account_v2(
+            metaplex_metadata.key,
+            &config.key(),
+            name,
+            symbol,
+            uri,
+        )?;
+        invoke_signed(
+            &ix,
+            &[
+                metaplex_metadata.to_account_info(),
+                config.to_account_info(),
+            ],
+            config_seeds,
+        )?;
+
+        return Ok(());
+    }
+
+    let mut token_metadata = {
+        let data = exa_mint.try_borrow_data()?;
+        StateWithExtensions::<Mint>::unpack(&data)?.get_variable_len_extension::<TokenMetadata>()?
+    };
+    let rent = Rent::get()?;
+
+    for (field, value) in [
+        (Field::Name, name),
+        (Field::Symbol, symbol),
+        (Field::Uri, uri),
+    ] {
+        // The mint is reallocated to fit the updated field, so the payer
+        // covers the rent of the difference.
+        let old_size = token_metadata.tlv_size_of()?;
+        token_metadata.update(field.clone(), value.clone());
+        let new_len = exa_mint.data_len() - old_size + token_metadata.tlv_size_of()?;
+        let lamports = rent
+            .minimum_balance(new_len)
+            .saturating_sub(exa_mint.lamports());
+        if lamports != 0 {
+            let ctx2 = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: exa_mint.to_account_info(),
+                },
+            );
+            transfer(ctx2, lamports)?;
+        }
+
+        let ix = update_field(token_program.key, exa_mint.key, &config.key(), field, value);
+        invoke_signed(
+            &ix,
+            &[exa_mint.to_account_info(), config.to_account_info()],
+            config_seeds,
+        )?;
+    }
+
+    Ok(())
+}
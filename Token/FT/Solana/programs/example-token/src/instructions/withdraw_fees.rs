@@ -0,0 +1,49 @@
+use crate::{
+    state::*,
+    utils::{find_ata, transfer},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+    /// CHECK: it's derived from the treasury seeds
+    #[account(seeds = [b"TREASURY"], bump)]
+    treasury_authority: AccountInfo<'info>,
+    /// CHECK: it's checked to be the treasury authority ATA
+    #[account(
+        mut,
+        address = find_ata(treasury_authority.key, exa_mint.key, exa_mint.owner)
+    )]
+    treasury: AccountInfo<'info>,
+    /// CHECK: it's checked to be the bridged mint
+    #[account(address = config.mint)]
+    exa_mint: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    #[account(mut)]
+    destination: AccountInfo<'info>,
+    /// CHECK: it's checked to be the token program owning the bridged mint
+    #[account(address = *exa_mint.owner)]
+    token_program: AccountInfo<'info>,
+}
+
+/// Withdraws `amount` of the collected protocol fees to `destination`.
+pub fn withdraw_fees<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawFees<'info>>,
+    amount: u64,
+) -> Result<()> {
+    transfer(
+        &ctx.accounts.exa_mint,
+        &ctx.accounts.treasury,
+        &ctx.accounts.destination,
+        &ctx.accounts.treasury_authority,
+        ctx.accounts.config.decimals,
+        amount,
+        &[&[b"TREASURY", &[ctx.bumps.treasury_authority]]],
+        ctx.remaining_accounts,
+    )?;
+
+    Ok(())
+}
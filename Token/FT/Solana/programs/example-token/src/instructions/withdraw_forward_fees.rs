@@ -0,0 +1,46 @@
+use crate::{error::*, state::*};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct WithdrawForwardFees<'info> {
+    #[account(has_one = admin)]
+    config: Account<'info, ExampleTokenConfig>,
+    admin: Signer<'info>,
+    #[account(mut, seeds = [b"FORWARD_FEE_VAULT"], bump)]
+    fee_vault: SystemAccount<'info>,
+    /// CHECK: it only receives lamports
+    #[account(mut)]
+    destination: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Withdraws `amount` lamports from the forward fee vault to `destination`.
+/// The vault keeps its rent-exempt reserve unless emptied.
+pub fn withdraw_forward_fees(ctx: Context<WithdrawForwardFees>, amount: u64) -> Result<()> {
+    let fee_vault = &ctx.accounts.fee_vault;
+
+    let remaining = fee_vault
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ExampleTokenError::ForwardFeeVaultDepleted)?;
+    require!(
+        remaining == 0 || remaining >= Rent::get()?.minimum_balance(0),
+        ExampleTokenError::ForwardFeeVaultDepleted
+    );
+
+    let vault_seeds: &[&[&[u8]]] = &[&[b"FORWARD_FEE_VAULT", &[ctx.bumps.fee_vault]]];
+    let ctx2 = CpiContext::new_with_signer(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: fee_vault.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+        },
+        vault_seeds,
+    );
+    transfer(ctx2, amount)?;
+
+    Ok(())
+}
@@ -1,12 +1,14 @@
 //! Example token protocol, utilizing UIP for cross-chain communication.
 #![allow(unexpected_cfgs)]
 
-use crate::instructions::*;
+use crate::{instructions::*, state::Role};
 use anchor_lang::prelude::*;
 
-mod addresses;
 pub mod error;
+pub mod events;
 mod instructions;
+mod metaplex;
+pub mod payload;
 pub mod state;
 mod utils;
 
@@ -25,9 +27,39 @@ solana_security_txt::security_txt! {
 pub mod example_token {
     use super::*;
 
-    /// Initializes the example token bridge contract.
-    pub fn initialize(ctx: Context<Initialize>, admin: Pubkey, decimals: u8) -> Result<()> {
-        instructions::initialize(ctx, admin, decimals)
+    /// Initializes the example token bridge contract. The EXA mint is created
+    /// under Token-2022 with the specified extensions if `token_2022` is set.
+    /// The `metadata` is embedded in a Token-2022 mint or created with
+    /// Metaplex otherwise. The config becomes the freeze authority of the
+    /// mint if `freezable` is set. With a transfer hook, the instructions
+    /// transferring EXA take the extra accounts of the hook as their remaining
+    /// accounts. The `chain_id` of the Solana cluster is signed in permits.
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        admin: Pubkey,
+        decimals: u8,
+        token_2022: Option<Token2022Extensions>,
+        metadata: Option<Metadata>,
+        freezable: bool,
+        chain_id: u128,
+    ) -> Result<()> {
+        instructions::initialize(
+            ctx, admin, decimals, token_2022, metadata, freezable, chain_id,
+        )
+    }
+
+    /// Initializes the example token bridge contract in the lock/release mode,
+    /// bridging an existing `mint` by locking it in a vault. The extension
+    /// has to be rebuilt with the mint in `EXA_LOCKED_MINT` and registered
+    /// again, or incoming transfers fail. Mints with a transfer hook are
+    /// rejected.
+    pub fn initialize_lock_release(
+        ctx: Context<InitializeLockRelease>,
+        admin: Pubkey,
+        chain_id: u128,
+    ) -> Result<()> {
+        instructions::initialize_lock_release(ctx, admin, chain_id)
     }
 
     /// Mint tokens.
@@ -40,19 +72,74 @@ pub mod example_token {
         instructions::register_extension(ctx, ipfs_cid)
     }
 
-    /// Sends tokens to a specified destination, paying the specified `uip_fee`.
-    pub fn bridge(
-        ctx: Context<Bridge>,
-        destination: Destination,
-        to: Vec<u8>,
+    /// Sends tokens to the chain `dest_chain_id`, paying the specified
+    /// `uip_fee`. The destination contract is resolved from the origin set for
+    /// the chain, and the recipient must be an address of that chain. The
+    /// protocol fee is taken from `amount`, with a share going to the
+    /// `referrer` if set. The transfer is processed once the transaction
+    /// reaches the `commitment`, or the finalized one if `amount` is above the
+    /// threshold set by the admin.
+    #[allow(clippy::too_many_arguments)]
+    pub fn bridge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Bridge<'info>>,
+        dest_chain_id: u128,
+        to: Recipient,
         amount: u64,
         uip_fee: u64,
         custom_gas_limit: u128,
+        referrer: Option<Pubkey>,
+        commitment: ProposalCommitment,
+    ) -> Result<()> {
+        instructions::bridge(
+            ctx,
+            dest_chain_id,
+            to,
+            amount,
+            uip_fee,
+            custom_gas_limit,
+            referrer,
+            commitment,
+        )
+    }
+
+    /// Sends tokens to many recipients on the chain `dest_chain_id` in a single
+    /// message, paying the specified `uip_fee` once. The protocol fee is taken
+    /// from each amount. Only Solana deployments decode the batch payload, so
+    /// `dest_chain_id` must be a Solana chain.
+    pub fn bridge_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Bridge<'info>>,
+        dest_chain_id: u128,
+        recipients: Vec<BatchRecipient>,
+        uip_fee: u64,
+        custom_gas_limit: u128,
+        commitment: ProposalCommitment,
     ) -> Result<()> {
-        instructions::bridge(ctx, destination, to, amount, uip_fee, custom_gas_limit)
+        instructions::bridge_batch(
+            ctx,
+            dest_chain_id,
+            recipients,
+            uip_fee,
+            custom_gas_limit,
+            commitment,
+        )
     }
 
-    /// Executes an incoming cross-chain message.
+    /// Sends tokens of the owner who signed the `permit` off-chain, with the
+    /// relayer paying the rent and the `uip_fee`. The ed25519 signature must
+    /// be verified by the preceding instruction, and the owner must have
+    /// approved the `PERMIT` PDA as a delegate of their token account.
+    pub fn bridge_with_permit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BridgeWithPermit<'info>>,
+        permit: Permit,
+        uip_fee: u64,
+    ) -> Result<()> {
+        instructions::bridge_with_permit(ctx, permit, uip_fee)
+    }
+
+    /// Executes an incoming cross-chain message. The batch, asset and refund
+    /// payloads are only sent by Solana deployments, while the forward payload
+    /// is accepted from any chain sending it, which the EVM example token
+    /// doesn't.
     #[instruction(discriminator = uip_solana_sdk::EXECUTE_DISCRIMINATOR)]
     pub fn execute<'info>(ctx: Context<'_, '_, 'info, 'info, Execute>) -> Result<()> {
         instructions::execute(ctx)
@@ -63,4 +150,312 @@ pub mod example_token {
     pub fn update_admin(ctx: Context<UpdateAdmin>, new_admin: Pubkey) -> Result<()> {
         instructions::update_admin(ctx, new_admin)
     }
+
+    /// Sets the example token contract `address` on the chain `chain_id`,
+    /// whether bridging to and from it is `enabled` and the `decimals` of the
+    /// token there.
+    pub fn set_origin(
+        ctx: Context<SetOrigin>,
+        chain_id: u128,
+        address: [u8; 32],
+        enabled: bool,
+        decimals: u8,
+    ) -> Result<()> {
+        instructions::set_origin(ctx, chain_id, address, enabled, decimals)
+    }
+
+    /// Removes the example token contract known on the chain `chain_id`. The
+    /// origin is disabled rather than closed, keeping its supply counters.
+    pub fn remove_origin(ctx: Context<RemoveOrigin>, chain_id: u128) -> Result<()> {
+        instructions::remove_origin(ctx, chain_id)
+    }
+
+    /// Sets the volume limits for bridging to and from the chain `chain_id`.
+    pub fn set_limits(ctx: Context<SetLimits>, chain_id: u128, limits: Limits) -> Result<()> {
+        instructions::set_limits(ctx, chain_id, limits)
+    }
+
+    /// Sets whether the chain `chain_id` bounces back transfers it can't
+    /// deliver, so that transfers to it are recorded to be refunded, and the
+    /// `refund_window` in seconds after which a record can be closed. Only
+    /// Solana chains decode the refundable payload, so only they can be
+    /// refundable.
+    pub fn set_refundable(
+        ctx: Context<SetRefundable>,
+        chain_id: u128,
+        refundable: bool,
+        refund_window: u64,
+    ) -> Result<()> {
+        instructions::set_refundable(ctx, chain_id, refundable, refund_window)
+    }
+
+    /// Closes the record of the outgoing transfer `nonce` once its refund
+    /// window has elapsed, giving up its refund.
+    pub fn close_outbound_transfer(ctx: Context<CloseOutboundTransfer>, nonce: u64) -> Result<()> {
+        instructions::close_outbound_transfer(ctx, nonce)
+    }
+
+    /// Mints the tokens of an incoming transfer that was queued because it
+    /// exceeded the inbound limit or the settlement threshold.
+    pub fn release_queued_transfer(
+        ctx: Context<ReleaseQueuedTransfer>,
+        msg_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::release_queued_transfer(ctx, msg_hash)
+    }
+
+    /// Releases the tokens of an incoming asset transfer that was queued
+    /// because it exceeded the settlement threshold.
+    pub fn release_queued_asset_transfer(
+        ctx: Context<ReleaseQueuedAssetTransfer>,
+        msg_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::release_queued_asset_transfer(ctx, msg_hash)
+    }
+
+    /// Cancels a queued incoming transfer, so that its tokens are never
+    /// minted.
+    pub fn cancel_queued_transfer(
+        ctx: Context<CancelQueuedTransfer>,
+        msg_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::cancel_queued_transfer(ctx, msg_hash)
+    }
+
+    /// Sets the amount from which incoming transfers are queued for `delay`
+    /// seconds before they can be released, 0 to disable it.
+    pub fn set_settlement_delay(
+        ctx: Context<SetSettlementDelay>,
+        threshold: u64,
+        delay: u64,
+    ) -> Result<()> {
+        instructions::set_settlement_delay(ctx, threshold, delay)
+    }
+
+    /// Sets the guardian that can pause the bridge.
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian(ctx, guardian)
+    }
+
+    /// Pauses outbound or inbound bridging, or a single route.
+    pub fn pause(ctx: Context<Pause>, target: PauseTarget) -> Result<()> {
+        instructions::pause(ctx, target)
+    }
+
+    /// Unpauses outbound or inbound bridging, or a single route.
+    pub fn unpause(ctx: Context<Unpause>, target: PauseTarget) -> Result<()> {
+        instructions::unpause(ctx, target)
+    }
+
+    /// Sets the protocol fee and the referrer share of it, in basis points.
+    pub fn set_fee(ctx: Context<SetFee>, fee_bps: u16, referrer_share_bps: u16) -> Result<()> {
+        instructions::set_fee(ctx, fee_bps, referrer_share_bps)
+    }
+
+    /// Withdraws collected protocol fees from the treasury.
+    pub fn withdraw_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawFees<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_fees(ctx, amount)
+    }
+
+    /// Claims the tokens of an incoming transfer that could not be delivered
+    /// to the signing recipient.
+    pub fn claim<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Claim<'info>>,
+        msg_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::claim(ctx, msg_hash)
+    }
+
+    /// Recovers the tokens of an incoming transfer that could not be delivered
+    /// and cannot be claimed by its recipient.
+    pub fn recover_escrow<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecoverEscrow<'info>>,
+        msg_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::recover_escrow(ctx, msg_hash)
+    }
+
+    /// Grants `role` to `account`.
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, account: Pubkey) -> Result<()> {
+        instructions::grant_role(ctx, role, account)
+    }
+
+    /// Revokes `role` from `account`.
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role, account: Pubkey) -> Result<()> {
+        instructions::revoke_role(ctx, role, account)
+    }
+
+    /// Logs the amount of tokens outstanding on each chain whose origin is
+    /// passed in the remaining accounts.
+    pub fn reconcile_supply<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReconcileSupply<'info>>,
+    ) -> Result<()> {
+        instructions::reconcile_supply(ctx)
+    }
+
+    /// Updates the name, symbol and URI of the EXA mint.
+    pub fn update_metadata(ctx: Context<UpdateMetadata>, metadata: Metadata) -> Result<()> {
+        instructions::update_metadata(ctx, metadata)
+    }
+
+    /// Sets the amount from which outgoing transfers wait for the finalized
+    /// commitment, 0 to disable it.
+    pub fn set_finalized_threshold(
+        ctx: Context<SetFinalizedThreshold>,
+        threshold: u64,
+    ) -> Result<()> {
+        instructions::set_finalized_threshold(ctx, threshold)
+    }
+
+    /// Freezes a token account of the EXA mint.
+    pub fn freeze(ctx: Context<Freeze>) -> Result<()> {
+        instructions::freeze(ctx)
+    }
+
+    /// Thaws a frozen token account of the EXA mint.
+    pub fn thaw(ctx: Context<Freeze>) -> Result<()> {
+        instructions::thaw(ctx)
+    }
+
+    /// Blocks `address`, left-padded to 32 bytes if it's an EVM one, from
+    /// sending and receiving bridged tokens.
+    pub fn block(ctx: Context<Block>, address: [u8; 32]) -> Result<()> {
+        instructions::block(ctx, address)
+    }
+
+    /// Removes `address` from the blocklist.
+    pub fn unblock(ctx: Context<Unblock>, address: [u8; 32]) -> Result<()> {
+        instructions::unblock(ctx, address)
+    }
+
+    /// Sets the authority whose attestations recipients of incoming transfers
+    /// must hold, `None` to not require them.
+    pub fn set_attestation_authority(
+        ctx: Context<SetAttestationAuthority>,
+        authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_attestation_authority(ctx, authority)
+    }
+
+    /// Attests that `recipient` can receive bridged tokens.
+    pub fn attest(ctx: Context<Attest>, recipient: Pubkey) -> Result<()> {
+        instructions::attest(ctx, recipient)
+    }
+
+    /// Revokes the attestation of `recipient`.
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>, recipient: Pubkey) -> Result<()> {
+        instructions::revoke_attestation(ctx, recipient)
+    }
+
+    /// Registers the asset `asset_id`, creating its mint with `decimals`.
+    pub fn register_asset(ctx: Context<RegisterAsset>, asset_id: u64, decimals: u8) -> Result<()> {
+        instructions::register_asset(ctx, asset_id, decimals)
+    }
+
+    /// Registers the asset `asset_id` in the lock/release mode, bridging an
+    /// existing `mint` by locking it in a vault. The asset origins on the other
+    /// chains have to be set with the mint, or incoming transfers of the asset
+    /// fail. Mints with a transfer hook are rejected.
+    pub fn register_asset_lock_release(
+        ctx: Context<RegisterAssetLockRelease>,
+        asset_id: u64,
+    ) -> Result<()> {
+        instructions::register_asset_lock_release(ctx, asset_id)
+    }
+
+    /// Sets the contract `address` of the asset `asset_id` on the chain
+    /// `chain_id`, whether bridging the asset to and from it is `enabled` and
+    /// the `decimals` of the asset there. The `remote_mint` of an asset bridged
+    /// there in the lock/release mode, owned by Token-2022 if
+    /// `remote_token_2022` is set, is carried in the transfers of the asset so
+    /// that the extension there can derive its accounts.
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_asset_origin(
+        ctx: Context<SetAssetOrigin>,
+        asset_id: u64,
+        chain_id: u128,
+        address: [u8; 32],
+        enabled: bool,
+        decimals: u8,
+        remote_mint: Option<Pubkey>,
+        remote_token_2022: bool,
+    ) -> Result<()> {
+        instructions::set_asset_origin(
+            ctx,
+            asset_id,
+            chain_id,
+            address,
+            enabled,
+            decimals,
+            remote_mint,
+            remote_token_2022,
+        )
+    }
+
+    /// Sets the volume limits for bridging the asset `asset_id` to and from
+    /// the chain `chain_id`.
+    pub fn set_asset_limits(
+        ctx: Context<SetAssetLimits>,
+        asset_id: u64,
+        chain_id: u128,
+        limits: Limits,
+    ) -> Result<()> {
+        instructions::set_asset_limits(ctx, asset_id, chain_id, limits)
+    }
+
+    /// Sends tokens of the asset `asset_id` to the chain `dest_chain_id`,
+    /// paying the specified `uip_fee`. The protocol fee is taken from
+    /// `amount`. Only Solana deployments decode the asset payload, so
+    /// `dest_chain_id` must be a Solana chain.
+    #[allow(clippy::too_many_arguments)]
+    pub fn bridge_asset(
+        ctx: Context<BridgeAsset>,
+        asset_id: u64,
+        dest_chain_id: u128,
+        to: Recipient,
+        amount: u64,
+        uip_fee: u64,
+        custom_gas_limit: u128,
+        commitment: ProposalCommitment,
+    ) -> Result<()> {
+        instructions::bridge_asset(
+            ctx,
+            asset_id,
+            dest_chain_id,
+            to,
+            amount,
+            uip_fee,
+            custom_gas_limit,
+            commitment,
+        )
+    }
+
+    /// Sends an incoming transfer routed through Solana to its final
+    /// destination chain, paying the UIP fee chosen by the payer from the
+    /// forward fee vault.
+    pub fn forward(ctx: Context<Forward>, msg_hash: [u8; 32], uip_fee: u64) -> Result<()> {
+        instructions::forward(ctx, msg_hash, uip_fee)
+    }
+
+    /// Escrows the tokens of a transfer that can't be forwarded or bounced.
+    pub fn cancel_forward(ctx: Context<CancelForward>, msg_hash: [u8; 32]) -> Result<()> {
+        instructions::cancel_forward(ctx, msg_hash)
+    }
+
+    /// Sets the custom gas limit of forwarded transfers, `None` to disable
+    /// forwarding.
+    pub fn set_forward_gas_limit(
+        ctx: Context<SetForwardGasLimit>,
+        gas_limit: Option<u128>,
+    ) -> Result<()> {
+        instructions::set_forward_gas_limit(ctx, gas_limit)
+    }
+
+    /// Withdraws lamports from the forward fee vault.
+    pub fn withdraw_forward_fees(ctx: Context<WithdrawForwardFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_forward_fees(ctx, amount)
+    }
 }
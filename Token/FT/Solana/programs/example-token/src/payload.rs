@@ -0,0 +1,278 @@
+//! Example token cross-chain payload.
+
+use alloy_sol_types::{
+    sol_data::{Array, Bool, Bytes, FixedBytes, Uint},
+    SolType,
+};
+use anchor_lang::solana_program::pubkey::MAX_SEED_LEN;
+use ruint::aliases::U256;
+
+/// Maximal number of accounts passed to the program called on delivery,
+/// bounded by the accounts the extension can pass to the execution.
+pub const MAX_CALL_ACCOUNTS: usize = 13;
+
+/// Maximal number of recipients of a batch transfer, bounded by the accounts
+/// the extension can pass to the execution.
+pub const MAX_BATCH_RECIPIENTS: usize = 4;
+
+type TransferAbi = (Bytes, Bytes, Uint<256>);
+type CallAbi = (FixedBytes<32>, Array<(FixedBytes<32>, Bool)>, Bytes);
+type TransferAndCallAbi = (Bytes, Bytes, Uint<256>, CallAbi);
+type AssetTransferAbi = (Uint<64>, Bytes, Bytes, Uint<256>, FixedBytes<32>, Bool);
+type TransferBatchAbi = (Bytes, Array<(Bytes, Uint<256>)>);
+type TransferForwardAbi = (Bytes, Bytes, Uint<256>, Uint<128>);
+type RefundableTransferAbi = (Uint<64>, TransferAbi);
+type RefundAbi = (Uint<64>, (Bytes, Uint<256>));
+
+/// A token transfer between chains.
+#[derive(Debug)]
+pub struct Transfer {
+    /// Sender of the tokens on the source chain.
+    pub from: Vec<u8>,
+    /// Recipient of the tokens on the destination chain.
+    pub to: Vec<u8>,
+    /// Amount of tokens in the decimals of the destination chain.
+    pub amount: U256,
+    /// Program to call once the tokens are delivered, if any.
+    pub call: Option<Call>,
+    /// Nonce of the transfer on the source chain if it's refunded when it
+    /// can't be delivered, in which case it carries no call.
+    pub nonce: Option<u64>,
+}
+
+/// A call to a Solana program made once the bridged tokens are delivered to
+/// the `CALLER` PDA of the source chain and sender.
+#[derive(Debug)]
+pub struct Call {
+    /// Program to invoke.
+    pub program_id: [u8; 32],
+    /// Accounts passed to the program after the caller PDA, with whether they
+    /// are writable.
+    pub accounts: Vec<([u8; 32], bool)>,
+    /// Instruction data passed to the program.
+    pub data: Vec<u8>,
+}
+
+impl Transfer {
+    /// Decodes a transfer, followed by a call if the payload carries one, or
+    /// preceded by its nonce if it's refundable.
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        if let Ok((from, to, amount)) = TransferAbi::abi_decode_params(payload, true) {
+            return Some(Self {
+                from: from.into(),
+                to: to.into(),
+                amount,
+                call: None,
+                nonce: None,
+            });
+        }
+
+        if let Ok((nonce, (from, to, amount))) =
+            RefundableTransferAbi::abi_decode_params(payload, true)
+        {
+            return Some(Self {
+                from: from.into(),
+                to: to.into(),
+                amount,
+                call: None,
+                nonce: Some(nonce),
+            });
+        }
+
+        let (from, to, amount, (program_id, accounts, data)) =
+            TransferAndCallAbi::abi_decode_params(payload, true).ok()?;
+        // The sender seeds the caller PDA, so it can't exceed a seed length.
+        if accounts.len() > MAX_CALL_ACCOUNTS || from.len() > MAX_SEED_LEN {
+            return None;
+        }
+
+        Some(Self {
+            from: from.into(),
+            to: to.into(),
+            amount,
+            call: Some(Call {
+                program_id: program_id.0,
+                accounts: accounts
+                    .into_iter()
+                    .map(|(key, is_writable)| (key.0, is_writable))
+                    .collect(),
+                data: data.into(),
+            }),
+            nonce: None,
+        })
+    }
+
+    /// Encodes the transfer as a payload. The nonce is dropped if the transfer
+    /// carries a call.
+    pub fn encode(&self) -> Vec<u8> {
+        match (&self.call, self.nonce) {
+            (None, None) => {
+                TransferAbi::abi_encode_params(&(self.from.clone(), self.to.clone(), self.amount))
+            }
+            (None, Some(nonce)) => RefundableTransferAbi::abi_encode_params(&(
+                nonce,
+                (self.from.clone(), self.to.clone(), self.amount),
+            )),
+            (Some(call), _) => TransferAndCallAbi::abi_encode_params(&(
+                self.from.clone(),
+                self.to.clone(),
+                self.amount,
+                (call.program_id, call.accounts.clone(), call.data.clone()),
+            )),
+        }
+    }
+}
+
+/// A token transfer from one sender to many recipients between chains.
+#[derive(Debug)]
+pub struct TransferBatch {
+    /// Sender of the tokens on the source chain.
+    pub from: Vec<u8>,
+    /// Recipients of the tokens on the destination chain, with the amounts in
+    /// the decimals of the destination chain.
+    pub transfers: Vec<(Vec<u8>, U256)>,
+}
+
+impl TransferBatch {
+    /// Decodes a batch transfer with at least one and at most
+    /// [`MAX_BATCH_RECIPIENTS`] recipients.
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        let (from, transfers) = TransferBatchAbi::abi_decode_params(payload, true).ok()?;
+        if transfers.is_empty() || transfers.len() > MAX_BATCH_RECIPIENTS {
+            return None;
+        }
+
+        Some(Self {
+            from: from.into(),
+            transfers: transfers
+                .into_iter()
+                .map(|(to, amount)| (to.into(), amount))
+                .collect(),
+        })
+    }
+
+    /// Encodes the batch transfer as a payload.
+    pub fn encode(&self) -> Vec<u8> {
+        TransferBatchAbi::abi_encode_params(&(self.from.clone(), self.transfers.clone()))
+    }
+}
+
+/// A transfer of a registered asset between chains, routed by its id. EXA
+/// transfers don't carry an asset id.
+#[derive(Debug)]
+pub struct AssetTransfer {
+    /// Identifier of the asset.
+    pub asset_id: u64,
+    /// Sender of the tokens on the source chain.
+    pub from: Vec<u8>,
+    /// Recipient of the tokens on the destination chain.
+    pub to: Vec<u8>,
+    /// Amount of tokens in the decimals of the destination chain.
+    pub amount: U256,
+    /// Mint of the asset on the destination chain if it's bridged there in the
+    /// lock/release mode, encoded as zero bytes if it's the mint created by
+    /// the program, which the extension derives from the asset id.
+    pub mint: Option<[u8; 32]>,
+    /// Whether the mint on the destination chain is owned by Token-2022.
+    pub token_2022: bool,
+}
+
+impl AssetTransfer {
+    /// Decodes a transfer of a registered asset.
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        let (asset_id, from, to, amount, mint, token_2022) =
+            AssetTransferAbi::abi_decode_params(payload, true).ok()?;
+
+        Some(Self {
+            asset_id,
+            from: from.into(),
+            to: to.into(),
+            amount,
+            mint: (mint != [0; 32]).then_some(mint.0),
+            token_2022,
+        })
+    }
+
+    /// Encodes the asset transfer as a payload.
+    pub fn encode(&self) -> Vec<u8> {
+        AssetTransferAbi::abi_encode_params(&(
+            self.asset_id,
+            self.from.clone(),
+            self.to.clone(),
+            self.amount,
+            self.mint.unwrap_or_default(),
+            self.token_2022,
+        ))
+    }
+}
+
+/// A token transfer routed through Solana to a final destination chain, which
+/// isn't directly connected to the source one. It's forwarded as a
+/// [`Transfer`] preserving the sender and the recipient.
+#[derive(Debug)]
+pub struct TransferForward {
+    /// Sender of the tokens on the source chain.
+    pub from: Vec<u8>,
+    /// Recipient of the tokens on the final destination chain.
+    pub to: Vec<u8>,
+    /// Amount of tokens in the decimals of Solana as the destination chain.
+    pub amount: U256,
+    /// Identifier of the final destination chain.
+    pub dest_chain_id: u128,
+}
+
+impl TransferForward {
+    /// Decodes a transfer to be forwarded.
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        let (from, to, amount, dest_chain_id) =
+            TransferForwardAbi::abi_decode_params(payload, true).ok()?;
+
+        Some(Self {
+            from: from.into(),
+            to: to.into(),
+            amount,
+            dest_chain_id,
+        })
+    }
+
+    /// Encodes the transfer to be forwarded as a payload.
+    pub fn encode(&self) -> Vec<u8> {
+        TransferForwardAbi::abi_encode_params(&(
+            self.from.clone(),
+            self.to.clone(),
+            self.amount,
+            self.dest_chain_id,
+        ))
+    }
+}
+
+/// A refund of a transfer that couldn't be delivered on the destination chain,
+/// sent back to the source chain of the transfer.
+#[derive(Debug)]
+pub struct Refund {
+    /// Nonce of the refunded transfer on the chain receiving the refund.
+    pub nonce: u64,
+    /// Original sender of the tokens, who gets them back.
+    pub from: Vec<u8>,
+    /// Amount of tokens refunded in the decimals of the chain receiving the
+    /// refund.
+    pub amount: U256,
+}
+
+impl Refund {
+    /// Decodes a refund.
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        let (nonce, (from, amount)) = RefundAbi::abi_decode_params(payload, true).ok()?;
+
+        Some(Self {
+            nonce,
+            from: from.into(),
+            amount,
+        })
+    }
+
+    /// Encodes the refund as a payload.
+    pub fn encode(&self) -> Vec<u8> {
+        RefundAbi::abi_encode_params(&(self.nonce, (self.from.clone(), self.amount)))
+    }
+}
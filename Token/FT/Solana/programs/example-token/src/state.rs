@@ -1,6 +1,6 @@
 //! Example token account structures.
 
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
 
 /// The smart contract configuration.
 #[account]
@@ -8,10 +8,734 @@ use anchor_lang::prelude::*;
 pub struct ExampleTokenConfig {
     /// Administrator key that can be used to register the UIP extension.
     pub admin: Pubkey,
+    /// Decimals of the EXA mint.
+    pub decimals: u8,
+    /// Key that can pause the bridge. Only the admin can unpause it.
+    pub guardian: Pubkey,
+    /// Whether sending tokens to other chains is paused.
+    pub outbound_paused: bool,
+    /// Whether receiving tokens from other chains is paused.
+    pub inbound_paused: bool,
+    /// Chains to and from which bridging is paused.
+    pub paused_routes: Vec<u128>,
+    /// The bridged mint, either the EXA mint or an existing one in the
+    /// lock/release mode.
+    pub mint: Pubkey,
+    /// Whether tokens are locked in the vault and released from it instead of
+    /// being burned and minted.
+    pub lock_release: bool,
+    /// Amount of tokens locked in the vault in the lock/release mode.
+    pub vault_balance: u64,
+    /// Protocol fee taken from bridged amounts, in basis points.
+    pub fee_bps: u16,
+    /// Share of the protocol fee paid to the referrer, in basis points.
+    pub referrer_share_bps: u16,
+    /// Amount from which outgoing transfers wait for the finalized commitment
+    /// regardless of the one requested, 0 if disabled.
+    pub finalized_threshold: u64,
+    /// Authority issuing the attestations that recipients of incoming
+    /// transfers must hold, if required.
+    pub attestation_authority: Option<Pubkey>,
+    /// Custom gas limit of the onward message of forwarded transfers, `None`
+    /// if forwarding is disabled.
+    pub forward_gas_limit: Option<u128>,
+    /// Amount from which incoming transfers are queued for the settlement
+    /// delay before they can be released, 0 if disabled.
+    pub settlement_threshold: u64,
+    /// Delay in seconds before a transfer queued by the settlement threshold
+    /// can be released.
+    pub settlement_delay: u64,
+    /// Nonce of the next outgoing transfer to a refundable origin.
+    pub next_transfer_nonce: u64,
+    /// Identifier of the Solana cluster the contract is deployed on, which
+    /// permits are bound to.
+    pub chain_id: u128,
 }
 
 impl ExampleTokenConfig {
+    /// Maximal number of individually paused routes.
+    pub const MAX_PAUSED_ROUTES: usize = 16;
+
+    pub(crate) fn space() -> usize {
+        let space_admin = 32;
+        let space_decimals = 1;
+        let space_guardian = 32;
+        let space_outbound_paused = 1;
+        let space_inbound_paused = 1;
+        let space_paused_routes = 4 + 16 * Self::MAX_PAUSED_ROUTES;
+        let space_mint = 32;
+        let space_lock_release = 1;
+        let space_vault_balance = 8;
+        let space_fee_bps = 2;
+        let space_referrer_share_bps = 2;
+        let space_finalized_threshold = 8;
+        let space_attestation_authority = 1 + 32;
+        let space_forward_gas_limit = 1 + 16;
+        let space_settlement_threshold = 8;
+        let space_settlement_delay = 8;
+        let space_next_transfer_nonce = 8;
+        let space_chain_id = 16;
+        space_admin
+            + space_decimals
+            + space_guardian
+            + space_outbound_paused
+            + space_inbound_paused
+            + space_paused_routes
+            + space_mint
+            + space_lock_release
+            + space_vault_balance
+            + space_fee_bps
+            + space_referrer_share_bps
+            + space_finalized_threshold
+            + space_attestation_authority
+            + space_forward_gas_limit
+            + space_settlement_threshold
+            + space_settlement_delay
+            + space_next_transfer_nonce
+            + space_chain_id
+    }
+
+    /// Denominator of the basis point values.
+    pub const BPS_DENOMINATOR: u16 = 10_000;
+
+    pub(crate) fn is_route_paused(&self, chain_id: u128) -> bool {
+        self.paused_routes.contains(&chain_id)
+    }
+
+    /// Returns whether an incoming transfer of `amount` must wait for the
+    /// settlement delay.
+    pub(crate) fn is_delayed(&self, amount: u64) -> bool {
+        self.settlement_threshold != 0 && amount >= self.settlement_threshold
+    }
+}
+
+/// Bookkeeping of a bridged mint, which is the EXA mint of the config or the
+/// mint of a registered asset. The pool account is the mint authority, or the
+/// owner of the vault in the lock/release mode.
+pub trait Pool: AccountSerialize + AccountDeserialize + Owner + Clone {
+    /// Decimals of the mint.
+    fn decimals(&self) -> u8;
+    /// Whether tokens are locked in the vault and released from it instead of
+    /// being burned and minted.
+    fn lock_release(&self) -> bool;
+    /// Amount of tokens locked in the vault in the lock/release mode.
+    fn vault_balance_mut(&mut self) -> &mut u64;
+}
+
+impl Pool for ExampleTokenConfig {
+    fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    fn lock_release(&self) -> bool {
+        self.lock_release
+    }
+
+    fn vault_balance_mut(&mut self) -> &mut u64 {
+        &mut self.vault_balance
+    }
+}
+
+/// A bridged asset other than EXA, registered by the admin under an asset id
+/// carried in the payload.
+#[account]
+#[derive(Debug)]
+pub struct Asset {
+    /// Identifier of the asset, shared by all chains.
+    pub asset_id: u64,
+    /// The bridged mint, either created for the asset or an existing one in
+    /// the lock/release mode.
+    pub mint: Pubkey,
+    /// Decimals of the mint.
+    pub decimals: u8,
+    /// Whether tokens are locked in the vault and released from it instead of
+    /// being burned and minted.
+    pub lock_release: bool,
+    /// Amount of tokens locked in the vault in the lock/release mode.
+    pub vault_balance: u64,
+}
+
+impl Asset {
+    pub(crate) fn space() -> usize {
+        let space_asset_id = 8;
+        let space_mint = 32;
+        let space_decimals = 1;
+        let space_lock_release = 1;
+        let space_vault_balance = 8;
+        space_asset_id + space_mint + space_decimals + space_lock_release + space_vault_balance
+    }
+}
+
+impl Pool for Asset {
+    fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    fn lock_release(&self) -> bool {
+        self.lock_release
+    }
+
+    fn vault_balance_mut(&mut self) -> &mut u64 {
+        &mut self.vault_balance
+    }
+}
+
+/// A known example token deployment on another chain. The origins of a
+/// registered asset are derived from its id as well.
+#[account]
+#[derive(Debug)]
+pub struct Origin {
+    /// Identifier of the remote chain.
+    pub chain_id: u128,
+    /// Address of the example token contract on the remote chain.
+    pub address: [u8; 32],
+    /// Whether tokens can be bridged to and from the remote chain.
+    pub enabled: bool,
+    /// Decimals of the token on the remote chain. Amounts in the payload are
+    /// denominated in them.
+    pub decimals: u8,
+    /// Limit on the volume of tokens bridged to the remote chain.
+    pub outbound_limit: RateLimit,
+    /// Limit on the volume of tokens bridged from the remote chain.
+    pub inbound_limit: RateLimit,
+    /// Minimal amount of a single outgoing transfer.
+    pub min_amount: u64,
+    /// Maximal amount of a single outgoing transfer, 0 if unbounded.
+    pub max_amount: u64,
+    /// Maximal amount a single wallet can bridge to the remote chain per day,
+    /// 0 if unbounded.
+    pub user_daily_cap: u64,
+    /// Total amount of tokens sent to the remote chain, in local decimals.
+    pub total_outbound: u128,
+    /// Total amount of tokens received from the remote chain, in local
+    /// decimals. Queued transfers are counted once released.
+    pub total_inbound: u128,
+    /// Whether the remote chain bounces back transfers it can't deliver, in
+    /// which case outgoing transfers are recorded to be refunded.
+    pub refundable: bool,
+    /// Time in seconds after which the sender of a recorded transfer can close
+    /// its record, giving up the refund.
+    pub refund_window: u64,
+    /// Mint of the asset on the remote chain if it's bridged there in the
+    /// lock/release mode, carried in asset transfers so that the extension
+    /// there can derive its accounts. Unused for EXA.
+    pub remote_mint: Option<Pubkey>,
+    /// Whether the remote mint is owned by Token-2022.
+    pub remote_token_2022: bool,
+}
+
+impl Origin {
+    /// Returns the amount of tokens sent to the remote chain and not yet
+    /// received back, which is negative if more was received than sent.
+    pub fn outstanding(&self) -> i128 {
+        self.total_outbound as i128 - self.total_inbound as i128
+    }
+
+    pub(crate) fn space() -> usize {
+        let space_chain_id = 16;
+        let space_address = 32;
+        let space_enabled = 1;
+        let space_decimals = 1;
+        let space_outbound_limit = RateLimit::space();
+        let space_inbound_limit = RateLimit::space();
+        let space_min_amount = 8;
+        let space_max_amount = 8;
+        let space_user_daily_cap = 8;
+        let space_total_outbound = 16;
+        let space_total_inbound = 16;
+        let space_refundable = 1;
+        let space_refund_window = 8;
+        let space_remote_mint = 1 + 32;
+        let space_remote_token_2022 = 1;
+        space_chain_id
+            + space_address
+            + space_enabled
+            + space_decimals
+            + space_outbound_limit
+            + space_inbound_limit
+            + space_min_amount
+            + space_max_amount
+            + space_user_daily_cap
+            + space_total_outbound
+            + space_total_inbound
+            + space_refundable
+            + space_refund_window
+            + space_remote_mint
+            + space_remote_token_2022
+    }
+}
+
+/// A rolling window limit on the bridged volume, implemented as a token
+/// bucket refilled linearly over the window.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct RateLimit {
+    /// Maximal volume within the window, 0 if unbounded.
+    pub capacity: u64,
+    /// Length of the window in seconds.
+    pub window: u64,
+    /// Volume currently available.
+    pub available: u64,
+    /// Timestamp up to which the elapsed time was converted into capacity.
+    pub last_updated: i64,
+}
+
+impl RateLimit {
+    pub(crate) fn space() -> usize {
+        let space_capacity = 8;
+        let space_window = 8;
+        let space_available = 8;
+        let space_last_updated = 8;
+        space_capacity + space_window + space_available + space_last_updated
+    }
+
+    /// Replaces the limit parameters, filling the bucket up.
+    pub(crate) fn reset(&mut self, capacity: u64, window: u64, now: i64) {
+        self.capacity = capacity;
+        self.window = window;
+        self.available = capacity;
+        self.last_updated = now;
+    }
+
+    /// Refills the bucket for the time passed since the last update and
+    /// consumes `amount` from it. Returns `false` without consuming anything
+    /// if the volume available is insufficient.
+    pub(crate) fn try_consume(&mut self, amount: u64, now: i64) -> bool {
+        if self.capacity == 0 {
+            return true;
+        }
+
+        let elapsed = now.saturating_sub(self.last_updated).max(0) as u128;
+        let refill = if self.window == 0 {
+            self.capacity as u128
+        } else {
+            elapsed * self.capacity as u128 / self.window as u128
+        };
+        if refill > 0 {
+            let refilled = self.available as u128 + refill;
+            if refilled >= self.capacity as u128 || self.window == 0 {
+                self.available = self.capacity;
+                self.last_updated = now;
+            } else {
+                // Only advance by the time converted into capacity, rounded
+                // up, so that the remainder carries over to the next refill.
+                let converted = (refill * self.window as u128).div_ceil(self.capacity as u128);
+                self.available = refilled as u64;
+                self.last_updated += converted as i64;
+            }
+        }
+
+        if amount > self.available {
+            return false;
+        }
+        self.available -= amount;
+        true
+    }
+
+    /// Like `try_consume`, but lets an `amount` above the capacity through
+    /// once the bucket is full, emptying it, as it would never fit otherwise.
+    pub(crate) fn try_consume_capped(&mut self, amount: u64, now: i64) -> bool {
+        self.try_consume(amount.min(self.capacity), now)
+    }
+}
+
+/// Volume bridged by a wallet to a remote chain during the current day.
+#[account]
+#[derive(Debug)]
+pub struct UserUsage {
+    /// Number of the day since the Unix epoch.
+    pub day: i64,
+    /// Amount bridged during the day.
+    pub amount: u64,
+}
+
+impl UserUsage {
+    pub(crate) fn space() -> usize {
+        let space_day = 8;
+        let space_amount = 8;
+        space_day + space_amount
+    }
+}
+
+/// Addresses that can't send or receive bridged tokens, in the format of the
+/// payload, where EVM addresses are left-padded to 32 bytes.
+#[account]
+#[derive(Debug, Default)]
+pub struct Blocklist {
+    pub addresses: Vec<[u8; 32]>,
+}
+
+impl Blocklist {
+    /// Maximal number of blocked addresses.
+    pub const MAX_ADDRESSES: usize = 100;
+
+    pub(crate) fn space() -> usize {
+        4 + 32 * Self::MAX_ADDRESSES
+    }
+
+    /// Loads the blocklist from its PDA, which is empty if nothing was ever
+    /// blocked.
+    pub(crate) fn load(blocklist: &AccountInfo) -> Result<Self> {
+        if blocklist.data_is_empty() {
+            return Ok(Self::default());
+        }
+        Self::try_deserialize(&mut &blocklist.try_borrow_data()?[..])
+    }
+
+    /// Returns whether `address` in the payload format is blocked.
+    pub(crate) fn contains(&self, address: &[u8]) -> bool {
+        self.addresses.iter().any(|blocked| blocked[..] == *address)
+    }
+}
+
+/// Attestation that a wallet can receive bridged tokens, issued by the
+/// attestation authority.
+#[account]
+#[derive(Debug)]
+pub struct Attestation {
+    /// Authority that issued the attestation.
+    pub issuer: Pubkey,
+}
+
+impl Attestation {
     pub(crate) fn space() -> usize {
         32
     }
+
+    /// Returns whether `recipient` may receive tokens, which requires
+    /// `attestation` to be its attestation PDA issued by the current authority
+    /// if the config requires one.
+    pub(crate) fn is_satisfied(
+        config: &ExampleTokenConfig,
+        attestation: &AccountInfo,
+        recipient: &Pubkey,
+    ) -> Result<bool> {
+        let Some(authority) = config.attestation_authority else {
+            return Ok(true);
+        };
+        let (key, _) =
+            Pubkey::find_program_address(&[b"ATTESTATION", recipient.as_ref()], &crate::ID);
+        if *attestation.key != key || attestation.data_is_empty() {
+            return Ok(false);
+        }
+        let attestation = Self::try_deserialize(&mut &attestation.try_borrow_data()?[..])?;
+        Ok(attestation.issuer == authority)
+    }
+}
+
+/// Nonce of the next bridge permit signed by a token owner.
+#[account]
+#[derive(Debug)]
+pub struct PermitNonce {
+    /// Nonce the next permit must carry.
+    pub nonce: u64,
+}
+
+impl PermitNonce {
+    pub(crate) fn space() -> usize {
+        8
+    }
+}
+
+/// An incoming transfer that exceeded the inbound limit or the settlement
+/// threshold and awaits release.
+#[account]
+#[derive(Debug)]
+pub struct QueuedTransfer {
+    /// Hash of the message, which differs from the key of the queued transfer
+    /// for a recipient of a batch transfer.
+    pub msg_hash: [u8; 32],
+    /// Identifier of the source chain.
+    pub src_chain_id: u128,
+    /// Identifier of the registered asset, or `None` for EXA.
+    pub asset_id: Option<u64>,
+    /// Sender of the tokens as encoded in the message.
+    pub from: Vec<u8>,
+    /// Recipient of the tokens.
+    pub to: Pubkey,
+    /// Amount of tokens to mint, in local decimals.
+    pub amount: u64,
+    /// Account that paid the rent and gets it back on release.
+    pub rent_payer: Pubkey,
+    /// Timestamp from which the transfer can be released.
+    pub release_after: i64,
+}
+
+impl QueuedTransfer {
+    pub(crate) fn space(from_len: usize) -> usize {
+        let space_msg_hash = 32;
+        let space_src_chain_id = 16;
+        let space_asset_id = 1 + 8;
+        let space_from = 4 + from_len;
+        let space_to = 32;
+        let space_amount = 8;
+        let space_rent_payer = 32;
+        let space_release_after = 8;
+        space_msg_hash
+            + space_src_chain_id
+            + space_asset_id
+            + space_from
+            + space_to
+            + space_amount
+            + space_rent_payer
+            + space_release_after
+    }
+}
+
+/// An incoming transfer routed through Solana that awaits being proposed to its
+/// final destination chain, or a refundable transfer that couldn't be delivered
+/// and awaits being bounced back to its source chain.
+#[account]
+#[derive(Debug)]
+pub struct PendingForward {
+    /// Identifier of the source chain.
+    pub src_chain_id: u128,
+    /// Identifier of the final destination chain, which is the source chain
+    /// for a bounced transfer.
+    pub dest_chain_id: u128,
+    /// Original sender of the tokens as encoded in the message.
+    pub from: Vec<u8>,
+    /// Recipient of the tokens on the final destination chain as encoded in
+    /// the message.
+    pub to: Vec<u8>,
+    /// Amount of tokens to forward, in local decimals.
+    pub amount: u64,
+    /// Account that paid the rent and gets it back once forwarded.
+    pub rent_payer: Pubkey,
+    /// Nonce of the transfer on the source chain if it's bounced back as a
+    /// refund.
+    pub refund_nonce: Option<u64>,
+}
+
+impl PendingForward {
+    pub(crate) fn space(from_len: usize, to_len: usize) -> usize {
+        let space_src_chain_id = 16;
+        let space_dest_chain_id = 16;
+        let space_from = 4 + from_len;
+        let space_to = 4 + to_len;
+        let space_amount = 8;
+        let space_rent_payer = 32;
+        let space_refund_nonce = 1 + 8;
+        space_src_chain_id
+            + space_dest_chain_id
+            + space_from
+            + space_to
+            + space_amount
+            + space_rent_payer
+            + space_refund_nonce
+    }
+}
+
+/// An outgoing transfer to a refundable origin, recorded under its nonce until
+/// it's refunded or its sender gives up the refund.
+#[account]
+#[derive(Debug)]
+pub struct OutboundTransfer {
+    /// Identifier of the destination chain.
+    pub dest_chain_id: u128,
+    /// Sender of the tokens, who gets the refund and the rent back.
+    pub sender: Pubkey,
+    /// Amount of tokens bridged after the protocol fee, in local decimals,
+    /// which bounds the refund.
+    pub amount: u64,
+    /// Timestamp from which the sender can close the record.
+    pub closable_after: i64,
+}
+
+impl OutboundTransfer {
+    pub(crate) fn space() -> usize {
+        let space_dest_chain_id = 16;
+        let space_sender = 32;
+        let space_amount = 8;
+        let space_closable_after = 8;
+        space_dest_chain_id + space_sender + space_amount + space_closable_after
+    }
+}
+
+/// An incoming transfer that could not be delivered to its recipient, held
+/// in the escrow until claimed.
+#[account]
+#[derive(Debug)]
+pub struct Escrow {
+    /// Identifier of the source chain.
+    pub src_chain_id: u128,
+    /// Mint of the tokens held, which is the EXA mint unless the transfer is
+    /// of a registered asset.
+    pub mint: Pubkey,
+    /// Sender of the tokens as encoded in the message.
+    pub from: Vec<u8>,
+    /// Recipient of the tokens as encoded in the message.
+    pub to: Vec<u8>,
+    /// Amount of tokens held, in local decimals.
+    pub amount: u64,
+    /// Account that paid the rent and gets it back on claim.
+    pub rent_payer: Pubkey,
+    /// Timestamp after which the tokens can be claimed, later than the
+    /// escrow creation for a transfer above the settlement threshold.
+    pub claimable_after: i64,
+}
+
+impl Escrow {
+    pub(crate) fn space(from_len: usize, to_len: usize) -> usize {
+        let space_src_chain_id = 16;
+        let space_mint = 32;
+        let space_from = 4 + from_len;
+        let space_to = 4 + to_len;
+        let space_amount = 8;
+        let space_rent_payer = 32;
+        let space_claimable_after = 8;
+        space_src_chain_id
+            + space_mint
+            + space_from
+            + space_to
+            + space_amount
+            + space_rent_payer
+            + space_claimable_after
+    }
+
+    /// Returns the recipient if it's a valid Solana address.
+    pub(crate) fn recipient(&self) -> Option<Pubkey> {
+        Pubkey::try_from(&self.to[..]).ok()
+    }
+
+    /// Returns the key used in place of the message hash in the escrow seeds
+    /// for the recipient at `index` of a batch transfer.
+    pub fn batch_key(msg_hash: &[u8; 32], index: u8) -> [u8; 32] {
+        hashv(&[msg_hash, &[index]]).to_bytes()
+    }
+}
+
+/// A record of an incoming transfer, created once per message.
+#[account]
+#[derive(Debug)]
+pub struct Receipt {
+    /// Identifier of the source chain.
+    pub src_chain_id: u128,
+    /// Sender of the tokens on the source chain.
+    pub sender: Vec<u8>,
+    /// Recipient of the tokens as encoded in the message, empty for a batch
+    /// transfer.
+    pub recipient: Vec<u8>,
+    /// Amount of tokens received, in local decimals.
+    pub amount: u64,
+    /// Slot in which the message was executed.
+    pub slot: u64,
+    /// What happened to the tokens on execution.
+    pub status: ReceiptStatus,
+}
+
+impl Receipt {
+    pub(crate) fn space(sender_len: usize, recipient_len: usize) -> usize {
+        let space_src_chain_id = 16;
+        let space_sender = 4 + sender_len;
+        let space_recipient = 4 + recipient_len;
+        let space_amount = 8;
+        let space_slot = 8;
+        let space_status = 1;
+        space_src_chain_id
+            + space_sender
+            + space_recipient
+            + space_amount
+            + space_slot
+            + space_status
+    }
+}
+
+/// Outcome of an incoming transfer.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReceiptStatus {
+    /// The tokens were delivered to the recipient.
+    Delivered,
+    /// The transfer exceeded the inbound limit or the settlement threshold
+    /// and was queued.
+    Queued,
+    /// The tokens could not be delivered and were escrowed, or some of them
+    /// for a batch transfer.
+    Escrowed,
+    /// The transfer is routed to another chain and awaits being forwarded.
+    Forwarded,
+    /// The queued transfer, or one of them for a batch transfer, was
+    /// cancelled by the guardian or a pauser.
+    Cancelled,
+    /// The refundable transfer could not be delivered and awaits being bounced
+    /// back to the source chain.
+    Bounced,
+    /// The tokens of an outgoing transfer that could not be delivered on the
+    /// destination chain were returned to its sender.
+    Refunded,
+    /// The amount, or the total of a batch transfer, doesn't fit into `u64`
+    /// in local decimals, so nothing was delivered.
+    Rejected,
+}
+
+/// Keys holding the roles that gate the program instructions. The roles are
+/// granted and revoked by the admin.
+#[account]
+#[derive(Debug)]
+pub struct Roles {
+    /// Keys allowed to mint EXA.
+    pub minters: Vec<Pubkey>,
+    /// Keys allowed to manage the known deployments on other chains and their
+    /// limits.
+    pub origin_managers: Vec<Pubkey>,
+    /// Keys allowed to pause the bridge and cancel queued transfers, in
+    /// addition to the guardian.
+    pub pausers: Vec<Pubkey>,
+    /// Keys allowed to register the UIP extension.
+    pub extension_managers: Vec<Pubkey>,
+}
+
+impl Roles {
+    /// Maximal number of holders of a single role.
+    pub const MAX_HOLDERS: usize = 8;
+
+    pub(crate) fn space() -> usize {
+        let space_role = 4 + 32 * Self::MAX_HOLDERS;
+        4 * space_role
+    }
+
+    pub(crate) fn holders(&self, role: Role) -> &Vec<Pubkey> {
+        match role {
+            Role::Minter => &self.minters,
+            Role::OriginManager => &self.origin_managers,
+            Role::Pauser => &self.pausers,
+            Role::ExtensionManager => &self.extension_managers,
+        }
+    }
+
+    pub(crate) fn holders_mut(&mut self, role: Role) -> &mut Vec<Pubkey> {
+        match role {
+            Role::Minter => &mut self.minters,
+            Role::OriginManager => &mut self.origin_managers,
+            Role::Pauser => &mut self.pausers,
+            Role::ExtensionManager => &mut self.extension_managers,
+        }
+    }
+
+    /// Returns whether `key` holds `role`.
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        self.holders(role).contains(key)
+    }
+}
+
+/// A role gating some of the program instructions.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// Can mint EXA.
+    Minter,
+    /// Can set and remove origins and their limits.
+    OriginManager,
+    /// Can pause the bridge and cancel queued transfers.
+    Pauser,
+    /// Can register the UIP extension.
+    ExtensionManager,
+}
+
+impl Role {
+    /// All the roles, granted to the admin on initialization.
+    pub const ALL: [Role; 4] = [
+        Role::Minter,
+        Role::OriginManager,
+        Role::Pauser,
+        Role::ExtensionManager,
+    ];
 }
@@ -1,17 +1,388 @@
-use anchor_lang::prelude::*;
+use crate::{error::*, state::*};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+use ruint::aliases::U256;
+use solana_invoke::{invoke, invoke_signed};
+use spl_associated_token_account::instruction::create_associated_token_account;
+use spl_token_2022::{
+    extension::{transfer_hook, StateWithExtensions},
+    instruction::{burn, mint_to, transfer_checked},
+    onchain::invoke_transfer_checked,
+    state::AccountState,
+};
+use uip_solana_sdk::chains::{SOLANA_DEVNET_CHAIN_ID, SOLANA_MAINNET_CHAIN_ID};
 
-pub fn find_ata(wallet_address: &Pubkey, token_mint_address: &Pubkey) -> Pubkey {
+pub fn find_ata(
+    wallet_address: &Pubkey,
+    token_mint_address: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Pubkey {
     const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
         pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
-    const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
 
     Pubkey::find_program_address(
         &[
             &wallet_address.to_bytes(),
-            &TOKEN_PROGRAM_ID.to_bytes(),
+            &token_program_id.to_bytes(),
             &token_mint_address.to_bytes(),
         ],
         &ASSOCIATED_TOKEN_PROGRAM_ID,
     )
     .0
 }
+
+/// Creates `token_account` as the ATA of `to` if it doesn't exist.
+pub fn create_ata_if_needed<'info>(
+    payer: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+) -> Result<()> {
+    if token_account.data_is_empty() {
+        let ix = create_associated_token_account(payer.key, to.key, mint.key, mint.owner);
+        invoke(
+            &ix,
+            &[
+                payer.clone(),
+                token_account.clone(),
+                to.clone(),
+                mint.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Checks whether tokens of `mint` can be delivered to `token_account`, the
+/// ATA of `to`, without the token program failing.
+pub fn is_deliverable(to: &AccountInfo, token_account: &AccountInfo, mint: &AccountInfo) -> bool {
+    if *to.key == Pubkey::default() || to.executable {
+        return false;
+    }
+    if token_account.data_is_empty() {
+        return true;
+    }
+    if token_account.owner != mint.owner {
+        return false;
+    }
+
+    let Ok(data) = token_account.try_borrow_data() else {
+        return false;
+    };
+    match StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data) {
+        Ok(account) => {
+            account.base.mint == *mint.key
+                && account.base.owner == *to.key
+                && account.base.state == AccountState::Initialized
+        }
+        Err(_) => false,
+    }
+}
+
+/// Takes `amount` tokens out of `token_account` of `sender`, burning them or
+/// locking them in the vault of the `pool` depending on its mode, signed with
+/// `signer_seeds` if `sender` is a PDA. Returns the amount taken out of
+/// circulation, which is less than `amount` if the mint charges a transfer
+/// fee.
+pub fn debit<'info, P: Pool>(
+    pool: &mut Account<'info, P>,
+    mint: &AccountInfo<'info>,
+    vault: Option<&AccountInfo<'info>>,
+    token_account: &AccountInfo<'info>,
+    sender: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    if !pool.lock_release() {
+        let ix = burn(
+            mint.owner,
+            token_account.key,
+            mint.key,
+            sender.key,
+            &[],
+            amount,
+        )?;
+        invoke_signed(
+            &ix,
+            &[token_account.clone(), mint.clone(), sender.clone()],
+            signer_seeds,
+        )?;
+        return Ok(amount);
+    }
+
+    let vault = checked_vault(pool, mint, vault)?;
+    let balance_before = token_amount(vault)?;
+
+    transfer(
+        mint,
+        token_account,
+        vault,
+        sender,
+        pool.decimals(),
+        amount,
+        signer_seeds,
+        &[],
+    )?;
+
+    let locked = token_amount(vault)? - balance_before;
+    let vault_balance = pool.vault_balance_mut();
+    *vault_balance = vault_balance
+        .checked_add(locked)
+        .ok_or(ExampleTokenError::AmountOverflow)?;
+
+    Ok(locked)
+}
+
+/// Gives `amount` tokens to `token_account`, minting them or releasing them
+/// from the vault of the `pool` depending on its mode, signed by the pool
+/// with `pool_seeds`.
+pub fn credit<'info, P: Pool>(
+    pool: &mut Account<'info, P>,
+    pool_seeds: &[&[u8]],
+    mint: &AccountInfo<'info>,
+    vault: Option<&AccountInfo<'info>>,
+    token_account: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let pool_info = pool.to_account_info();
+
+    if !pool.lock_release() {
+        let ix = mint_to(
+            mint.owner,
+            mint.key,
+            token_account.key,
+            pool_info.key,
+            &[],
+            amount,
+        )?;
+        invoke_signed(
+            &ix,
+            &[mint.clone(), token_account.clone(), pool_info],
+            &[pool_seeds],
+        )?;
+        return Ok(());
+    }
+
+    let vault = checked_vault(pool, mint, vault)?;
+    let vault_balance = pool.vault_balance_mut();
+    *vault_balance = vault_balance
+        .checked_sub(amount)
+        .ok_or(ExampleTokenError::InsufficientVaultBalance)?;
+
+    let ix = transfer_checked(
+        mint.owner,
+        vault.key,
+        mint.key,
+        token_account.key,
+        pool_info.key,
+        &[],
+        amount,
+        pool.decimals(),
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            vault.clone(),
+            mint.clone(),
+            token_account.clone(),
+            pool_info,
+        ],
+        &[pool_seeds],
+    )?;
+
+    Ok(())
+}
+
+/// Transfers `amount` tokens of `mint` from `from` to `to`, signed by
+/// `authority` with `signer_seeds` if it's a PDA. The extra accounts of the
+/// transfer hook of the mint, if any, are resolved from `hook_accounts`.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer<'info>(
+    mint: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    decimals: u8,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+    hook_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    invoke_transfer_checked(
+        mint.owner,
+        from.clone(),
+        mint.clone(),
+        to.clone(),
+        authority.clone(),
+        hook_accounts,
+        amount,
+        decimals,
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+/// Checks that `vault` is passed and is the ATA of the `pool` for `mint`.
+fn checked_vault<'a, 'info, P: Pool>(
+    pool: &Account<'info, P>,
+    mint: &AccountInfo<'info>,
+    vault: Option<&'a AccountInfo<'info>>,
+) -> Result<&'a AccountInfo<'info>> {
+    let vault = vault.ok_or(ErrorCode::ConstraintAccountIsNone)?;
+    require_keys_eq!(
+        vault.key(),
+        find_ata(&pool.key(), mint.key, mint.owner),
+        ErrorCode::ConstraintAddress
+    );
+    Ok(vault)
+}
+
+/// Reads the decimals of a mint of either token program.
+/// Checks that `mint` has no transfer hook, as the extension can't pass its
+/// extra accounts when releasing locked tokens.
+pub fn require_no_transfer_hook(mint: &AccountInfo) -> Result<()> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    require!(
+        transfer_hook::get_program_id(&mint).is_none(),
+        ExampleTokenError::TransferHookNotSupported
+    );
+    Ok(())
+}
+
+pub fn mint_decimals(mint: &AccountInfo) -> Result<u8> {
+    let data = mint.try_borrow_data()?;
+    Ok(
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?
+            .base
+            .decimals,
+    )
+}
+
+/// Reads the amount held by a token account of either token program.
+fn token_amount(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    Ok(
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?
+            .base
+            .amount,
+    )
+}
+
+/// Computes `bps` basis points of `amount`, rounding down.
+pub fn bps_of(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / ExampleTokenConfig::BPS_DENOMINATOR as u128) as u64
+}
+
+/// Returns whether `chain_id` is a Solana cluster, whose deployments are the
+/// only ones decoding the payloads beyond the regular transfer.
+pub fn is_solana_chain(chain_id: u128) -> bool {
+    matches!(chain_id, SOLANA_MAINNET_CHAIN_ID | SOLANA_DEVNET_CHAIN_ID)
+}
+
+/// Scales a local token `amount` to the remote representation with
+/// `remote_decimals`. Fails if part of the amount cannot be represented
+/// remotely.
+pub fn to_remote_amount(amount: u64, local_decimals: u8, remote_decimals: u8) -> Result<U256> {
+    let amount = U256::from(amount);
+
+    if remote_decimals >= local_decimals {
+        amount
+            .checked_mul(pow10(remote_decimals - local_decimals)?)
+            .ok_or_else(|| error!(ExampleTokenError::AmountOverflow))
+    } else {
+        let factor = pow10(local_decimals - remote_decimals)?;
+        require!(
+            (amount % factor).is_zero(),
+            ExampleTokenError::AmountHasDust
+        );
+        Ok(amount / factor)
+    }
+}
+
+/// Scales a remote token `amount` with `remote_decimals` to the local
+/// representation, rounding down the dust worth less than a local unit.
+/// Returns `None` if the result doesn't fit into `u64`, so that the caller can
+/// record the transfer as rejected instead of failing the message for good.
+pub fn to_local_amount(amount: U256, local_decimals: u8, remote_decimals: u8) -> Option<u64> {
+    let amount = if remote_decimals >= local_decimals {
+        amount / pow10(remote_decimals - local_decimals).ok()?
+    } else {
+        amount.checked_mul(pow10(local_decimals - remote_decimals).ok()?)?
+    };
+
+    amount.try_into().ok()
+}
+
+/// Rounds a local token `amount` down to what can be represented with
+/// `remote_decimals`.
+pub fn round_to_remote(amount: u64, local_decimals: u8, remote_decimals: u8) -> u64 {
+    match local_decimals.checked_sub(remote_decimals) {
+        Some(exp) => 10u64
+            .checked_pow(exp.into())
+            .map_or(0, |factor| amount - amount % factor),
+        None => amount,
+    }
+}
+
+fn pow10(exp: u8) -> Result<U256> {
+    U256::from(10)
+        .checked_pow(U256::from(exp))
+        .ok_or_else(|| error!(ExampleTokenError::AmountOverflow))
+}
+
+/// Checks that the instruction preceding the current one verifies the
+/// ed25519 signature of `message` by `signer`, with all the data embedded in
+/// it.
+pub fn verify_ed25519(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    // Number of signatures and padding, then seven `u16` offsets.
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_END: usize = OFFSETS_START + 7 * 2;
+
+    let index = load_current_index_checked(instructions)?;
+    let ix = index
+        .checked_sub(1)
+        .map(|index| load_instruction_at_checked(index as usize, instructions))
+        .transpose()?
+        .ok_or(ExampleTokenError::InvalidPermitSignature)?;
+    require!(
+        ix.program_id == ed25519_program::ID
+            && ix.accounts.is_empty()
+            && ix.data.len() >= OFFSETS_END
+            && ix.data[0] == 1,
+        ExampleTokenError::InvalidPermitSignature
+    );
+
+    let offsets: Vec<usize> = ix.data[OFFSETS_START..OFFSETS_END]
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]) as usize)
+        .collect();
+    let Ok(
+        [_, signature_ix, public_key_offset, public_key_ix, message_offset, message_size, message_ix],
+    ) = <[usize; 7]>::try_from(offsets)
+    else {
+        return err!(ExampleTokenError::InvalidPermitSignature);
+    };
+    // The signature, public key and message must be in the ed25519
+    // instruction itself.
+    let current = u16::MAX as usize;
+    require!(
+        signature_ix == current && public_key_ix == current && message_ix == current,
+        ExampleTokenError::InvalidPermitSignature
+    );
+
+    let public_key = ix.data.get(public_key_offset..public_key_offset + 32);
+    let signed_message = ix.data.get(message_offset..message_offset + message_size);
+    require!(
+        public_key == Some(signer.as_ref()) && signed_message == Some(message),
+        ExampleTokenError::InvalidPermitSignature
+    );
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+import * as anchor from "@coral-xyz/anchor";
+import { attest, revokeAttestation } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: attest <recipient> [--revoke]");
+    process.exit(1);
+  }
+
+  const recipient = new PublicKey(process.argv[2]);
+  const shouldRevoke = process.argv[3] == "--revoke";
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = shouldRevoke
+    ? await revokeAttestation({ authority: payer, recipient })
+    : await attest({ authority: payer, recipient });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
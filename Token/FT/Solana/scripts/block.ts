@@ -0,0 +1,33 @@
+import * as anchor from "@coral-xyz/anchor";
+import { block, unblock } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { hexToBytes } from "../helpers/endpoint";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: block <address> [--unblock]");
+    process.exit(1);
+  }
+
+  let address: Buffer;
+  if (process.argv[2].startsWith("0x")) {
+    address = Buffer.alloc(32);
+    hexToBytes(process.argv[2]).copy(address, 12);
+  } else {
+    address = new PublicKey(process.argv[2]).toBuffer();
+  }
+  const shouldUnblock = process.argv[3] == "--unblock";
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = shouldUnblock
+    ? await unblock({ admin: payer, address })
+    : await block({ admin: payer, address });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -1,14 +1,28 @@
 import * as anchor from "@coral-xyz/anchor";
-import { bridge, Destination } from "../helpers/exampleToken";
+import { bridge, parseRecipient } from "../helpers/exampleToken";
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
-import { BN } from "bn.js";
-import { hexToBytes } from "../helpers/endpoint";
+import BN from "bn.js";
 import { PublicKey } from "@solana/web3.js";
+import {
+  MANTLE_SEPOLIA_CHAIN_ID,
+  POLYGON_AMOY_CHAIN_ID,
+  SOLANA_DEVNET_CHAIN_ID,
+  SOLANA_MAINNET_CHAIN_ID,
+  TEIB_CHAIN_ID,
+} from "../helpers/utils";
 
 async function main(): Promise<void> {
+  const finalizedIndex = process.argv.indexOf("--finalized");
+  if (finalizedIndex != -1) {
+    process.argv.splice(finalizedIndex, 1);
+  }
+  const commitment = finalizedIndex != -1
+    ? { finalized: {} }
+    : { confirmed: {} };
+
   if (process.argv.length < 2 + 6) {
     console.error(
-      "Usage: sendMessage <#times> <dst-chain> <uip-fee> <custom-gas-limit> <to> <base-amount>",
+      "Usage: sendMessage <#times> <dst-chain> <uip-fee> <custom-gas-limit> <to> <base-amount> [<referrer>] [--finalized]",
     );
     process.exit(1);
   }
@@ -17,39 +31,38 @@ async function main(): Promise<void> {
   const dstChain = process.argv[3];
   const uipFee = new BN(process.argv[4]);
   const customGasLimit = new BN(process.argv[5]);
-  let to: Buffer;
-  if (process.argv[6].startsWith("0x")) {
-    to = hexToBytes(process.argv[6]);
-  } else {
-    to = new PublicKey(process.argv[6]).toBuffer();
-  }
+  const to = parseRecipient(process.argv[6]);
   const baseAmount = new BN(process.argv[7]);
+  const referrer = process.argv[8] ? new PublicKey(process.argv[8]) : undefined;
 
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
   const payer = (provider.wallet as NodeWallet).payer;
 
-  let destination: Destination;
+  let destChainId: BN;
   switch (dstChain) {
     case "solana-mainnet":
-      destination = { solanaMainnet: {} };
+      destChainId = SOLANA_MAINNET_CHAIN_ID;
       break;
     case "solana-devnet":
-      destination = { solanaDevnet: {} };
+      destChainId = SOLANA_DEVNET_CHAIN_ID;
       break;
     case "polygon-amoy":
-      destination = { polygonAmoy: {} };
+      destChainId = POLYGON_AMOY_CHAIN_ID;
       break;
     case "mantle-sepolia":
-      destination = { mantleSepolia: {} };
+      destChainId = MANTLE_SEPOLIA_CHAIN_ID;
       break;
     case "teib":
-      destination = { teib: {} };
+      destChainId = TEIB_CHAIN_ID;
       break;
     default:
-      throw new Error(
-        "Invalid chain name, must be one of solana-mainnet, solana-devnet, polygon-amoy, mantle-sepolia, teib",
-      );
+      if (!/^\d+$/.test(dstChain)) {
+        throw new Error(
+          "Invalid destination, must be chain ID or one of solana-mainnet, solana-devnet, polygon-amoy, mantle-sepolia, teib",
+        );
+      }
+      destChainId = new BN(dstChain);
   }
 
   const promises = Array.from({ length: times }, async (_, i) => {
@@ -58,10 +71,12 @@ async function main(): Promise<void> {
         const { transactionSignature } = await bridge({
           uipFee,
           customGasLimit,
-          destination,
+          destChainId,
           sender: payer,
           amount: baseAmount.add(new BN(i)),
           to,
+          referrer,
+          commitment,
         });
         console.log(`${i + 1} signature:`, transactionSignature);
         break;
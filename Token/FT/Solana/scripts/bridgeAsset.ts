@@ -0,0 +1,38 @@
+import * as anchor from "@coral-xyz/anchor";
+import { bridgeAsset, parseRecipient } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import BN from "bn.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 6) {
+    console.error(
+      "Usage: bridge-asset <asset-id> <dst-chain-id> <uip-fee> <custom-gas-limit> <to> <amount>",
+    );
+    process.exit(1);
+  }
+
+  const assetId = new BN(process.argv[2]);
+  const destChainId = new BN(process.argv[3]);
+  const uipFee = new BN(process.argv[4]);
+  const customGasLimit = new BN(process.argv[5]);
+  const to = parseRecipient(process.argv[6]);
+  const amount = new BN(process.argv[7]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await bridgeAsset({
+    assetId,
+    destChainId,
+    uipFee,
+    customGasLimit,
+    sender: payer,
+    to,
+    amount,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
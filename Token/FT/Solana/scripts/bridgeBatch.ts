@@ -0,0 +1,49 @@
+import * as anchor from "@coral-xyz/anchor";
+import { bridgeBatch, parseRecipient } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import BN from "bn.js";
+
+async function main(): Promise<void> {
+  const finalizedIndex = process.argv.indexOf("--finalized");
+  if (finalizedIndex != -1) {
+    process.argv.splice(finalizedIndex, 1);
+  }
+  const commitment = finalizedIndex != -1
+    ? { finalized: {} }
+    : { confirmed: {} };
+
+  if (process.argv.length < 2 + 5 || process.argv.length % 2 == 0) {
+    console.error(
+      "Usage: bridgeBatch <dst-chain-id> <uip-fee> <custom-gas-limit> <to> <base-amount> [<to> <base-amount>...] [--finalized]",
+    );
+    process.exit(1);
+  }
+
+  const destChainId = new BN(process.argv[2]);
+  const uipFee = new BN(process.argv[3]);
+  const customGasLimit = new BN(process.argv[4]);
+  const recipients = [];
+  for (let i = 5; i < process.argv.length; i += 2) {
+    recipients.push({
+      to: parseRecipient(process.argv[i]),
+      amount: new BN(process.argv[i + 1]),
+    });
+  }
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await bridgeBatch({
+    uipFee,
+    customGasLimit,
+    destChainId,
+    sender: payer,
+    recipients,
+    commitment,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
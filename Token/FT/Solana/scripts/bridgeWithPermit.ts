@@ -0,0 +1,52 @@
+import * as anchor from "@coral-xyz/anchor";
+import { bridgeWithPermit, parseRecipient } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import BN from "bn.js";
+import { hexToBytes } from "../helpers/endpoint";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  const finalizedIndex = process.argv.indexOf("--finalized");
+  if (finalizedIndex != -1) {
+    process.argv.splice(finalizedIndex, 1);
+  }
+  const commitment = finalizedIndex != -1
+    ? { finalized: {} }
+    : { confirmed: {} };
+
+  if (process.argv.length < 2 + 9) {
+    console.error(
+      "Usage: bridgeWithPermit <owner> <dst-chain-id> <to> <base-amount> <nonce> <deadline> <custom-gas-limit> <signature> <uip-fee> [--finalized]",
+    );
+    process.exit(1);
+  }
+
+  const owner = new PublicKey(process.argv[2]);
+  const permit = {
+    destChainId: new BN(process.argv[3]),
+    to: parseRecipient(process.argv[4]),
+    amount: new BN(process.argv[5]),
+    nonce: new BN(process.argv[6]),
+    deadline: new BN(process.argv[7]),
+    customGasLimit: new BN(process.argv[8]),
+    commitment,
+  };
+  const signature = hexToBytes(process.argv[9]);
+  const uipFee = new BN(process.argv[10]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await bridgeWithPermit({
+    permit,
+    owner,
+    signature,
+    relayer: payer,
+    uipFee,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
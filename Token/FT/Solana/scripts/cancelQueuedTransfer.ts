@@ -0,0 +1,26 @@
+import * as anchor from "@coral-xyz/anchor";
+import { cancelQueuedTransfer } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { hexToBytes } from "../helpers/endpoint";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: cancel-queued-transfer <msg-hash>");
+    process.exit(1);
+  }
+
+  const msgHash = hexToBytes(process.argv[2]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await cancelQueuedTransfer({
+    authority: payer,
+    msgHash,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -0,0 +1,29 @@
+import * as anchor from "@coral-xyz/anchor";
+import { claim } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { hexToBytes } from "../helpers/endpoint";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 2) {
+    console.error("Usage: claim <msg-hash> <destination-token-account>");
+    process.exit(1);
+  }
+
+  const msgHash = hexToBytes(process.argv[2]);
+  const destination = new PublicKey(process.argv[3]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await claim({
+    recipient: payer,
+    msgHash,
+    destination,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -0,0 +1,29 @@
+import * as anchor from "@coral-xyz/anchor";
+import { forward } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { hexToBytes } from "../helpers/endpoint";
+import BN from "bn.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 2) {
+    console.error("Usage: forward <msg-hash> <uip-fee>");
+    process.exit(1);
+  }
+
+  const msgHash = hexToBytes(process.argv[2]);
+  const uipFee = new BN(process.argv[3]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await forward({
+    payer,
+    msgHash,
+    uipFee,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -0,0 +1,26 @@
+import * as anchor from "@coral-xyz/anchor";
+import { freeze, thaw } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: freeze <token-account> [--thaw]");
+    process.exit(1);
+  }
+
+  const tokenAccount = new PublicKey(process.argv[2]);
+  const shouldThaw = process.argv[3] == "--thaw";
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = shouldThaw
+    ? await thaw({ admin: payer, tokenAccount })
+    : await freeze({ admin: payer, tokenAccount });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -0,0 +1,46 @@
+import * as anchor from "@coral-xyz/anchor";
+import { grantRole, revokeRole, Role } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { PublicKey } from "@solana/web3.js";
+
+function parseRole(role: string): Role {
+  switch (role) {
+    case "minter":
+      return { minter: {} };
+    case "origin-manager":
+      return { originManager: {} };
+    case "pauser":
+      return { pauser: {} };
+    case "extension-manager":
+      return { extensionManager: {} };
+    default:
+      throw new Error(
+        "Invalid role, must be one of minter, origin-manager, pauser, extension-manager",
+      );
+  }
+}
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 2) {
+    console.error(
+      "Usage: grantRole <minter|origin-manager|pauser|extension-manager> <account> [--revoke]",
+    );
+    process.exit(1);
+  }
+
+  const role = parseRole(process.argv[2]);
+  const account = new PublicKey(process.argv[3]);
+  const shouldRevoke = process.argv[4] == "--revoke";
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = shouldRevoke
+    ? await revokeRole({ admin: payer, role, account })
+    : await grantRole({ admin: payer, role, account });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -1,16 +1,53 @@
 import * as anchor from "@coral-xyz/anchor";
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
 import { expect } from "bun:test";
-import { initialize, registerExtension } from "../helpers/exampleToken";
+import {
+  initialize,
+  Metadata,
+  registerExtension,
+  Token2022Extensions,
+} from "../helpers/exampleToken";
+import { BN } from "bn.js";
+import { PublicKey } from "@solana/web3.js";
 
 async function main(): Promise<void> {
-  if (process.argv.length < 2 + 1) {
-    console.error("Usage: initialize <ipfs-cid> <decimals>");
+  const freezableIndex = process.argv.indexOf("--freezable");
+  if (freezableIndex != -1) {
+    process.argv.splice(freezableIndex, 1);
+  }
+
+  let transferHookProgramId: PublicKey | null = null;
+  const transferHookIndex = process.argv.indexOf("--transfer-hook");
+  if (transferHookIndex != -1) {
+    transferHookProgramId = new PublicKey(
+      process.argv.splice(transferHookIndex, 2)[1],
+    );
+  }
+
+  let metadata: Metadata | undefined;
+  const metadataIndex = process.argv.indexOf("--metadata");
+  if (metadataIndex != -1) {
+    const [name, symbol, uri] = process.argv.splice(metadataIndex, 4).slice(1);
+    metadata = { name, symbol, uri };
+  }
+
+  if (process.argv.length < 2 + 1 || (metadata && !metadata.uri)) {
+    console.error(
+      "Usage: initialize <ipfs-cid> <decimals> [<transfer-fee-bps> <maximum-fee>] [--transfer-hook <program-id>] [--metadata <name> <symbol> <uri>] [--freezable]",
+    );
     process.exit(1);
   }
 
   const ipfsCid = process.argv[2];
   const decimals = Number(process.argv[3]);
+  let token2022: Token2022Extensions | undefined;
+  if (process.argv.length >= 2 + 4) {
+    token2022 = {
+      transferFeeBasisPoints: Number(process.argv[4]),
+      maximumFee: new BN(process.argv[5]),
+      transferHookProgramId,
+    };
+  }
 
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -21,6 +58,9 @@ async function main(): Promise<void> {
       payer,
       admin: payer.publicKey,
       decimals,
+      token2022,
+      metadata,
+      freezable: freezableIndex != -1,
     });
     console.log("Initialize transaction signature:", transactionSignature);
   } catch (e) {
@@ -29,7 +69,7 @@ async function main(): Promise<void> {
   }
 
   const { transactionSignature } = await registerExtension({
-    admin: payer,
+    authority: payer,
     payer,
     ipfsCid,
   });
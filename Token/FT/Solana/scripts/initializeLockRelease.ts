@@ -0,0 +1,43 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { expect } from "bun:test";
+import {
+  initializeLockRelease,
+  registerExtension,
+} from "../helpers/exampleToken";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 2) {
+    console.error("Usage: initialize-lock-release <ipfs-cid> <mint>");
+    process.exit(1);
+  }
+
+  const ipfsCid = process.argv[2];
+  const mint = new PublicKey(process.argv[3]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  try {
+    const { transactionSignature } = await initializeLockRelease({
+      payer,
+      admin: payer.publicKey,
+      mint,
+    });
+    console.log("Initialize transaction signature:", transactionSignature);
+  } catch (e) {
+    expect(e.toString()).toInclude("already in use");
+    console.warn("Already initialized");
+  }
+
+  const { transactionSignature } = await registerExtension({
+    authority: payer,
+    payer,
+    ipfsCid,
+  });
+  console.log("RegisterExtension transaction signature:", transactionSignature);
+}
+
+main();
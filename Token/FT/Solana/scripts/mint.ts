@@ -26,7 +26,7 @@ async function main(): Promise<void> {
   );
 
   const { transactionSignature } = await mint({
-    admin: payer,
+    authority: payer,
     amount,
     owner,
   });
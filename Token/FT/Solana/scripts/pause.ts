@@ -0,0 +1,39 @@
+import * as anchor from "@coral-xyz/anchor";
+import { pause, PauseTarget, unpause } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { BN } from "bn.js";
+
+function parseTarget(target: string): PauseTarget {
+  switch (target) {
+    case "outbound":
+      return { outbound: {} };
+    case "inbound":
+      return { inbound: {} };
+    default:
+      return { route: { chainId: new BN(target) } };
+  }
+}
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error(
+      "Usage: pause <outbound|inbound|chain-id> [--unpause]",
+    );
+    process.exit(1);
+  }
+
+  const target = parseTarget(process.argv[2]);
+  const shouldUnpause = process.argv[3] == "--unpause";
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = shouldUnpause
+    ? await unpause({ admin: payer, target })
+    : await pause({ authority: payer, target });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -0,0 +1,21 @@
+import * as anchor from "@coral-xyz/anchor";
+import { reconcileSupply } from "../helpers/exampleToken";
+import BN from "bn.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: reconcileSupply <chain-id>...");
+    process.exit(1);
+  }
+
+  const chainIds = process.argv.slice(2).map((chainId) => new BN(chainId));
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const { transactionSignature } = await reconcileSupply(chainIds);
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
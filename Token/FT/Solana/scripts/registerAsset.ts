@@ -0,0 +1,43 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  registerAsset,
+  registerAssetLockRelease,
+} from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { BN } from "bn.js";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  const lockReleaseIndex = process.argv.indexOf("--lock-release");
+  let mint: PublicKey | undefined;
+  if (lockReleaseIndex != -1) {
+    mint = new PublicKey(process.argv[lockReleaseIndex + 1]);
+    process.argv.splice(lockReleaseIndex, 2);
+  }
+
+  if (process.argv.length < 2 + (mint ? 1 : 2)) {
+    console.error(
+      "Usage: register-asset <asset-id> <decimals> | <asset-id> --lock-release <mint>",
+    );
+    process.exit(1);
+  }
+
+  const assetId = new BN(process.argv[2]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = mint
+    ? await registerAssetLockRelease({ admin: payer, payer, assetId, mint })
+    : await registerAsset({
+      admin: payer,
+      payer,
+      assetId,
+      decimals: Number(process.argv[3]),
+    });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
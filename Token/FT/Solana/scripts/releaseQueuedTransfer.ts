@@ -0,0 +1,34 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  EXAMPLE_TOKEN_PROGRAM,
+  findQueuedTransfer,
+  releaseQueuedAssetTransfer,
+  releaseQueuedTransfer,
+} from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { hexToBytes } from "../helpers/endpoint";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: release-queued-transfer <msg-hash>");
+    process.exit(1);
+  }
+
+  const msgHash = hexToBytes(process.argv[2]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { assetId } = await EXAMPLE_TOKEN_PROGRAM.account.queuedTransfer.fetch(
+    findQueuedTransfer(msgHash),
+  );
+  const release = assetId === null
+    ? releaseQueuedTransfer
+    : releaseQueuedAssetTransfer;
+  const { transactionSignature } = await release({ payer, msgHash });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
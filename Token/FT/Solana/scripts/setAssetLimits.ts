@@ -0,0 +1,40 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setAssetLimits } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { BN } from "bn.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 9) {
+    console.error(
+      "Usage: set-asset-limits <asset-id> <chain-id> <outbound-capacity> <outbound-window> <inbound-capacity> <inbound-window> <min-amount> <max-amount> <user-daily-cap>",
+    );
+    process.exit(1);
+  }
+
+  const assetId = new BN(process.argv[2]);
+  const chainId = new BN(process.argv[3]);
+  const limits = {
+    outboundCapacity: new BN(process.argv[4]),
+    outboundWindow: new BN(process.argv[5]),
+    inboundCapacity: new BN(process.argv[6]),
+    inboundWindow: new BN(process.argv[7]),
+    minAmount: new BN(process.argv[8]),
+    maxAmount: new BN(process.argv[9]),
+    userDailyCap: new BN(process.argv[10]),
+  };
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setAssetLimits({
+    authority: payer,
+    assetId,
+    chainId,
+    limits,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
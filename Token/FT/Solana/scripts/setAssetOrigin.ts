@@ -0,0 +1,53 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setAssetOrigin } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { BN } from "bn.js";
+import { hexToBytes } from "../helpers/endpoint";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  const token2022Index = process.argv.indexOf("--token-2022");
+  if (token2022Index != -1) {
+    process.argv.splice(token2022Index, 1);
+  }
+
+  if (process.argv.length < 2 + 5) {
+    console.error(
+      "Usage: set-asset-origin <asset-id> <chain-id> <address> <enabled> <decimals> [<remote-mint> [--token-2022]]",
+    );
+    process.exit(1);
+  }
+
+  const assetId = new BN(process.argv[2]);
+  const chainId = new BN(process.argv[3]);
+  let address: Buffer;
+  if (process.argv[4].startsWith("0x")) {
+    address = Buffer.alloc(32);
+    hexToBytes(process.argv[4]).copy(address, 12);
+  } else {
+    address = new PublicKey(process.argv[4]).toBuffer();
+  }
+  const enabled = process.argv[5] == "true";
+  const decimals = Number(process.argv[6]);
+  const remoteMint = process.argv[7] && new PublicKey(process.argv[7]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setAssetOrigin({
+    authority: payer,
+    payer,
+    assetId,
+    chainId,
+    address,
+    enabled,
+    decimals,
+    remoteMint,
+    remoteToken2022: token2022Index != -1,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -0,0 +1,28 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setAttestationAuthority } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: set-attestation-authority <authority|none>");
+    process.exit(1);
+  }
+
+  const authority = process.argv[2] == "none"
+    ? null
+    : new PublicKey(process.argv[2]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setAttestationAuthority({
+    admin: payer,
+    authority,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
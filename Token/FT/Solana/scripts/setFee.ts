@@ -0,0 +1,27 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setFee } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 2) {
+    console.error("Usage: set-fee <fee-bps> <referrer-share-bps>");
+    process.exit(1);
+  }
+
+  const feeBps = Number(process.argv[2]);
+  const referrerShareBps = Number(process.argv[3]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setFee({
+    admin: payer,
+    feeBps,
+    referrerShareBps,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -0,0 +1,26 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setFinalizedThreshold } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import BN from "bn.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: setFinalizedThreshold <threshold>");
+    process.exit(1);
+  }
+
+  const threshold = new BN(process.argv[2]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setFinalizedThreshold({
+    admin: payer,
+    threshold,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
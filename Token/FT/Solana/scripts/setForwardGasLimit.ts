@@ -0,0 +1,28 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setForwardGasLimit } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import BN from "bn.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: set-forward-gas-limit <gas-limit|none>");
+    process.exit(1);
+  }
+
+  const gasLimit = process.argv[2] == "none"
+    ? null
+    : new BN(process.argv[2]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setForwardGasLimit({
+    admin: payer,
+    gasLimit,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
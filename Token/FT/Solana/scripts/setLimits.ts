@@ -0,0 +1,38 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setLimits } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { BN } from "bn.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 8) {
+    console.error(
+      "Usage: set-limits <chain-id> <outbound-capacity> <outbound-window> <inbound-capacity> <inbound-window> <min-amount> <max-amount> <user-daily-cap>",
+    );
+    process.exit(1);
+  }
+
+  const chainId = new BN(process.argv[2]);
+  const limits = {
+    outboundCapacity: new BN(process.argv[3]),
+    outboundWindow: new BN(process.argv[4]),
+    inboundCapacity: new BN(process.argv[5]),
+    inboundWindow: new BN(process.argv[6]),
+    minAmount: new BN(process.argv[7]),
+    maxAmount: new BN(process.argv[8]),
+    userDailyCap: new BN(process.argv[9]),
+  };
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setLimits({
+    authority: payer,
+    chainId,
+    limits,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
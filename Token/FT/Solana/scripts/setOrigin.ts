@@ -0,0 +1,41 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setOrigin } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { BN } from "bn.js";
+import { hexToBytes } from "../helpers/endpoint";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 4) {
+    console.error("Usage: set-origin <chain-id> <address> <enabled> <decimals>");
+    process.exit(1);
+  }
+
+  const chainId = new BN(process.argv[2]);
+  let address: Buffer;
+  if (process.argv[3].startsWith("0x")) {
+    address = Buffer.alloc(32);
+    hexToBytes(process.argv[3]).copy(address, 12);
+  } else {
+    address = new PublicKey(process.argv[3]).toBuffer();
+  }
+  const enabled = process.argv[4] == "true";
+  const decimals = Number(process.argv[5]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setOrigin({
+    authority: payer,
+    payer,
+    chainId,
+    address,
+    enabled,
+    decimals,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
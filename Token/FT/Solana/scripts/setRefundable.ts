@@ -0,0 +1,32 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setRefundable } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { BN } from "bn.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 3) {
+    console.error(
+      "Usage: set-refundable <chain-id> <refundable> <refund-window>",
+    );
+    process.exit(1);
+  }
+
+  const chainId = new BN(process.argv[2]);
+  const refundable = process.argv[3] == "true";
+  const refundWindow = new BN(process.argv[4]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setRefundable({
+    authority: payer,
+    chainId,
+    refundable,
+    refundWindow,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
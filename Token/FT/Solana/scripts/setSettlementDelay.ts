@@ -0,0 +1,28 @@
+import * as anchor from "@coral-xyz/anchor";
+import { setSettlementDelay } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import BN from "bn.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 2) {
+    console.error("Usage: set-settlement-delay <threshold> <delay-seconds>");
+    process.exit(1);
+  }
+
+  const threshold = new BN(process.argv[2]);
+  const delay = new BN(process.argv[3]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await setSettlementDelay({
+    admin: payer,
+    threshold,
+    delay,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
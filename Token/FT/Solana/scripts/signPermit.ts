@@ -0,0 +1,62 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  approvePermit,
+  fetchPermitNonce,
+  parseRecipient,
+  signPermit,
+} from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import BN from "bn.js";
+
+async function main(): Promise<void> {
+  const finalizedIndex = process.argv.indexOf("--finalized");
+  if (finalizedIndex != -1) {
+    process.argv.splice(finalizedIndex, 1);
+  }
+  const commitment = finalizedIndex != -1
+    ? { finalized: {} }
+    : { confirmed: {} };
+
+  if (process.argv.length < 2 + 5) {
+    console.error(
+      "Usage: signPermit <dst-chain-id> <to> <base-amount> <validity-seconds> <custom-gas-limit> [--finalized]",
+    );
+    process.exit(1);
+  }
+
+  const destChainId = new BN(process.argv[2]);
+  const to = parseRecipient(process.argv[3]);
+  const amount = new BN(process.argv[4]);
+  const deadline = new BN(Math.floor(Date.now() / 1000))
+    .add(new BN(process.argv[5]));
+  const customGasLimit = new BN(process.argv[6]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await approvePermit({
+    owner: payer,
+    amount,
+  });
+  console.log("Approve transaction signature:", transactionSignature);
+
+  const nonce = await fetchPermitNonce(payer.publicKey);
+  const permit = {
+    destChainId,
+    to,
+    amount,
+    nonce,
+    deadline,
+    customGasLimit,
+    commitment,
+  };
+  const signature = signPermit(permit, payer);
+
+  console.log("Owner:", payer.publicKey.toBase58());
+  console.log("Nonce:", nonce.toString());
+  console.log("Deadline:", deadline.toString());
+  console.log("Signature:", signature.toString("hex"));
+}
+
+main();
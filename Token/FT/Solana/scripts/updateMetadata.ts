@@ -0,0 +1,25 @@
+import * as anchor from "@coral-xyz/anchor";
+import { updateMetadata } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 3) {
+    console.error("Usage: updateMetadata <name> <symbol> <uri>");
+    process.exit(1);
+  }
+
+  const [name, symbol, uri] = process.argv.slice(2);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await updateMetadata({
+    admin: payer,
+    metadata: { name, symbol, uri },
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
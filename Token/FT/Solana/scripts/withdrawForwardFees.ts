@@ -0,0 +1,29 @@
+import * as anchor from "@coral-xyz/anchor";
+import { withdrawForwardFees } from "../helpers/exampleToken";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { BN } from "bn.js";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 2) {
+    console.error("Usage: withdraw-forward-fees <destination> <lamports>");
+    process.exit(1);
+  }
+
+  const destination = new PublicKey(process.argv[2]);
+  const amount = new BN(process.argv[3]);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await withdrawForwardFees({
+    admin: payer,
+    destination,
+    amount,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
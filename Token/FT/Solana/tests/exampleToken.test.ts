@@ -1,43 +1,119 @@
 import {
   encodeTransmitterParams,
   executeFull,
+  executeInSteps,
   findExtension,
   findMessage,
+  msgHashFull,
   signMsg,
   simulateExecuteLite,
   UIP_PROGRAM,
   unloadMessage,
 } from "../helpers/endpoint";
 import {
+  approvePermit,
+  attest,
+  batchEscrowKey,
+  block,
+  BLOCKLIST,
   bridge,
-  Destination,
+  cancelForward,
+  bridgeAsset,
+  bridgeBatch,
+  bridgeWithPermit,
+  cancelQueuedTransfer,
+  claim,
+  closeOutboundTransfer,
   EXA_MINT,
   EXAMPLE_TOKEN_CONFIG,
   EXAMPLE_TOKEN_PROGRAM,
+  ESCROW_AUTHORITY,
+  fetchAssetMint,
+  fetchPermitNonce,
+  findAsset,
+  findAssetMint,
+  findAssetOrigin,
+  findAttestation,
+  findCaller,
+  findEscrow,
+  findMetaplexMetadata,
+  findOrigin,
+  findOutboundTransfer,
+  findPendingForward,
+  findQueuedTransfer,
+  findReceipt,
+  forward,
+  FORWARD_FEE_VAULT,
+  freeze,
+  grantRole,
   initialize,
   mint,
+  pause,
+  reconcileSupply,
+  recoverEscrow,
+  releaseQueuedAssetTransfer,
+  releaseQueuedTransfer,
+  registerAsset,
+  registerAssetLockRelease,
   registerExtension,
+  removeOrigin,
+  revokeAttestation,
+  revokeRole,
+  ROLES,
+  setAssetOrigin,
+  setAttestationAuthority,
+  setFee,
+  setFinalizedThreshold,
+  setForwardGasLimit,
+  setGuardian,
+  setLimits,
+  setOrigin,
+  setRefundable,
+  setSettlementDelay,
+  signPermit,
+  thaw,
+  TREASURY_AUTHORITY,
+  unblock,
+  unpause,
   updateAdmin,
+  updateMetadata,
+  withdrawFees,
+  withdrawForwardFees,
 } from "../helpers/exampleToken";
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  TransactionSignature,
+} from "@solana/web3.js";
 import { afterAll, beforeAll, describe, expect, test } from "bun:test";
 import {
   disperse,
+  POLYGON_AMOY_CHAIN_ID,
   readKeypairFromFile,
   setupTests,
+  sleep,
   SOLANA_CHAIN_ID,
+  SOLANA_MAINNET_CHAIN_ID,
+  TEIB_CHAIN_ID,
+  transfer,
   transferEverything,
 } from "../helpers/utils";
 import BN from "bn.js";
 import bs58 from "bs58";
-import { Wallet } from "ethers";
+import { AbiCoder, Wallet } from "ethers";
 import { randomInt } from "crypto";
 import { CID } from "multiformats";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
   getAccount,
   getAssociatedTokenAddressSync,
+  getMint,
   getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 
@@ -46,6 +122,8 @@ const executor = readKeypairFromFile("keys/executor.json");
 const proposer = new Keypair();
 const sender = new Keypair();
 const receiver = new Keypair();
+const guardian = new Keypair();
+const referrer = new Keypair();
 const signer = new Wallet(
   "0x74e3ffad2b87174dc1d806edf1a01e3b017cf1be05d1894d329826f10fa1d72f",
 );
@@ -60,6 +138,11 @@ const transmitterParamsEncoded = encodeTransmitterParams(transmitterParams);
 
 const { connection, payer } = setupTests();
 
+// SPL Memo v1, which logs the instruction data and ignores the accounts.
+const MEMO_V1_PROGRAM_ID = new PublicKey(
+  "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo",
+);
+
 beforeAll(async () => {
   await disperse(
     connection,
@@ -73,6 +156,373 @@ afterAll(async () => {
   await transferEverything(connection, [proposer, executor, sender], payer);
 });
 
+/**
+ * Executes a transfer of `amount` remote units from `sender` to `to`, which is
+ * a malformed recipient if given as raw bytes, passing `recipientAccount` as
+ * the `to` account if given.
+ */
+async function receiveTransfer(
+  to: PublicKey | Buffer,
+  amount: BN,
+  recipientAccount?: PublicKey,
+): Promise<Buffer> {
+  const srcChainId = SOLANA_CHAIN_ID;
+  // The program id stands in for the accounts of a malformed recipient.
+  const recipient = recipientAccount ?? (to instanceof PublicKey
+    ? to
+    : EXAMPLE_TOKEN_PROGRAM.programId);
+  const tokenAccount = to instanceof PublicKey
+    ? getAssociatedTokenAddressSync(EXA_MINT, to, true)
+    : EXAMPLE_TOKEN_PROGRAM.programId;
+  const msgData = {
+    initialProposal: {
+      senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+      destAddr: EXAMPLE_TOKEN_PROGRAM.programId,
+      totalFee: new BN(80085),
+      payload: Buffer.from(
+        AbiCoder.defaultAbiCoder().encode(["bytes", "bytes", "uint256"], [
+          sender.publicKey.toBuffer(),
+          to instanceof PublicKey ? to.toBuffer() : to,
+          amount.toString(),
+        ]).slice(2),
+        "hex",
+      ),
+      reserved: Buffer.from([]),
+      transmitterParams: transmitterParamsEncoded,
+      selector: Array.from(Buffer.alloc(32)),
+    },
+    srcChainData: {
+      srcBlockNumber: new BN(randomInt(256)),
+      srcChainId,
+      srcOpTxId: [
+        Array.from(Keypair.generate().publicKey.toBuffer()),
+        Array.from(Keypair.generate().publicKey.toBuffer()),
+      ],
+    },
+  };
+  const msgHash = msgHashFull(msgData);
+
+  await executeFull({
+    executor,
+    msgData,
+    signatures: [signMsg(signer, msgData)],
+    superSignatures: [signMsg(superSigner, msgData)],
+    accounts: [
+      { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: true },
+      { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: true },
+      {
+        pubkey: findQueuedTransfer(msgHash),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: findEscrow(msgHash), isSigner: false, isWritable: true },
+      { pubkey: findPendingForward(msgHash), isSigner: false, isWritable: true },
+      { pubkey: findReceipt(msgHash), isSigner: false, isWritable: true },
+      { pubkey: EXA_MINT, isSigner: false, isWritable: true },
+      {
+        pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: ESCROW_AUTHORITY, isSigner: false, isWritable: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(EXA_MINT, ESCROW_AUTHORITY, true),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: tokenAccount,
+        isSigner: false,
+        isWritable: to instanceof PublicKey,
+      },
+      { pubkey: recipient, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: BLOCKLIST, isSigner: false, isWritable: false },
+      {
+        pubkey: to instanceof PublicKey ? findAttestation(to) : recipient,
+        isSigner: false,
+        isWritable: false,
+      },
+    ],
+    spendingLimit: new BN(3_000_000),
+  });
+  await unloadMessage({ payer: executor, message: findMessage(msgData) });
+
+  return msgHash;
+}
+
+/**
+ * Returns the accounts of a batch transfer to `recipients`, whose escrow and
+ * queued transfer are derived from their batch key.
+ */
+function batchAccounts(
+  srcChainId: BN,
+  msgHash: Buffer,
+  recipients: PublicKey[],
+): AccountMeta[] {
+  return [
+    { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: true },
+    { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: true },
+    { pubkey: findReceipt(msgHash), isSigner: false, isWritable: true },
+    { pubkey: EXA_MINT, isSigner: false, isWritable: true },
+    {
+      pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+      isSigner: false,
+      isWritable: false,
+    },
+    { pubkey: ESCROW_AUTHORITY, isSigner: false, isWritable: false },
+    {
+      pubkey: getAssociatedTokenAddressSync(EXA_MINT, ESCROW_AUTHORITY, true),
+      isSigner: false,
+      isWritable: true,
+    },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    {
+      pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+      isSigner: false,
+      isWritable: false,
+    },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: BLOCKLIST, isSigner: false, isWritable: false },
+    ...recipients.flatMap((to, i) => [
+      {
+        pubkey: findEscrow(batchEscrowKey(msgHash, i)),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: findQueuedTransfer(batchEscrowKey(msgHash, i)),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: getAssociatedTokenAddressSync(EXA_MINT, to),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: to, isSigner: false, isWritable: false },
+      { pubkey: findAttestation(to), isSigner: false, isWritable: false },
+    ]),
+  ];
+}
+
+/**
+ * Executes a transfer of the registered asset `assetId` to `to` with the given
+ * `payload`.
+ */
+async function receiveAsset(
+  assetId: BN,
+  to: PublicKey,
+  payload: Buffer,
+): Promise<Buffer> {
+  const srcChainId = SOLANA_CHAIN_ID;
+  const { mint, tokenProgram, vault } = await fetchAssetMint(assetId);
+  const msgData = {
+    initialProposal: {
+      senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+      destAddr: EXAMPLE_TOKEN_PROGRAM.programId,
+      totalFee: new BN(80085),
+      payload,
+      reserved: Buffer.from([]),
+      transmitterParams: transmitterParamsEncoded,
+      selector: Array.from(Buffer.alloc(32)),
+    },
+    srcChainData: {
+      srcBlockNumber: new BN(randomInt(256)),
+      srcChainId,
+      srcOpTxId: [
+        Array.from(Keypair.generate().publicKey.toBuffer()),
+        Array.from(Keypair.generate().publicKey.toBuffer()),
+      ],
+    },
+  };
+  const msgHash = msgHashFull(msgData);
+
+  await executeFull({
+    executor,
+    msgData,
+    signatures: [signMsg(signer, msgData)],
+    superSignatures: [signMsg(superSigner, msgData)],
+    accounts: [
+      { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: false },
+      { pubkey: findAsset(assetId), isSigner: false, isWritable: true },
+      {
+        pubkey: findAssetOrigin(srcChainId, assetId),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: findQueuedTransfer(msgHash), isSigner: false, isWritable: true },
+      { pubkey: findEscrow(msgHash), isSigner: false, isWritable: true },
+      { pubkey: findReceipt(msgHash), isSigner: false, isWritable: true },
+      { pubkey: mint, isSigner: false, isWritable: true },
+      {
+        pubkey: vault ?? EXAMPLE_TOKEN_PROGRAM.programId,
+        isSigner: false,
+        isWritable: vault !== null,
+      },
+      { pubkey: ESCROW_AUTHORITY, isSigner: false, isWritable: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(
+          mint,
+          ESCROW_AUTHORITY,
+          true,
+          tokenProgram,
+        ),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: getAssociatedTokenAddressSync(mint, to, true, tokenProgram),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: to, isSigner: false, isWritable: false },
+      { pubkey: tokenProgram, isSigner: false, isWritable: false },
+      {
+        pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: BLOCKLIST, isSigner: false, isWritable: false },
+      { pubkey: findAttestation(to), isSigner: false, isWritable: false },
+    ],
+    spendingLimit: new BN(3_000_000),
+  });
+  await unloadMessage({ payer: executor, message: findMessage(msgData) });
+
+  return msgHash;
+}
+
+/**
+ * Executes a transfer of `amount` from `sender` to the receiver with a call of
+ * SPL Memo v1 logging "bridged".
+ */
+async function receiveCall(
+  amount: BN,
+): Promise<{ msgHash: Buffer; transactionSignature: TransactionSignature }> {
+  const srcChainId = SOLANA_CHAIN_ID;
+  const caller = findCaller(srcChainId, sender.publicKey.toBuffer());
+  const callerAta = getAssociatedTokenAddressSync(EXA_MINT, caller, true);
+  // The whole payload has to fit into the `load_message` transaction, which
+  // leaves room for three call accounts.
+  const callAccounts = [0, 1, 2].map(() => Keypair.generate().publicKey);
+  const payload = Buffer.from(
+    AbiCoder.defaultAbiCoder().encode(
+      [
+        "bytes",
+        "bytes",
+        "uint256",
+        "tuple(bytes32,tuple(bytes32,bool)[],bytes)",
+      ],
+      [
+        sender.publicKey.toBuffer(),
+        receiver.publicKey.toBuffer(),
+        amount.toString(),
+        [
+          MEMO_V1_PROGRAM_ID.toBuffer(),
+          callAccounts.map((key) => [key.toBuffer(), false]),
+          Buffer.from("bridged"),
+        ],
+      ],
+    ).slice(2),
+    "hex",
+  );
+
+  const msgData = {
+    initialProposal: {
+      senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+      destAddr: EXAMPLE_TOKEN_PROGRAM.programId,
+      totalFee: new BN(80085),
+      payload,
+      reserved: Buffer.from([]),
+      transmitterParams: transmitterParamsEncoded,
+      selector: Array.from(Buffer.alloc(32)),
+    },
+    srcChainData: {
+      srcBlockNumber: new BN(randomInt(256)),
+      srcChainId,
+      srcOpTxId: [
+        Array.from(Keypair.generate().publicKey.toBuffer()),
+        Array.from(Keypair.generate().publicKey.toBuffer()),
+      ],
+    },
+  };
+  const msgHash = msgHashFull(msgData);
+
+  const { transactionSignature } = await executeInSteps({
+    executor,
+    msgData,
+    signatures: [signMsg(signer, msgData)],
+    superSignatures: [signMsg(superSigner, msgData)],
+    accounts: [
+      { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: true },
+      { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: true },
+      {
+        pubkey: findQueuedTransfer(msgHash),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: findEscrow(msgHash), isSigner: false, isWritable: true },
+      {
+        pubkey: findPendingForward(msgHash),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: findReceipt(msgHash), isSigner: false, isWritable: true },
+      { pubkey: EXA_MINT, isSigner: false, isWritable: true },
+      {
+        pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: ESCROW_AUTHORITY, isSigner: false, isWritable: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(
+          EXA_MINT,
+          ESCROW_AUTHORITY,
+          true,
+        ),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: callerAta, isSigner: false, isWritable: true },
+      { pubkey: caller, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: BLOCKLIST, isSigner: false, isWritable: false },
+      {
+        pubkey: findAttestation(receiver.publicKey),
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: MEMO_V1_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: caller, isSigner: false, isWritable: false },
+      ...callAccounts.map((pubkey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: false,
+      })),
+    ],
+    spendingLimit: new BN(5_000_000),
+    computeUnits: 400_000,
+  });
+  await unloadMessage({ payer: executor, message: findMessage(msgData) });
+
+  return { msgHash, transactionSignature };
+}
+
 describe("example token", () => {
   test("initialize", async () => {
     // await createMint(
@@ -107,6 +557,8 @@ describe("example token", () => {
         payer,
         admin: admin.publicKey,
         decimals: 18,
+        metadata: { name: "Example Token", symbol: "EXA", uri: "" },
+        freezable: true,
       });
     } catch (e) {
       expect(e.toString()).toInclude("already in use");
@@ -117,6 +569,10 @@ describe("example token", () => {
         EXAMPLE_TOKEN_CONFIG,
       );
     expect(config.admin).toEqual(admin.publicKey);
+    expect(config.chainId.eq(SOLANA_CHAIN_ID)).toBeTrue();
+
+    const exaMint = await getMint(connection, EXA_MINT);
+    expect(exaMint.freezeAuthority).toEqual(EXAMPLE_TOKEN_CONFIG);
   });
 
   test("mint", async () => {
@@ -136,7 +592,7 @@ describe("example token", () => {
     const amount = new BN(1_000_000 * Math.pow(10, 9));
 
     await mint({
-      admin,
+      authority: admin,
       amount,
       owner: sender.publicKey,
     });
@@ -156,7 +612,7 @@ describe("example token", () => {
       "bafkreihwalovxt6jjsko7gws4m34frlqoqi5huqd5x3lacjrqyyzxm3tuy";
 
     await registerExtension({
-      admin,
+      authority: admin,
       payer,
       ipfsCid,
     });
@@ -170,13 +626,54 @@ describe("example token", () => {
     );
   });
 
+  test("setOrigin", async () => {
+    const { decimals } = await getMint(connection, EXA_MINT);
+
+    await setOrigin({
+      authority: admin,
+      payer,
+      chainId: SOLANA_CHAIN_ID,
+      address: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+      enabled: true,
+      decimals,
+    });
+
+    const origin = await EXAMPLE_TOKEN_PROGRAM.account.origin.fetch(
+      findOrigin(SOLANA_CHAIN_ID),
+    );
+    expect(origin.chainId.eq(SOLANA_CHAIN_ID)).toBeTrue();
+    expect(Buffer.from(origin.address)).toEqual(
+      EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+    );
+    expect(origin.enabled).toBeTrue();
+    expect(origin.decimals).toEqual(decimals);
+  });
+
+  test("removeOrigin", async () => {
+    await setOrigin({
+      authority: admin,
+      payer,
+      chainId: TEIB_CHAIN_ID,
+      address: Buffer.alloc(32, 1),
+      enabled: false,
+      decimals: 18,
+    });
+
+    await removeOrigin({ authority: admin, chainId: TEIB_CHAIN_ID });
+
+    const origin = await EXAMPLE_TOKEN_PROGRAM.account.origin.fetch(
+      findOrigin(TEIB_CHAIN_ID),
+    );
+    expect(origin.enabled).toBeFalse();
+    expect(origin.address).toEqual(Array(32).fill(0));
+    expect(origin.decimals).toEqual(18);
+  });
+
   const uipFee = new BN(80085);
   const customGasLimit = new BN(1_000_000);
   const srcOpTxId = new Array<Array<number>>();
   const amount = new BN(1_000_000);
-  const destination: Destination = connection.rpcEndpoint.includes("mainnet")
-    ? { solanaMainnet: {} }
-    : { solanaDevnet: {} };
+  const destChainId = SOLANA_CHAIN_ID;
 
   test("bridge", async () => {
     const eventPromise: Promise<void> = new Promise((resolve, reject) => {
@@ -203,12 +700,12 @@ describe("example token", () => {
     ).then((x) => x.amount);
 
     const { transactionSignature } = await bridge({
-      destination,
+      destChainId,
       uipFee,
       customGasLimit,
       sender,
       amount,
-      to: receiver.publicKey.toBuffer(),
+      to: { solana: { address: receiver.publicKey } },
     });
     const txId = bs58.decode(transactionSignature);
     srcOpTxId[0] = Array.from(txId.subarray(0, 32));
@@ -228,110 +725,900 @@ describe("example token", () => {
     );
   });
 
-  test("receive", async () => {
-    const destAddr = EXAMPLE_TOKEN_PROGRAM.programId.toBuffer();
-    const uipFee = new BN(80085);
-    const srcBlockNumber = new BN(randomInt(256));
-    const srcChainId = SOLANA_CHAIN_ID;
-    const srcOpTxId = new Array<Array<number>>();
-    let selector = new Array<number>();
-    let payload: Buffer = Buffer.alloc(0);
+  test("bridge to unknown chain", async () => {
+    await expect(
+      bridge({
+        destChainId: TEIB_CHAIN_ID,
+        uipFee,
+        customGasLimit,
+        sender,
+        amount,
+        to: { solana: { address: receiver.publicKey } },
+      }),
+    ).rejects.toThrow("UnknownDestinationChain");
+  });
 
-    const eventPromise: Promise<void> = new Promise((resolve, reject) => {
-      UIP_PROGRAM.addEventListener(
-        "messageProposed",
-        (event) => {
-          try {
-            expect(event.sender).toEqual(EXAMPLE_TOKEN_PROGRAM.programId);
-            selector = event.selector;
-            payload = event.payload;
-            resolve();
-          } catch (error) {
-            reject(error);
-          }
-        },
-      );
+  test("bridge to invalid recipient", async () => {
+    for (
+      const to of [
+        { evm: { address: Array.from(Buffer.alloc(20, 1)) } },
+        { solana: { address: PublicKey.default } },
+      ]
+    ) {
+      await expect(
+        bridge({
+          destChainId,
+          uipFee,
+          customGasLimit,
+          sender,
+          amount,
+          to,
+        }),
+      ).rejects.toThrow("InvalidRecipient");
+    }
+  });
 
-      setTimeout(() => {
-        reject(new Error("Event did not fire within timeout"));
-      }, 15000);
+  test("bridge with permit", async () => {
+    await approvePermit({ owner: sender, amount });
+
+    const nonce = await fetchPermitNonce(sender.publicKey);
+    const permit = {
+      destChainId,
+      to: { solana: { address: receiver.publicKey } },
+      amount,
+      nonce,
+      deadline: new BN(Math.floor(Date.now() / 1000) + 600),
+      customGasLimit,
+      commitment: { confirmed: {} },
+    };
+    const signature = signPermit(permit, sender);
+    const senderAta = getAssociatedTokenAddressSync(EXA_MINT, sender.publicKey);
+    const balanceBefore = await getAccount(connection, senderAta)
+      .then((x) => x.amount);
+
+    await bridgeWithPermit({
+      permit,
+      owner: sender.publicKey,
+      signature,
+      relayer: proposer,
+      uipFee,
     });
 
-    const { transactionSignature } = await bridge({
+    const balanceAfter = await getAccount(connection, senderAta)
+      .then((x) => x.amount);
+    expect(balanceBefore - balanceAfter).toEqual(BigInt(amount.toString()));
+    expect((await fetchPermitNonce(sender.publicKey)).eq(nonce.addn(1)))
+      .toBeTrue();
+
+    const input = {
+      permit,
+      owner: sender.publicKey,
+      signature,
+      relayer: proposer,
       uipFee,
-      customGasLimit,
-      destination,
-      sender,
-      amount,
-      to: receiver.publicKey.toBuffer(),
+    };
+    await expect(bridgeWithPermit(input)).rejects.toThrow(
+      "InvalidPermitNonce",
+    );
+    await expect(
+      bridgeWithPermit({
+        ...input,
+        permit: { ...permit, nonce: nonce.addn(1), amount: amount.muln(2) },
+      }),
+    ).rejects.toThrow("InvalidPermitSignature");
+    await expect(
+      bridgeWithPermit({
+        ...input,
+        permit: { ...permit, nonce: nonce.addn(1), customGasLimit: new BN(1) },
+      }),
+    ).rejects.toThrow("InvalidPermitSignature");
+
+    // A permit signed for another cluster can't be replayed on this one.
+    const otherCluster = { ...permit, nonce: nonce.addn(1) };
+    await expect(
+      bridgeWithPermit({
+        ...input,
+        permit: otherCluster,
+        signature: signPermit(otherCluster, sender, SOLANA_MAINNET_CHAIN_ID),
+        chainId: SOLANA_MAINNET_CHAIN_ID,
+      }),
+    ).rejects.toThrow("InvalidPermitSignature");
+  });
+
+  test("bridge with dust", async () => {
+    const { decimals } = await getMint(connection, EXA_MINT);
+
+    await setOrigin({
+      authority: admin,
+      payer,
+      chainId: POLYGON_AMOY_CHAIN_ID,
+      address: Buffer.alloc(32, 1),
+      enabled: true,
+      decimals: decimals - 3,
     });
 
-    const txId = bs58.decode(transactionSignature);
-    srcOpTxId[0] = Array.from(txId.subarray(0, 32));
-    srcOpTxId[1] = Array.from(txId.subarray(32));
+    await expect(
+      bridge({
+        destChainId: POLYGON_AMOY_CHAIN_ID,
+        uipFee,
+        customGasLimit,
+        sender,
+        amount: new BN(1_000_001),
+        to: { solana: { address: new PublicKey(Buffer.alloc(32, 2)) } },
+      }),
+    ).rejects.toThrow("AmountHasDust");
 
-    await eventPromise;
+    await removeOrigin({ authority: admin, chainId: POLYGON_AMOY_CHAIN_ID });
+  });
 
-    const msgData = {
-      initialProposal: {
-        senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
-        destAddr: new PublicKey(destAddr),
-        totalFee: uipFee,
-        payload,
-        reserved: Buffer.from([]),
-        transmitterParams: transmitterParamsEncoded,
-        selector,
-      },
-      srcChainData: {
-        srcBlockNumber,
-        srcChainId,
-        srcOpTxId,
-      },
-    };
+  test("solana-only payloads", async () => {
+    const { decimals } = await getMint(connection, EXA_MINT);
 
-    const signatures = [signMsg(signer, msgData)];
-    const superSignatures = [signMsg(superSigner, msgData)];
+    await setOrigin({
+      authority: admin,
+      payer,
+      chainId: POLYGON_AMOY_CHAIN_ID,
+      address: Buffer.alloc(32, 1),
+      enabled: true,
+      decimals,
+    });
 
-    const accounts = [
-      { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: false },
-      { pubkey: EXA_MINT, isSigner: false, isWritable: true },
-      {
-        pubkey: getAssociatedTokenAddressSync(
-          EXA_MINT,
-          receiver.publicKey,
-        ),
-        isSigner: false,
-        isWritable: true,
-      },
-      { pubkey: receiver.publicKey, isSigner: false, isWritable: false },
-      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-      {
-        pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
-        isSigner: false,
-        isWritable: false,
-      },
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-    ];
+    // The EVM example token decodes neither refunds nor batches.
+    await expect(
+      setRefundable({
+        authority: admin,
+        chainId: POLYGON_AMOY_CHAIN_ID,
+        refundable: true,
+        refundWindow: new BN(3),
+      }),
+    ).rejects.toThrow("SolanaOnlyPayload");
+    await expect(
+      bridgeBatch({
+        uipFee,
+        customGasLimit,
+        destChainId: POLYGON_AMOY_CHAIN_ID,
+        sender,
+        recipients: [
+          { to: { evm: { address: Array.from(Buffer.alloc(20, 2)) } }, amount },
+        ],
+      }),
+    ).rejects.toThrow("SolanaOnlyPayload");
 
-    const input = {
-      executor,
-      msgData,
-      signatures,
-      superSignatures,
-      accounts,
-      spendingLimit: new BN(100_000),
-    };
+    await removeOrigin({ authority: admin, chainId: POLYGON_AMOY_CHAIN_ID });
+  });
 
-    const liteSimulationLamports = await simulateExecuteLite({
-      payer: admin.publicKey,
-      accounts,
-      destAddr: new PublicKey(destAddr),
-      payload,
-      senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
-      srcChainId,
+  test("bridge with limits", async () => {
+    const { decimals } = await getMint(connection, EXA_MINT);
+
+    await setOrigin({
+      authority: admin,
+      payer,
+      chainId: POLYGON_AMOY_CHAIN_ID,
+      address: Buffer.alloc(32, 1),
+      enabled: true,
+      decimals,
     });
 
-    const balanceBefore = await connection.getBalance(executor.publicKey);
+    const noLimits = {
+      outboundCapacity: new BN(0),
+      outboundWindow: new BN(0),
+      inboundCapacity: new BN(0),
+      inboundWindow: new BN(0),
+      minAmount: new BN(0),
+      maxAmount: new BN(0),
+      userDailyCap: new BN(0),
+    };
+    const bridgeAmount = (amount: BN) =>
+      bridge({
+        destChainId: POLYGON_AMOY_CHAIN_ID,
+        uipFee,
+        customGasLimit,
+        sender,
+        amount,
+        to: { solana: { address: new PublicKey(Buffer.alloc(32, 2)) } },
+      });
+
+    await setLimits({
+      authority: admin,
+      chainId: POLYGON_AMOY_CHAIN_ID,
+      limits: { ...noLimits, minAmount: new BN(1000), maxAmount: new BN(5000) },
+    });
+    await expect(bridgeAmount(new BN(999))).rejects.toThrow(
+      "AmountBelowMinimum",
+    );
+    await expect(bridgeAmount(new BN(5001))).rejects.toThrow(
+      "AmountAboveMaximum",
+    );
+
+    await setLimits({
+      authority: admin,
+      chainId: POLYGON_AMOY_CHAIN_ID,
+      limits: {
+        ...noLimits,
+        outboundCapacity: new BN(5000),
+        outboundWindow: new BN(86400),
+      },
+    });
+    await expect(bridgeAmount(new BN(5001))).rejects.toThrow(
+      "OutboundRateLimitExceeded",
+    );
+
+    await setLimits({
+      authority: admin,
+      chainId: POLYGON_AMOY_CHAIN_ID,
+      limits: { ...noLimits, userDailyCap: new BN(5000) },
+    });
+    await expect(bridgeAmount(new BN(5001))).rejects.toThrow(
+      "UserDailyLimitExceeded",
+    );
+
+    const origin = await EXAMPLE_TOKEN_PROGRAM.account.origin.fetch(
+      findOrigin(POLYGON_AMOY_CHAIN_ID),
+    );
+    expect(origin.userDailyCap.eqn(5000)).toBeTrue();
+
+    await removeOrigin({ authority: admin, chainId: POLYGON_AMOY_CHAIN_ID });
+  });
+
+  test("pause", async () => {
+    await setGuardian({ admin, guardian: guardian.publicKey });
+
+    const config = await EXAMPLE_TOKEN_PROGRAM.account.exampleTokenConfig
+      .fetch(EXAMPLE_TOKEN_CONFIG);
+    expect(config.guardian).toEqual(guardian.publicKey);
+
+    const bridgeInput = {
+      destChainId,
+      uipFee,
+      customGasLimit,
+      sender,
+      amount,
+      to: { solana: { address: receiver.publicKey } },
+    };
+
+    await pause({ authority: guardian, target: { outbound: {} } });
+    await expect(bridge(bridgeInput)).rejects.toThrow("OutboundPaused");
+    await expect(
+      unpause({ admin: guardian, target: { outbound: {} } }),
+    ).rejects.toThrow("ConstraintHasOne");
+    await unpause({ admin, target: { outbound: {} } });
+
+    await pause({
+      authority: guardian,
+      target: { route: { chainId: destChainId } },
+    });
+    await expect(bridge(bridgeInput)).rejects.toThrow("RoutePaused");
+    await unpause({ admin, target: { route: { chainId: destChainId } } });
+
+    await expect(
+      pause({ authority: sender, target: { inbound: {} } }),
+    ).rejects.toThrow("NotGuardian");
+
+    const config2 = await EXAMPLE_TOKEN_PROGRAM.account.exampleTokenConfig
+      .fetch(EXAMPLE_TOKEN_CONFIG);
+    expect(config2.outboundPaused).toBeFalse();
+    expect(config2.inboundPaused).toBeFalse();
+    expect(config2.pausedRoutes).toEqual([]);
+  });
+
+  test("blocklist", async () => {
+    const bridgeInput = {
+      destChainId,
+      uipFee,
+      customGasLimit,
+      sender,
+      amount,
+      to: { solana: { address: receiver.publicKey } },
+    };
+
+    await expect(
+      block({ admin: sender, address: sender.publicKey.toBuffer() }),
+    ).rejects.toThrow("ConstraintHasOne");
+
+    await block({ admin, address: sender.publicKey.toBuffer() });
+    await expect(bridge(bridgeInput)).rejects.toThrow("Blocked");
+    await unblock({ admin, address: sender.publicKey.toBuffer() });
+
+    await block({ admin, address: receiver.publicKey.toBuffer() });
+    await expect(bridge(bridgeInput)).rejects.toThrow("Blocked");
+    await unblock({ admin, address: receiver.publicKey.toBuffer() });
+
+    const blocklist = await EXAMPLE_TOKEN_PROGRAM.account.blocklist.fetch(
+      BLOCKLIST,
+    );
+    expect(blocklist.addresses).toEqual([]);
+  });
+
+  test("freeze", async () => {
+    const tokenAccount = getAssociatedTokenAddressSync(
+      EXA_MINT,
+      sender.publicKey,
+    );
+
+    await expect(freeze({ admin: sender, tokenAccount })).rejects.toThrow(
+      "ConstraintHasOne",
+    );
+
+    await freeze({ admin, tokenAccount });
+    expect(
+      await getAccount(connection, tokenAccount).then((x) => x.isFrozen),
+    ).toBeTrue();
+
+    await thaw({ admin, tokenAccount });
+    expect(
+      await getAccount(connection, tokenAccount).then((x) => x.isFrozen),
+    ).toBeFalse();
+  });
+
+  test("attestation", async () => {
+    const authority = new Keypair();
+    await disperse(connection, [authority.publicKey], payer, 10_000_000);
+
+    await expect(
+      attest({ authority, recipient: receiver.publicKey }),
+    ).rejects.toThrow("NotAttestationAuthority");
+
+    await setAttestationAuthority({ admin, authority: authority.publicKey });
+    await attest({ authority, recipient: receiver.publicKey });
+
+    const attestation = await EXAMPLE_TOKEN_PROGRAM.account.attestation.fetch(
+      findAttestation(receiver.publicKey),
+    );
+    expect(attestation.issuer).toEqual(authority.publicKey);
+
+    await revokeAttestation({ authority, recipient: receiver.publicKey });
+    expect(
+      await connection.getAccountInfo(findAttestation(receiver.publicKey)),
+    ).toBeNull();
+
+    await setAttestationAuthority({ admin, authority: null });
+  });
+
+  test("assets", async () => {
+    await expect(
+      registerAsset({ admin, payer, assetId: new BN(0), decimals: 6 }),
+    ).rejects.toThrow("InvalidAssetId");
+
+    const mintedId = new BN(1);
+    await registerAsset({ admin, payer, assetId: mintedId, decimals: 6 });
+    const minted = await EXAMPLE_TOKEN_PROGRAM.account.asset.fetch(
+      findAsset(mintedId),
+    );
+    expect(minted.mint).toEqual(findAssetMint(mintedId));
+    expect(minted.lockRelease).toBeFalse();
+    const { mintAuthority } = await getMint(connection, minted.mint);
+    expect(mintAuthority).toEqual(findAsset(mintedId));
+
+    const lockedId = new BN(2);
+    const lockedMint = await createMint(
+      connection,
+      payer,
+      payer.publicKey,
+      null,
+      9,
+    );
+    await registerAssetLockRelease({
+      admin,
+      payer,
+      assetId: lockedId,
+      mint: lockedMint,
+    });
+    await setAssetOrigin({
+      authority: admin,
+      payer,
+      assetId: lockedId,
+      chainId: SOLANA_CHAIN_ID,
+      address: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+      enabled: true,
+      decimals: 9,
+      remoteMint: lockedMint,
+    });
+    const origin = await EXAMPLE_TOKEN_PROGRAM.account.origin.fetch(
+      findAssetOrigin(SOLANA_CHAIN_ID, lockedId),
+    );
+    expect(origin.enabled).toBeTrue();
+    expect(origin.remoteMint).toEqual(lockedMint);
+    expect(origin.remoteToken2022).toBeFalse();
+
+    const tokenAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      lockedMint,
+      sender.publicKey,
+    );
+    await mintTo(
+      connection,
+      payer,
+      lockedMint,
+      tokenAccount.address,
+      payer,
+      BigInt(amount.toString()),
+    );
+
+    await expect(
+      bridgeAsset({
+        assetId: mintedId,
+        destChainId,
+        uipFee,
+        customGasLimit,
+        sender,
+        amount,
+        to: { solana: { address: receiver.publicKey } },
+      }),
+    ).rejects.toThrow("UnknownDestinationChain");
+
+    await bridgeAsset({
+      assetId: lockedId,
+      destChainId,
+      uipFee,
+      customGasLimit,
+      sender,
+      amount,
+      to: { solana: { address: receiver.publicKey } },
+    });
+    const vault = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(lockedMint, findAsset(lockedId), true),
+    );
+    expect(vault.amount).toEqual(BigInt(amount.toString()));
+    const locked = await EXAMPLE_TOKEN_PROGRAM.account.asset.fetch(
+      findAsset(lockedId),
+    );
+    expect(locked.vaultBalance.eq(amount)).toBeTrue();
+  });
+
+  test("token-2022 asset", async () => {
+    const assetId = new BN(3);
+    const mint = await createMint(
+      connection,
+      payer,
+      payer.publicKey,
+      null,
+      9,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    await registerAssetLockRelease({ admin, payer, assetId, mint });
+    await setAssetOrigin({
+      authority: admin,
+      payer,
+      assetId,
+      chainId: SOLANA_CHAIN_ID,
+      address: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+      enabled: true,
+      decimals: 9,
+      remoteMint: mint,
+      remoteToken2022: true,
+    });
+
+    const senderAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      mint,
+      sender.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    await mintTo(
+      connection,
+      payer,
+      mint,
+      senderAta.address,
+      payer,
+      BigInt(amount.toString()),
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+
+    let payload: Buffer = Buffer.alloc(0);
+    const eventPromise: Promise<void> = new Promise((resolve, reject) => {
+      UIP_PROGRAM.addEventListener("messageProposed", (event) => {
+        payload = event.payload;
+        resolve();
+      });
+
+      setTimeout(() => {
+        reject(new Error("Event did not fire within timeout"));
+      }, 15000);
+    });
+
+    await bridgeAsset({
+      assetId,
+      destChainId,
+      uipFee,
+      customGasLimit,
+      sender,
+      amount,
+      to: { solana: { address: receiver.publicKey } },
+    });
+    await eventPromise;
+
+    const asset = findAsset(assetId);
+    const vault = getAssociatedTokenAddressSync(
+      mint,
+      asset,
+      true,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    expect(
+      await getAccount(connection, vault, undefined, TOKEN_2022_PROGRAM_ID)
+        .then((x) => x.amount),
+    ).toEqual(BigInt(amount.toString()));
+
+    const receiverAta = getAssociatedTokenAddressSync(
+      mint,
+      receiver.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+    );
+
+    // The payload carries the mint, from which the extension derives the
+    // accounts of the locked asset.
+    const [, , , , remoteMint, remoteToken2022] = AbiCoder.defaultAbiCoder()
+      .decode(
+        ["uint64", "bytes", "bytes", "uint256", "bytes32", "bool"],
+        payload,
+      );
+    expect(remoteMint).toEqual("0x" + mint.toBuffer().toString("hex"));
+    expect(remoteToken2022).toBeTrue();
+
+    await receiveAsset(assetId, receiver.publicKey, payload);
+
+    expect(
+      await getAccount(
+        connection,
+        receiverAta,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      ).then((x) => x.amount),
+    ).toEqual(BigInt(amount.toString()));
+    expect(
+      await getAccount(connection, vault, undefined, TOKEN_2022_PROGRAM_ID)
+        .then((x) => x.amount),
+    ).toEqual(0n);
+    const { vaultBalance } = await EXAMPLE_TOKEN_PROGRAM.account.asset.fetch(
+      asset,
+    );
+    expect(vaultBalance.isZero()).toBeTrue();
+  });
+
+  test("receive delayed asset", async () => {
+    // The locked asset registered above, whose vault holds `amount`.
+    const assetId = new BN(2);
+    const { mint, vault } = await fetchAssetMint(assetId);
+    const receiverAta = getAssociatedTokenAddressSync(
+      mint,
+      receiver.publicKey,
+    );
+    const payload = Buffer.from(
+      AbiCoder.defaultAbiCoder().encode(
+        ["uint64", "bytes", "bytes", "uint256", "bytes32", "bool"],
+        [
+          assetId.toString(),
+          sender.publicKey.toBuffer(),
+          receiver.publicKey.toBuffer(),
+          amount.toString(),
+          mint.toBuffer(),
+          false,
+        ],
+      ).slice(2),
+      "hex",
+    );
+
+    await setSettlementDelay({ admin, threshold: amount, delay: new BN(3) });
+    try {
+      const msgHash = await receiveAsset(
+        assetId,
+        receiver.publicKey,
+        payload,
+      );
+      expect(
+        await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(msgHash))
+          .then((x) => x.status),
+      ).toEqual({ queued: {} });
+      const queuedTransfer = await EXAMPLE_TOKEN_PROGRAM.account
+        .queuedTransfer.fetch(findQueuedTransfer(msgHash));
+      expect(queuedTransfer.assetId.eq(assetId)).toBeTrue();
+      expect(queuedTransfer.to).toEqual(receiver.publicKey);
+      expect(await getAccount(connection, vault).then((x) => x.amount))
+        .toEqual(BigInt(amount.toString()));
+
+      await expect(
+        releaseQueuedTransfer({ payer: executor, msgHash }),
+      ).rejects.toThrow("QueuedAssetMismatch");
+      await expect(
+        releaseQueuedAssetTransfer({ payer: executor, msgHash }),
+      ).rejects.toThrow("SettlementDelayNotElapsed");
+
+      await sleep(4000);
+
+      await releaseQueuedAssetTransfer({ payer: executor, msgHash });
+      expect(await getAccount(connection, receiverAta).then((x) => x.amount))
+        .toEqual(BigInt(amount.toString()));
+      expect(await getAccount(connection, vault).then((x) => x.amount))
+        .toEqual(0n);
+      expect(
+        await connection.getAccountInfo(findQueuedTransfer(msgHash)),
+      ).toBeNull();
+    } finally {
+      await setSettlementDelay({
+        admin,
+        threshold: new BN(0),
+        delay: new BN(0),
+      });
+    }
+  });
+
+  test("roles", async () => {
+    const roles = await EXAMPLE_TOKEN_PROGRAM.account.roles.fetch(ROLES);
+    expect(roles.minters).toEqual([admin.publicKey]);
+    expect(roles.originManagers).toEqual([admin.publicKey]);
+    expect(roles.pausers).toEqual([admin.publicKey]);
+    expect(roles.extensionManagers).toEqual([admin.publicKey]);
+
+    const mintInput = {
+      authority: sender,
+      amount: new BN(1),
+      owner: sender.publicKey,
+    };
+    await expect(mint(mintInput)).rejects.toThrow("MissingRole");
+
+    await grantRole({
+      admin,
+      role: { minter: {} },
+      account: sender.publicKey,
+    });
+    await mint(mintInput);
+    await expect(
+      setLimits({
+        authority: sender,
+        chainId: destChainId,
+        limits: {
+          outboundCapacity: new BN(0),
+          outboundWindow: new BN(0),
+          inboundCapacity: new BN(0),
+          inboundWindow: new BN(0),
+          minAmount: new BN(0),
+          maxAmount: new BN(0),
+          userDailyCap: new BN(0),
+        },
+      }),
+    ).rejects.toThrow("MissingRole");
+
+    await revokeRole({
+      admin,
+      role: { minter: {} },
+      account: sender.publicKey,
+    });
+    await expect(mint(mintInput)).rejects.toThrow("MissingRole");
+    await expect(
+      grantRole({
+        admin: sender,
+        role: { minter: {} },
+        account: sender.publicKey,
+      }),
+    ).rejects.toThrow("ConstraintHasOne");
+  });
+
+  test("finalized threshold", async () => {
+    await setFinalizedThreshold({ admin, threshold: amount });
+
+    const config = await EXAMPLE_TOKEN_PROGRAM.account.exampleTokenConfig
+      .fetch(EXAMPLE_TOKEN_CONFIG);
+    expect(config.finalizedThreshold.eq(amount)).toBeTrue();
+
+    await bridge({
+      destChainId,
+      uipFee,
+      customGasLimit,
+      sender,
+      amount,
+      to: { solana: { address: receiver.publicKey } },
+      commitment: { confirmed: {} },
+    });
+
+    await expect(
+      setFinalizedThreshold({ admin: sender, threshold: new BN(0) }),
+    ).rejects.toThrow("ConstraintHasOne");
+    await setFinalizedThreshold({ admin, threshold: new BN(0) });
+  });
+
+  test("bridge with fee", async () => {
+    await setFee({ admin, feeBps: 100, referrerShareBps: 2500 });
+
+    const referrerAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      EXA_MINT,
+      referrer.publicKey,
+    ).then((x) => x.address);
+    const senderAta = getAssociatedTokenAddressSync(EXA_MINT, sender.publicKey);
+    const treasury = getAssociatedTokenAddressSync(
+      EXA_MINT,
+      TREASURY_AUTHORITY,
+      true,
+    );
+    const balances = () =>
+      Promise.all(
+        [senderAta, referrerAta, treasury].map((x) =>
+          getAccount(connection, x).then((x) => x.amount)
+        ),
+      );
+
+    const [senderBefore, referrerBefore, treasuryBefore] = await balances();
+
+    await bridge({
+      destChainId,
+      uipFee,
+      customGasLimit,
+      sender,
+      amount,
+      to: { solana: { address: receiver.publicKey } },
+      referrer: referrer.publicKey,
+    });
+
+    const [senderAfter, referrerAfter, treasuryAfter] = await balances();
+    expect(senderBefore - senderAfter).toEqual(BigInt(amount.toString()));
+    expect(referrerAfter - referrerBefore).toEqual(2500n);
+    expect(treasuryAfter - treasuryBefore).toEqual(7500n);
+
+    await withdrawFees({
+      admin,
+      destination: referrerAta,
+      amount: new BN(7500),
+    });
+    const [, referrerWithdrawn] = await balances();
+    expect(referrerWithdrawn - referrerAfter).toEqual(7500n);
+
+    await setFee({ admin, feeBps: 0, referrerShareBps: 0 });
+  });
+
+  test("receive", async () => {
+    const destAddr = EXAMPLE_TOKEN_PROGRAM.programId.toBuffer();
+    const uipFee = new BN(80085);
+    const srcBlockNumber = new BN(randomInt(256));
+    const srcChainId = SOLANA_CHAIN_ID;
+    const srcOpTxId = new Array<Array<number>>();
+    let selector = new Array<number>();
+    let payload: Buffer = Buffer.alloc(0);
+
+    const eventPromise: Promise<void> = new Promise((resolve, reject) => {
+      UIP_PROGRAM.addEventListener(
+        "messageProposed",
+        (event) => {
+          try {
+            expect(event.sender).toEqual(EXAMPLE_TOKEN_PROGRAM.programId);
+            selector = event.selector;
+            payload = event.payload;
+            resolve();
+          } catch (error) {
+            reject(error);
+          }
+        },
+      );
+
+      setTimeout(() => {
+        reject(new Error("Event did not fire within timeout"));
+      }, 15000);
+    });
+
+    const { transactionSignature } = await bridge({
+      uipFee,
+      customGasLimit,
+      destChainId,
+      sender,
+      amount,
+      to: { solana: { address: receiver.publicKey } },
+    });
+
+    const txId = bs58.decode(transactionSignature);
+    srcOpTxId[0] = Array.from(txId.subarray(0, 32));
+    srcOpTxId[1] = Array.from(txId.subarray(32));
+
+    await eventPromise;
+
+    const msgData = {
+      initialProposal: {
+        senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+        destAddr: new PublicKey(destAddr),
+        totalFee: uipFee,
+        payload,
+        reserved: Buffer.from([]),
+        transmitterParams: transmitterParamsEncoded,
+        selector,
+      },
+      srcChainData: {
+        srcBlockNumber,
+        srcChainId,
+        srcOpTxId,
+      },
+    };
+
+    const signatures = [signMsg(signer, msgData)];
+    const superSignatures = [signMsg(superSigner, msgData)];
+
+    const accounts = [
+      { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: true },
+      { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: true },
+      {
+        pubkey: findQueuedTransfer(msgHashFull(msgData)),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: findEscrow(msgHashFull(msgData)),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: findPendingForward(msgHashFull(msgData)),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: findReceipt(msgHashFull(msgData)),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: EXA_MINT, isSigner: false, isWritable: true },
+      {
+        pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: ESCROW_AUTHORITY, isSigner: false, isWritable: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(EXA_MINT, ESCROW_AUTHORITY, true),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: getAssociatedTokenAddressSync(
+          EXA_MINT,
+          receiver.publicKey,
+        ),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: receiver.publicKey, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: BLOCKLIST, isSigner: false, isWritable: false },
+      {
+        pubkey: findAttestation(receiver.publicKey),
+        isSigner: false,
+        isWritable: false,
+      },
+    ];
+
+    const input = {
+      executor,
+      msgData,
+      signatures,
+      superSignatures,
+      accounts,
+      spendingLimit: new BN(100_000),
+    };
+
+    const liteSimulationLamports = await simulateExecuteLite({
+      payer: admin.publicKey,
+      accounts,
+      destAddr: new PublicKey(destAddr),
+      payload,
+      senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+      srcChainId,
+    });
+
+    const balanceBefore = await connection.getBalance(executor.publicKey);
 
     let exaBalanceBefore: bigint = 0n;
     try {
@@ -341,32 +1628,1113 @@ describe("example token", () => {
           EXA_MINT,
           receiver.publicKey,
         ),
-      ).then((x) => x.amount);
-    } catch (e) {
-      expect(e.toString()).toInclude("TokenAccountNotFoundError");
+      ).then((x) => x.amount);
+    } catch (e) {
+      expect(e.toString()).toInclude("TokenAccountNotFoundError");
+    }
+
+    input.spendingLimit = new BN(3_000_000);
+    await executeFull(input);
+
+    const exaBalanceAfter = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(
+        EXA_MINT,
+        receiver.publicKey,
+      ),
+    ).then((x) => x.amount);
+    expect(exaBalanceAfter - exaBalanceBefore).toEqual(
+      BigInt(amount.toString()),
+    );
+
+    const receipt = await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(
+      findReceipt(msgHashFull(msgData)),
+    );
+    expect(receipt.srcChainId.toString()).toEqual(srcChainId.toString());
+    expect(receipt.recipient).toEqual(receiver.publicKey.toBuffer());
+    expect(receipt.amount.toString()).toEqual(amount.toString());
+    expect(receipt.status).toEqual({ delivered: {} });
+
+    await unloadMessage({ payer: executor, message: findMessage(msgData) });
+
+    const balanceAfter = await connection.getBalance(executor.publicKey);
+
+    expect(balanceBefore - balanceAfter).toEqual(
+      Number(liteSimulationLamports) + 5000,
+    );
+  });
+
+  test("escrow", async () => {
+    const escrowedStatus = (msgHash: Buffer) =>
+      EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(msgHash))
+        .then((x) => x.status);
+
+    // The executor can't force the escrow of a valid recipient by passing
+    // an executable account in its place.
+    await expect(
+      receiveTransfer(receiver.publicKey, amount, TOKEN_PROGRAM_ID),
+    ).rejects.toThrow("ConstraintAddress");
+
+    // A blocked recipient gets the tokens escrowed until it's unblocked.
+    await block({ admin, address: receiver.publicKey.toBuffer() });
+    const msgHash = await receiveTransfer(receiver.publicKey, amount).finally(
+      () => unblock({ admin, address: receiver.publicKey.toBuffer() }),
+    );
+    expect(await escrowedStatus(msgHash)).toEqual({ escrowed: {} });
+
+    const senderAta = getAssociatedTokenAddressSync(
+      EXA_MINT,
+      sender.publicKey,
+    );
+    await expect(
+      claim({ recipient: sender, msgHash, destination: senderAta }),
+    ).rejects.toThrow("NotEscrowRecipient");
+
+    const receiverAta = getAssociatedTokenAddressSync(
+      EXA_MINT,
+      receiver.publicKey,
+    );
+    // The admin can't move the tokens of a valid recipient elsewhere.
+    await expect(
+      recoverEscrow({ admin, msgHash, destination: senderAta }),
+    ).rejects.toThrow("ConstraintAddress");
+
+    await pause({ authority: guardian, target: { inbound: {} } });
+    await expect(
+      claim({ recipient: receiver, msgHash, destination: receiverAta }),
+    ).rejects.toThrow("InboundPaused");
+    await unpause({ admin, target: { inbound: {} } });
+
+    const receiverBefore = await getAccount(connection, receiverAta)
+      .then((x) => x.amount);
+    await claim({ recipient: receiver, msgHash, destination: receiverAta });
+    const receiverAfter = await getAccount(connection, receiverAta)
+      .then((x) => x.amount);
+    expect(receiverAfter - receiverBefore).toEqual(BigInt(amount.toString()));
+    expect(await connection.getAccountInfo(findEscrow(msgHash))).toBeNull();
+
+    // An EVM address can't receive tokens on Solana, so only the admin can
+    // recover them.
+    const malformed = await receiveTransfer(Buffer.alloc(20, 7), amount);
+    expect(await escrowedStatus(malformed)).toEqual({ escrowed: {} });
+    const escrow = await EXAMPLE_TOKEN_PROGRAM.account.escrow.fetch(
+      findEscrow(malformed),
+    );
+    expect(escrow.to).toEqual(Buffer.alloc(20, 7));
+
+    await expect(
+      recoverEscrow({ admin: sender, msgHash: malformed, destination: senderAta }),
+    ).rejects.toThrow("ConstraintHasOne");
+    const senderBefore = await getAccount(connection, senderAta)
+      .then((x) => x.amount);
+    await recoverEscrow({ admin, msgHash: malformed, destination: senderAta });
+    const senderAfter = await getAccount(connection, senderAta)
+      .then((x) => x.amount);
+    expect(senderAfter - senderBefore).toEqual(BigInt(amount.toString()));
+    expect(await connection.getAccountInfo(findEscrow(malformed))).toBeNull();
+  });
+
+  test("receive with a call", async () => {
+    const srcChainId = SOLANA_CHAIN_ID;
+    const caller = findCaller(srcChainId, sender.publicKey.toBuffer());
+    const callerAta = getAssociatedTokenAddressSync(EXA_MINT, caller, true);
+
+    const { msgHash, transactionSignature } = await receiveCall(amount);
+
+    // The tokens are held by the caller PDA of the source chain and sender.
+    expect(await getAccount(connection, callerAta).then((x) => x.amount))
+      .toEqual(BigInt(amount.toString()));
+    const receipt = await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(
+      findReceipt(msgHash),
+    );
+    expect(receipt.recipient).toEqual(receiver.publicKey.toBuffer());
+    expect(receipt.status).toEqual({ delivered: {} });
+
+    const tx = await connection.getTransaction(transactionSignature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    expect(tx.meta.logMessages.join("\n")).toInclude("bridged");
+    expect(findCaller(POLYGON_AMOY_CHAIN_ID, sender.publicKey.toBuffer()))
+      .not.toEqual(caller);
+  });
+
+  test("receive delayed call", async () => {
+    const receiverAta = getAssociatedTokenAddressSync(
+      EXA_MINT,
+      receiver.publicKey,
+    );
+
+    await setSettlementDelay({ admin, threshold: amount, delay: new BN(3) });
+    try {
+      const { msgHash, transactionSignature } = await receiveCall(amount);
+
+      // The transfer is queued for the recipient and the call is skipped.
+      expect(
+        await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(msgHash))
+          .then((x) => x.status),
+      ).toEqual({ queued: {} });
+      const queuedTransfer = await EXAMPLE_TOKEN_PROGRAM.account
+        .queuedTransfer.fetch(findQueuedTransfer(msgHash));
+      expect(queuedTransfer.to).toEqual(receiver.publicKey);
+      const tx = await connection.getTransaction(transactionSignature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const logs = tx.meta.logMessages.join("\n");
+      expect(logs).toInclude("Skipped the call as the tokens were queued");
+      expect(logs).not.toInclude("bridged");
+
+      await sleep(4000);
+
+      const balanceBefore = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      await releaseQueuedTransfer({ payer: executor, msgHash });
+      const balanceAfter = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      expect(balanceAfter - balanceBefore).toEqual(BigInt(amount.toString()));
+    } finally {
+      await setSettlementDelay({
+        admin,
+        threshold: new BN(0),
+        delay: new BN(0),
+      });
+    }
+  });
+
+  test("release to a blocked recipient", async () => {
+    await setSettlementDelay({ admin, threshold: amount, delay: new BN(3) });
+    try {
+      const msgHash = await receiveTransfer(receiver.publicKey, amount);
+      expect(
+        await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(msgHash))
+          .then((x) => x.status),
+      ).toEqual({ queued: {} });
+      const queuedTransfer = await EXAMPLE_TOKEN_PROGRAM.account
+        .queuedTransfer.fetch(findQueuedTransfer(msgHash));
+      expect(queuedTransfer.from).toEqual(sender.publicKey.toBuffer());
+
+      await sleep(4000);
+
+      // The recipient blocked while the transfer was queued gets the tokens
+      // escrowed on release.
+      await block({ admin, address: receiver.publicKey.toBuffer() });
+      await releaseQueuedTransfer({ payer: executor, msgHash }).finally(
+        () => unblock({ admin, address: receiver.publicKey.toBuffer() }),
+      );
+
+      expect(await connection.getAccountInfo(findQueuedTransfer(msgHash)))
+        .toBeNull();
+      const escrow = await EXAMPLE_TOKEN_PROGRAM.account.escrow.fetch(
+        findEscrow(msgHash),
+      );
+      expect(escrow.to).toEqual(receiver.publicKey.toBuffer());
+      expect(escrow.amount.eq(amount)).toBeTrue();
+
+      const receiverAta = getAssociatedTokenAddressSync(
+        EXA_MINT,
+        receiver.publicKey,
+      );
+      const balanceBefore = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      await claim({ recipient: receiver, msgHash, destination: receiverAta });
+      const balanceAfter = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      expect(balanceAfter - balanceBefore).toEqual(BigInt(amount.toString()));
+    } finally {
+      await setSettlementDelay({
+        admin,
+        threshold: new BN(0),
+        delay: new BN(0),
+      });
+    }
+  });
+
+  test("receive batch", async () => {
+    const destAddr = EXAMPLE_TOKEN_PROGRAM.programId.toBuffer();
+    const uipFee = new BN(80085);
+    const srcChainId = SOLANA_CHAIN_ID;
+    const recipients = [receiver.publicKey, referrer.publicKey];
+    let selector = new Array<number>();
+    let payload: Buffer = Buffer.alloc(0);
+
+    const eventPromise: Promise<void> = new Promise((resolve, reject) => {
+      UIP_PROGRAM.addEventListener(
+        "messageProposed",
+        (event) => {
+          selector = event.selector;
+          payload = event.payload;
+          resolve();
+        },
+      );
+
+      setTimeout(() => {
+        reject(new Error("Event did not fire within timeout"));
+      }, 15000);
+    });
+
+    const { transactionSignature } = await bridgeBatch({
+      uipFee,
+      customGasLimit,
+      destChainId,
+      sender,
+      recipients: recipients.map((address) => ({
+        to: { solana: { address } },
+        amount,
+      })),
+    });
+    const txId = bs58.decode(transactionSignature);
+
+    await eventPromise;
+
+    const msgData = {
+      initialProposal: {
+        senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+        destAddr: new PublicKey(destAddr),
+        totalFee: uipFee,
+        payload,
+        reserved: Buffer.from([]),
+        transmitterParams: transmitterParamsEncoded,
+        selector,
+      },
+      srcChainData: {
+        srcBlockNumber: new BN(randomInt(256)),
+        srcChainId,
+        srcOpTxId: [
+          Array.from(txId.subarray(0, 32)),
+          Array.from(txId.subarray(32)),
+        ],
+      },
+    };
+    const msgHash = msgHashFull(msgData);
+
+    const balances = () =>
+      Promise.all(
+        recipients.map((to) =>
+          getAccount(connection, getAssociatedTokenAddressSync(EXA_MINT, to))
+            .then((x) => x.amount)
+        ),
+      );
+    const balancesBefore = await balances();
+
+    await executeFull({
+      executor,
+      msgData,
+      signatures: [signMsg(signer, msgData)],
+      superSignatures: [signMsg(superSigner, msgData)],
+      accounts: batchAccounts(srcChainId, msgHash, recipients),
+      spendingLimit: new BN(3_000_000),
+    });
+
+    const balancesAfter = await balances();
+    for (const [i, balanceAfter] of balancesAfter.entries()) {
+      expect(balanceAfter - balancesBefore[i]).toEqual(
+        BigInt(amount.toString()),
+      );
     }
 
-    input.spendingLimit = new BN(3_000_000);
-    await executeFull(input);
+    const receipt = await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(
+      findReceipt(msgHash),
+    );
+    expect(receipt.recipient).toEqual(Buffer.alloc(0));
+    expect(receipt.amount.toString()).toEqual(amount.muln(2).toString());
+    expect(receipt.status).toEqual({ delivered: {} });
 
-    const exaBalanceAfter = await getAccount(
-      connection,
-      getAssociatedTokenAddressSync(
+    await unloadMessage({ payer: executor, message: findMessage(msgData) });
+  });
+
+  test("receive delayed batch", async () => {
+    const srcChainId = SOLANA_CHAIN_ID;
+    const recipients = [receiver.publicKey, referrer.publicKey];
+    // Each transfer is below the threshold, while their total isn't.
+    const half = amount.divn(2);
+    const msgData = {
+      initialProposal: {
+        senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+        destAddr: EXAMPLE_TOKEN_PROGRAM.programId,
+        totalFee: new BN(80085),
+        payload: Buffer.from(
+          AbiCoder.defaultAbiCoder().encode(
+            ["bytes", "tuple(bytes,uint256)[]"],
+            [
+              sender.publicKey.toBuffer(),
+              recipients.map((to) => [to.toBuffer(), half.toString()]),
+            ],
+          ).slice(2),
+          "hex",
+        ),
+        reserved: Buffer.from([]),
+        transmitterParams: transmitterParamsEncoded,
+        selector: Array.from(Buffer.alloc(32)),
+      },
+      srcChainData: {
+        srcBlockNumber: new BN(randomInt(256)),
+        srcChainId,
+        srcOpTxId: [
+          Array.from(Keypair.generate().publicKey.toBuffer()),
+          Array.from(Keypair.generate().publicKey.toBuffer()),
+        ],
+      },
+    };
+    const msgHash = msgHashFull(msgData);
+    const [released, cancelled] = recipients.map((_, i) =>
+      batchEscrowKey(msgHash, i)
+    );
+    const receiverAta = getAssociatedTokenAddressSync(
+      EXA_MINT,
+      receiver.publicKey,
+    );
+
+    await setSettlementDelay({ admin, threshold: amount, delay: new BN(3) });
+    try {
+      await executeFull({
+        executor,
+        msgData,
+        signatures: [signMsg(signer, msgData)],
+        superSignatures: [signMsg(superSigner, msgData)],
+        accounts: batchAccounts(srcChainId, msgHash, recipients),
+        spendingLimit: new BN(3_000_000),
+      });
+      await unloadMessage({ payer: executor, message: findMessage(msgData) });
+
+      expect(
+        await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(msgHash))
+          .then((x) => x.status),
+      ).toEqual({ queued: {} });
+      for (const [i, key] of [released, cancelled].entries()) {
+        const queuedTransfer = await EXAMPLE_TOKEN_PROGRAM.account
+          .queuedTransfer.fetch(findQueuedTransfer(key));
+        expect(queuedTransfer.to).toEqual(recipients[i]);
+        expect(queuedTransfer.amount.eq(half)).toBeTrue();
+        expect(queuedTransfer.msgHash).toEqual(Array.from(msgHash));
+      }
+
+      await expect(
+        releaseQueuedTransfer({ payer: executor, msgHash: released }),
+      ).rejects.toThrow("SettlementDelayNotElapsed");
+
+      await cancelQueuedTransfer({ authority: guardian, msgHash: cancelled });
+      expect(
+        await connection.getAccountInfo(findQueuedTransfer(cancelled)),
+      ).toBeNull();
+      expect(
+        await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(msgHash))
+          .then((x) => x.status),
+      ).toEqual({ cancelled: {} });
+
+      await sleep(4000);
+
+      const balanceBefore = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      await releaseQueuedTransfer({ payer: executor, msgHash: released });
+      const balanceAfter = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      expect(balanceAfter - balanceBefore).toEqual(BigInt(half.toString()));
+    } finally {
+      await setSettlementDelay({
+        admin,
+        threshold: new BN(0),
+        delay: new BN(0),
+      });
+    }
+  });
+
+  test("receive forwarded", async () => {
+    const srcChainId = SOLANA_CHAIN_ID;
+    const abi = AbiCoder.defaultAbiCoder();
+    const payload = Buffer.from(
+      abi.encode(["bytes", "bytes", "uint256", "uint128"], [
+        sender.publicKey.toBuffer(),
+        receiver.publicKey.toBuffer(),
+        amount.toString(),
+        destChainId.toString(),
+      ]).slice(2),
+      "hex",
+    );
+
+    const receiveForwarded = async () => {
+      const msgData = {
+        initialProposal: {
+          senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+          destAddr: EXAMPLE_TOKEN_PROGRAM.programId,
+          totalFee: new BN(80085),
+          payload,
+          reserved: Buffer.from([]),
+          transmitterParams: transmitterParamsEncoded,
+          selector: Array.from(Buffer.alloc(32)),
+        },
+        srcChainData: {
+          srcBlockNumber: new BN(randomInt(256)),
+          srcChainId,
+          srcOpTxId: [
+            Array.from(Keypair.generate().publicKey.toBuffer()),
+            Array.from(Keypair.generate().publicKey.toBuffer()),
+          ],
+        },
+      };
+      const msgHash = msgHashFull(msgData);
+
+      await executeFull({
+        executor,
+        msgData,
+        signatures: [signMsg(signer, msgData)],
+        superSignatures: [signMsg(superSigner, msgData)],
+        accounts: [
+          { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: false },
+          { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: true },
+          {
+            pubkey: findOrigin(destChainId),
+            isSigner: false,
+            isWritable: false,
+          },
+          {
+            pubkey: findPendingForward(msgHash),
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: findReceipt(msgHash), isSigner: false, isWritable: true },
+          {
+            pubkey: SystemProgram.programId,
+            isSigner: false,
+            isWritable: false,
+          },
+        ],
+        spendingLimit: new BN(5_000_000),
+      });
+      await unloadMessage({ payer: executor, message: findMessage(msgData) });
+
+      return msgHash;
+    };
+
+    await setForwardGasLimit({ admin, gasLimit: customGasLimit });
+    const msgHash = await receiveForwarded();
+
+    const receipt = await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(
+      findReceipt(msgHash),
+    );
+    expect(receipt.recipient).toEqual(receiver.publicKey.toBuffer());
+    expect(receipt.status).toEqual({ forwarded: {} });
+
+    const pendingForward = await EXAMPLE_TOKEN_PROGRAM.account.pendingForward
+      .fetch(findPendingForward(msgHash));
+    expect(pendingForward.destChainId.toString()).toEqual(
+      destChainId.toString(),
+    );
+    expect(pendingForward.from).toEqual(sender.publicKey.toBuffer());
+    expect(pendingForward.amount.toString()).toEqual(amount.toString());
+
+    await expect(forward({ payer: executor, msgHash, uipFee })).rejects.toThrow(
+      "ForwardFeeVaultDepleted",
+    );
+
+    await transfer(connection, payer, FORWARD_FEE_VAULT, 10_000_000);
+
+    let proposedPayload: Buffer = Buffer.alloc(0);
+    const eventPromise: Promise<void> = new Promise((resolve, reject) => {
+      UIP_PROGRAM.addEventListener("messageProposed", (event) => {
+        proposedPayload = event.payload;
+        resolve();
+      });
+
+      setTimeout(() => {
+        reject(new Error("Event did not fire within timeout"));
+      }, 15000);
+    });
+
+    const vaultBalanceBefore = await connection.getBalance(FORWARD_FEE_VAULT);
+    await forward({ payer: executor, msgHash, uipFee });
+    await eventPromise;
+
+    // The original sender and recipient are preserved in the onward payload.
+    const [from, to, forwardedAmount] = abi.decode(
+      ["bytes", "bytes", "uint256"],
+      proposedPayload,
+    );
+    expect(Buffer.from(from.slice(2), "hex")).toEqual(
+      sender.publicKey.toBuffer(),
+    );
+    expect(Buffer.from(to.slice(2), "hex")).toEqual(
+      receiver.publicKey.toBuffer(),
+    );
+    expect(forwardedAmount.toString()).toEqual(amount.toString());
+
+    const vaultBalanceAfter = await connection.getBalance(FORWARD_FEE_VAULT);
+    expect(vaultBalanceBefore - vaultBalanceAfter).toBeGreaterThan(0);
+    expect(
+      await connection.getAccountInfo(findPendingForward(msgHash)),
+    ).toBeNull();
+
+    await withdrawForwardFees({
+      admin,
+      destination: payer.publicKey,
+      amount: new BN(vaultBalanceAfter),
+    });
+    expect(await connection.getBalance(FORWARD_FEE_VAULT)).toEqual(0);
+
+    // A forward that can't be proposed is escrowed without a recipient on
+    // Solana, so only the admin can recover it.
+    const cancelled = await receiveForwarded();
+    await expect(cancelForward({ authority: sender, msgHash: cancelled }))
+      .rejects.toThrow("NotGuardian");
+    await cancelForward({ authority: admin, msgHash: cancelled });
+    expect(
+      await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(cancelled))
+        .then((x) => x.status),
+    ).toEqual({ escrowed: {} });
+    const escrow = await EXAMPLE_TOKEN_PROGRAM.account.escrow.fetch(
+      findEscrow(cancelled),
+    );
+    expect(escrow.to).toEqual(Buffer.alloc(0));
+    expect(escrow.amount.toString()).toEqual(amount.toString());
+    await recoverEscrow({
+      admin,
+      msgHash: cancelled,
+      destination: getAssociatedTokenAddressSync(EXA_MINT, sender.publicKey),
+    });
+  });
+
+  test("receive delayed", async () => {
+    const srcChainId = SOLANA_CHAIN_ID;
+    const abi = AbiCoder.defaultAbiCoder();
+    const receiverAta = getAssociatedTokenAddressSync(
+      EXA_MINT,
+      receiver.publicKey,
+    );
+
+    const receiveLarge = async () => {
+      const msgData = {
+        initialProposal: {
+          senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+          destAddr: EXAMPLE_TOKEN_PROGRAM.programId,
+          totalFee: new BN(80085),
+          payload: Buffer.from(
+            abi.encode(["bytes", "bytes", "uint256"], [
+              sender.publicKey.toBuffer(),
+              receiver.publicKey.toBuffer(),
+              amount.toString(),
+            ]).slice(2),
+            "hex",
+          ),
+          reserved: Buffer.from([]),
+          transmitterParams: transmitterParamsEncoded,
+          selector: Array.from(Buffer.alloc(32)),
+        },
+        srcChainData: {
+          srcBlockNumber: new BN(randomInt(256)),
+          srcChainId,
+          srcOpTxId: [
+            Array.from(Keypair.generate().publicKey.toBuffer()),
+            Array.from(Keypair.generate().publicKey.toBuffer()),
+          ],
+        },
+      };
+      const msgHash = msgHashFull(msgData);
+
+      await executeFull({
+        executor,
+        msgData,
+        signatures: [signMsg(signer, msgData)],
+        superSignatures: [signMsg(superSigner, msgData)],
+        accounts: [
+          { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: true },
+          { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: true },
+          {
+            pubkey: findQueuedTransfer(msgHash),
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: findEscrow(msgHash), isSigner: false, isWritable: true },
+          {
+            pubkey: findPendingForward(msgHash),
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: findReceipt(msgHash), isSigner: false, isWritable: true },
+          { pubkey: EXA_MINT, isSigner: false, isWritable: true },
+          {
+            pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+            isSigner: false,
+            isWritable: false,
+          },
+          { pubkey: ESCROW_AUTHORITY, isSigner: false, isWritable: false },
+          {
+            pubkey: getAssociatedTokenAddressSync(
+              EXA_MINT,
+              ESCROW_AUTHORITY,
+              true,
+            ),
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: receiverAta, isSigner: false, isWritable: true },
+          { pubkey: receiver.publicKey, isSigner: false, isWritable: false },
+          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+          {
+            pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+            isSigner: false,
+            isWritable: false,
+          },
+          {
+            pubkey: SystemProgram.programId,
+            isSigner: false,
+            isWritable: false,
+          },
+          { pubkey: BLOCKLIST, isSigner: false, isWritable: false },
+          {
+            pubkey: findAttestation(receiver.publicKey),
+            isSigner: false,
+            isWritable: false,
+          },
+        ],
+        spendingLimit: new BN(3_000_000),
+      });
+      await unloadMessage({ payer: executor, message: findMessage(msgData) });
+
+      return msgHash;
+    };
+
+    await setSettlementDelay({ admin, threshold: amount, delay: new BN(3) });
+
+    const released = await receiveLarge();
+    const cancelled = await receiveLarge();
+
+    const receipt = await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(
+      findReceipt(released),
+    );
+    expect(receipt.status).toEqual({ queued: {} });
+    const queuedTransfer = await EXAMPLE_TOKEN_PROGRAM.account.queuedTransfer
+      .fetch(findQueuedTransfer(released));
+    expect(queuedTransfer.to).toEqual(receiver.publicKey);
+    expect(queuedTransfer.releaseAfter.toNumber()).toBeGreaterThan(0);
+
+    await expect(
+      releaseQueuedTransfer({ payer: executor, msgHash: released }),
+    ).rejects.toThrow("SettlementDelayNotElapsed");
+
+    await expect(
+      cancelQueuedTransfer({ authority: sender, msgHash: cancelled }),
+    ).rejects.toThrow("NotGuardian");
+    await cancelQueuedTransfer({ authority: guardian, msgHash: cancelled });
+    expect(
+      await connection.getAccountInfo(findQueuedTransfer(cancelled)),
+    ).toBeNull();
+    expect(
+      await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(cancelled))
+        .then((x) => x.status),
+    ).toEqual({ cancelled: {} });
+
+    await sleep(4000);
+
+    const balanceBefore = await getAccount(connection, receiverAta).then((x) =>
+      x.amount
+    );
+    await releaseQueuedTransfer({ payer: executor, msgHash: released });
+    const balanceAfter = await getAccount(connection, receiverAta).then((x) =>
+      x.amount
+    );
+    expect(balanceAfter - balanceBefore).toEqual(BigInt(amount.toString()));
+
+    await setSettlementDelay({ admin, threshold: new BN(0), delay: new BN(0) });
+  });
+
+  test("receive with dust", async () => {
+    const { decimals } = await getMint(connection, EXA_MINT);
+    const setSolanaDecimals = (decimals: number) =>
+      setOrigin({
+        authority: admin,
+        payer,
+        chainId: SOLANA_CHAIN_ID,
+        address: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+        enabled: true,
+        decimals,
+      });
+
+    await setSolanaDecimals(decimals + 3);
+    try {
+      // The last three digits can't be represented locally, so they're
+      // rounded down rather than failing the message for good.
+      const receiverAta = getAssociatedTokenAddressSync(
         EXA_MINT,
         receiver.publicKey,
-      ),
-    ).then((x) => x.amount);
-    expect(exaBalanceAfter - exaBalanceBefore).toEqual(
-      BigInt(amount.toString()),
+      );
+      const balanceBefore = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      await receiveTransfer(receiver.publicKey, new BN(1_000_999));
+      const balanceAfter = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      expect(balanceAfter - balanceBefore).toEqual(1000n);
+    } finally {
+      await setSolanaDecimals(decimals);
+    }
+
+    // An amount that doesn't fit into u64 locally is rejected, and the
+    // message is executed without delivering anything.
+    const msgHash = await receiveTransfer(
+      receiver.publicKey,
+      new BN(2).pow(new BN(64)),
     );
+    const receipt = await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(
+      findReceipt(msgHash),
+    );
+    expect(receipt.status).toEqual({ rejected: {} });
+    expect(receipt.amount.isZero()).toBeTrue();
+  });
 
-    await unloadMessage({ payer: executor, message: findMessage(msgData) });
+  test("receive above inbound capacity", async () => {
+    const receiverAta = getAssociatedTokenAddressSync(
+      EXA_MINT,
+      receiver.publicKey,
+    );
+    const limits = {
+      outboundCapacity: new BN(0),
+      outboundWindow: new BN(0),
+      inboundCapacity: new BN(1000),
+      inboundWindow: new BN(10),
+      minAmount: new BN(0),
+      maxAmount: new BN(0),
+      userDailyCap: new BN(0),
+    };
+    await setLimits({ authority: admin, chainId: SOLANA_CHAIN_ID, limits });
 
-    const balanceAfter = await connection.getBalance(executor.publicKey);
+    try {
+      await receiveTransfer(receiver.publicKey, new BN(500));
+      const msgHash = await receiveTransfer(receiver.publicKey, new BN(3000));
+      expect(
+        await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(msgHash))
+          .then((x) => x.status),
+      ).toEqual({ queued: {} });
 
-    expect(balanceBefore - balanceAfter).toEqual(
-      Number(liteSimulationLamports) + 5000,
+      // A transfer above the capacity waits for the limit to refill fully.
+      await expect(
+        releaseQueuedTransfer({ payer: executor, msgHash }),
+      ).rejects.toThrow("InboundRateLimitExceeded");
+
+      await sleep(11000);
+
+      const balanceBefore = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      await releaseQueuedTransfer({ payer: executor, msgHash });
+      const balanceAfter = await getAccount(connection, receiverAta)
+        .then((x) => x.amount);
+      expect(balanceAfter - balanceBefore).toEqual(3000n);
+
+      const origin = await EXAMPLE_TOKEN_PROGRAM.account.origin.fetch(
+        findOrigin(SOLANA_CHAIN_ID),
+      );
+      expect(origin.inboundLimit.available.toNumber()).toBeLessThan(1000);
+    } finally {
+      await setLimits({
+        authority: admin,
+        chainId: SOLANA_CHAIN_ID,
+        limits: { ...limits, inboundCapacity: new BN(0), inboundWindow: new BN(0) },
+      });
+    }
+  });
+
+  test("refund", async () => {
+    const srcChainId = SOLANA_CHAIN_ID;
+    const abi = AbiCoder.defaultAbiCoder();
+    const senderAta = getAssociatedTokenAddressSync(EXA_MINT, sender.publicKey);
+
+    const receive = async (
+      payload: string,
+      accounts: (msgHash: Buffer) => AccountMeta[],
+    ) => {
+      const msgData = {
+        initialProposal: {
+          senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+          destAddr: EXAMPLE_TOKEN_PROGRAM.programId,
+          totalFee: new BN(80085),
+          payload: Buffer.from(payload.slice(2), "hex"),
+          reserved: Buffer.from([]),
+          transmitterParams: transmitterParamsEncoded,
+          selector: Array.from(Buffer.alloc(32)),
+        },
+        srcChainData: {
+          srcBlockNumber: new BN(randomInt(256)),
+          srcChainId,
+          srcOpTxId: [
+            Array.from(Keypair.generate().publicKey.toBuffer()),
+            Array.from(Keypair.generate().publicKey.toBuffer()),
+          ],
+        },
+      };
+      const msgHash = msgHashFull(msgData);
+
+      await executeFull({
+        executor,
+        msgData,
+        signatures: [signMsg(signer, msgData)],
+        superSignatures: [signMsg(superSigner, msgData)],
+        accounts: accounts(msgHash),
+        spendingLimit: new BN(5_000_000),
+      });
+      await unloadMessage({ payer: executor, message: findMessage(msgData) });
+
+      return msgHash;
+    };
+
+    await setRefundable({
+      authority: admin,
+      chainId: destChainId,
+      refundable: true,
+      refundWindow: new BN(3),
+    });
+
+    const { nextTransferNonce: nonce } = await EXAMPLE_TOKEN_PROGRAM.account
+      .exampleTokenConfig.fetch(EXAMPLE_TOKEN_CONFIG);
+
+    let proposedPayload: Buffer = Buffer.alloc(0);
+    const bridgeEventPromise: Promise<void> = new Promise((resolve, reject) => {
+      UIP_PROGRAM.addEventListener("messageProposed", (event) => {
+        proposedPayload = event.payload;
+        resolve();
+      });
+
+      setTimeout(() => {
+        reject(new Error("Event did not fire within timeout"));
+      }, 15000);
+    });
+
+    await bridge({
+      destChainId,
+      uipFee,
+      customGasLimit,
+      sender,
+      amount,
+      to: { solana: { address: receiver.publicKey } },
+    });
+    await bridgeEventPromise;
+
+    const outboundTransfer = await EXAMPLE_TOKEN_PROGRAM.account
+      .outboundTransfer.fetch(findOutboundTransfer(nonce));
+    expect(outboundTransfer.sender).toEqual(sender.publicKey);
+    expect(outboundTransfer.amount.toString()).toEqual(amount.toString());
+
+    // The transfer carries its nonce to be referenced by the refund.
+    const [proposedNonce, [from, , bridgedAmount]] = abi.decode(
+      ["uint64", "tuple(bytes, bytes, uint256)"],
+      proposedPayload,
+    );
+    expect(proposedNonce.toString()).toEqual(nonce.toString());
+    expect(Buffer.from(from.slice(2), "hex")).toEqual(
+      sender.publicKey.toBuffer(),
+    );
+    expect(bridgedAmount.toString()).toEqual(amount.toString());
+
+    const refundAccounts = (msgHash: Buffer) => [
+      { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: true },
+      { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: true },
+      {
+        pubkey: findOutboundTransfer(nonce),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: findReceipt(msgHash), isSigner: false, isWritable: true },
+      { pubkey: EXA_MINT, isSigner: false, isWritable: true },
+      {
+        pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: senderAta, isSigner: false, isWritable: true },
+      { pubkey: sender.publicKey, isSigner: false, isWritable: true },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ];
+    const refundPayload = (refundAmount: BN) =>
+      abi.encode(["uint64", "tuple(bytes, uint256)"], [
+        nonce.toString(),
+        [sender.publicKey.toBuffer(), refundAmount.toString()],
+      ]);
+
+    await expect(
+      receive(refundPayload(amount.addn(1)), refundAccounts),
+    ).rejects.toThrow("RefundExceedsTransfer");
+
+    const balanceBefore = await getAccount(connection, senderAta).then((x) =>
+      x.amount
+    );
+    const refunded = await receive(refundPayload(amount), refundAccounts);
+    const balanceAfter = await getAccount(connection, senderAta).then((x) =>
+      x.amount
+    );
+    expect(balanceAfter - balanceBefore).toEqual(BigInt(amount.toString()));
+    expect(
+      await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(refunded))
+        .then((x) => x.status),
+    ).toEqual({ refunded: {} });
+    expect(
+      await connection.getAccountInfo(findOutboundTransfer(nonce)),
+    ).toBeNull();
+
+    // The record is closed, so the refund can't be replayed.
+    await expect(receive(refundPayload(amount), refundAccounts)).rejects
+      .toThrow("AccountNotInitialized");
+
+    // A refundable transfer that can't be delivered on Solana is bounced.
+    const bounced = await receive(
+      abi.encode(["uint64", "tuple(bytes, bytes, uint256)"], [
+        nonce.toString(),
+        [sender.publicKey.toBuffer(), Buffer.alloc(20, 1), amount.toString()],
+      ]),
+      (msgHash) => [
+        { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: true },
+        { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: true },
+        {
+          pubkey: findQueuedTransfer(msgHash),
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: findEscrow(msgHash), isSigner: false, isWritable: true },
+        {
+          pubkey: findPendingForward(msgHash),
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: findReceipt(msgHash), isSigner: false, isWritable: true },
+        { pubkey: EXA_MINT, isSigner: false, isWritable: true },
+        {
+          pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+          isSigner: false,
+          isWritable: false,
+        },
+        { pubkey: ESCROW_AUTHORITY, isSigner: false, isWritable: false },
+        {
+          pubkey: getAssociatedTokenAddressSync(
+            EXA_MINT,
+            ESCROW_AUTHORITY,
+            true,
+          ),
+          isSigner: false,
+          isWritable: true,
+        },
+        {
+          pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+          isSigner: false,
+          isWritable: false,
+        },
+        {
+          pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+          isSigner: false,
+          isWritable: false,
+        },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        {
+          pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+          isSigner: false,
+          isWritable: false,
+        },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: BLOCKLIST, isSigner: false, isWritable: false },
+        {
+          pubkey: EXAMPLE_TOKEN_PROGRAM.programId,
+          isSigner: false,
+          isWritable: false,
+        },
+      ],
+    );
+    expect(
+      await EXAMPLE_TOKEN_PROGRAM.account.receipt.fetch(findReceipt(bounced))
+        .then((x) => x.status),
+    ).toEqual({ bounced: {} });
+    const pendingBounce = await EXAMPLE_TOKEN_PROGRAM.account.pendingForward
+      .fetch(findPendingForward(bounced));
+    expect(pendingBounce.refundNonce.toString()).toEqual(nonce.toString());
+
+    await transfer(connection, payer, FORWARD_FEE_VAULT, 10_000_000);
+
+    const bounceEventPromise: Promise<void> = new Promise((resolve, reject) => {
+      UIP_PROGRAM.addEventListener("messageProposed", (event) => {
+        proposedPayload = event.payload;
+        resolve();
+      });
+
+      setTimeout(() => {
+        reject(new Error("Event did not fire within timeout"));
+      }, 15000);
+    });
+    await forward({ payer: executor, msgHash: bounced, uipFee });
+    await bounceEventPromise;
+
+    // The refund names the original sender, who gets the tokens back.
+    const [refundNonce, [refundFrom, refundAmount]] = abi.decode(
+      ["uint64", "tuple(bytes, uint256)"],
+      proposedPayload,
+    );
+    expect(refundNonce.toString()).toEqual(nonce.toString());
+    expect(Buffer.from(refundFrom.slice(2), "hex")).toEqual(
+      sender.publicKey.toBuffer(),
+    );
+    expect(refundAmount.toString()).toEqual(amount.toString());
+
+    await withdrawForwardFees({
+      admin,
+      destination: payer.publicKey,
+      amount: new BN(await connection.getBalance(FORWARD_FEE_VAULT)),
+    });
+
+    // A sender gives up the refund of a delivered transfer to get the rent
+    // back once the refund window has elapsed.
+    const { nextTransferNonce: closed } = await EXAMPLE_TOKEN_PROGRAM.account
+      .exampleTokenConfig.fetch(EXAMPLE_TOKEN_CONFIG);
+    await bridge({
+      destChainId,
+      uipFee,
+      customGasLimit,
+      sender,
+      amount,
+      to: { solana: { address: receiver.publicKey } },
+    });
+    await expect(closeOutboundTransfer({ sender, nonce: closed })).rejects
+      .toThrow("RefundWindowNotElapsed");
+
+    await sleep(4000);
+
+    await closeOutboundTransfer({ sender, nonce: closed });
+    expect(
+      await connection.getAccountInfo(findOutboundTransfer(closed)),
+    ).toBeNull();
+
+    await setRefundable({
+      authority: admin,
+      chainId: destChainId,
+      refundable: false,
+      refundWindow: new BN(0),
+    });
+  });
+
+  test("reconcile supply", async () => {
+    const origin = await EXAMPLE_TOKEN_PROGRAM.account.origin.fetch(
+      findOrigin(SOLANA_CHAIN_ID),
+    );
+    expect(origin.totalOutbound.gtn(0)).toBeTrue();
+    expect(origin.totalInbound.lte(origin.totalOutbound)).toBeTrue();
+
+    await reconcileSupply([SOLANA_CHAIN_ID]);
+  });
+
+  test("update metadata", async () => {
+    const metadata = {
+      name: "Example Token",
+      symbol: "EXA",
+      uri: "https://example.com/exa.json",
+    };
+    await updateMetadata({ admin, metadata });
+
+    const account = await connection.getAccountInfo(
+      findMetaplexMetadata(EXA_MINT),
     );
+    expect(account.data.includes(Buffer.from(metadata.uri))).toBeTrue();
+
+    await expect(
+      updateMetadata({ admin: sender, metadata }),
+    ).rejects.toThrow("ConstraintHasOne");
   });
 
   test("update admin", async () => {
@@ -0,0 +1,333 @@
+import {
+  encodeTransmitterParams,
+  executeFull,
+  findMessage,
+  msgHashFull,
+  signMsg,
+  UIP_PROGRAM,
+  unloadMessage,
+} from "../helpers/endpoint";
+import {
+  BLOCKLIST,
+  bridge,
+  ESCROW_AUTHORITY,
+  EXAMPLE_TOKEN_CONFIG,
+  EXAMPLE_TOKEN_PROGRAM,
+  findAttestation,
+  findEscrow,
+  findOrigin,
+  findPendingForward,
+  findQueuedTransfer,
+  findReceipt,
+  initializeLockRelease,
+  registerExtension,
+  setOrigin,
+} from "../helpers/exampleToken";
+import {
+  Keypair,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import { afterAll, beforeAll, describe, expect, test } from "bun:test";
+import {
+  disperse,
+  POLYGON_AMOY_CHAIN_ID,
+  readKeypairFromFile,
+  setupTests,
+  SOLANA_CHAIN_ID,
+  transferEverything,
+} from "../helpers/utils";
+import BN from "bn.js";
+import { AbiCoder, Wallet } from "ethers";
+import { randomInt } from "crypto";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createInitializeMint2Instruction,
+  createInitializeTransferHookInstruction,
+  createMint,
+  ExtensionType,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+// The config is a singleton, so this suite needs a validator on which the
+// program hasn't been initialized yet, see the README.
+
+const admin = readKeypairFromFile("keys/admin.json");
+const executor = readKeypairFromFile("keys/executor.json");
+const sender = new Keypair();
+const receiver = new Keypair();
+const signer = new Wallet(
+  "0x74e3ffad2b87174dc1d806edf1a01e3b017cf1be05d1894d329826f10fa1d72f",
+);
+const superSigner = new Wallet(
+  "0xf496bcca0a4896011dbdbe2ec80417ed759a6a9cc72477b3a65b8d99b066b150",
+);
+const transmitterParamsEncoded = encodeTransmitterParams({
+  proposalCommitment: { confirmed: {} },
+  customGasLimit: new BN(2),
+});
+
+const { connection, payer } = setupTests();
+
+const uipFee = new BN(80085);
+const customGasLimit = new BN(1_000_000);
+const amount = new BN(1_000_000);
+let mint: PublicKey;
+let vault: PublicKey;
+
+beforeAll(async () => {
+  await disperse(
+    connection,
+    [admin.publicKey, executor.publicKey, sender.publicKey],
+    payer,
+    150_000_000,
+  );
+
+  mint = await createMint(connection, payer, payer.publicKey, null, 9);
+  vault = getAssociatedTokenAddressSync(mint, EXAMPLE_TOKEN_CONFIG, true);
+  const senderAta = await getOrCreateAssociatedTokenAccount(
+    connection,
+    payer,
+    mint,
+    sender.publicKey,
+  );
+  await mintTo(
+    connection,
+    payer,
+    mint,
+    senderAta.address,
+    payer,
+    BigInt(amount.muln(2).toString()),
+  );
+});
+
+afterAll(async () => {
+  await transferEverything(connection, [executor, sender], payer);
+});
+
+/** Bridges `amount` tokens of the sender to the receiver on Solana itself. */
+async function bridgeOut(): Promise<Buffer> {
+  let payload: Buffer = Buffer.alloc(0);
+  const eventPromise: Promise<void> = new Promise((resolve, reject) => {
+    UIP_PROGRAM.addEventListener("messageProposed", (event) => {
+      payload = event.payload;
+      resolve();
+    });
+
+    setTimeout(() => {
+      reject(new Error("Event did not fire within timeout"));
+    }, 15000);
+  });
+
+  await bridge({
+    destChainId: SOLANA_CHAIN_ID,
+    uipFee,
+    customGasLimit,
+    sender,
+    amount,
+    to: { solana: { address: receiver.publicKey } },
+  });
+  await eventPromise;
+
+  return payload;
+}
+
+/** Executes the transfer `payload` as if it came back from Solana. */
+async function receive(payload: Buffer): Promise<void> {
+  const srcChainId = SOLANA_CHAIN_ID;
+  const msgData = {
+    initialProposal: {
+      senderAddr: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+      destAddr: EXAMPLE_TOKEN_PROGRAM.programId,
+      totalFee: uipFee,
+      payload,
+      reserved: Buffer.from([]),
+      transmitterParams: transmitterParamsEncoded,
+      selector: Array.from(Buffer.alloc(32)),
+    },
+    srcChainData: {
+      srcBlockNumber: new BN(randomInt(256)),
+      srcChainId,
+      srcOpTxId: [
+        Array.from(Keypair.generate().publicKey.toBuffer()),
+        Array.from(Keypair.generate().publicKey.toBuffer()),
+      ],
+    },
+  };
+  const msgHash = msgHashFull(msgData);
+
+  await executeFull({
+    executor,
+    msgData,
+    signatures: [signMsg(signer, msgData)],
+    superSignatures: [signMsg(superSigner, msgData)],
+    accounts: [
+      { pubkey: EXAMPLE_TOKEN_CONFIG, isSigner: false, isWritable: true },
+      { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: true },
+      {
+        pubkey: findQueuedTransfer(msgHash),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: findEscrow(msgHash), isSigner: false, isWritable: true },
+      { pubkey: findPendingForward(msgHash), isSigner: false, isWritable: true },
+      { pubkey: findReceipt(msgHash), isSigner: false, isWritable: true },
+      { pubkey: mint, isSigner: false, isWritable: true },
+      { pubkey: vault, isSigner: false, isWritable: true },
+      { pubkey: ESCROW_AUTHORITY, isSigner: false, isWritable: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(mint, ESCROW_AUTHORITY, true),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: getAssociatedTokenAddressSync(mint, receiver.publicKey),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: receiver.publicKey, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: BLOCKLIST, isSigner: false, isWritable: false },
+      {
+        pubkey: findAttestation(receiver.publicKey),
+        isSigner: false,
+        isWritable: false,
+      },
+    ],
+    spendingLimit: new BN(3_000_000),
+  });
+  await unloadMessage({ payer: executor, message: findMessage(msgData) });
+}
+
+const fetchVaultBalance = () =>
+  EXAMPLE_TOKEN_PROGRAM.account.exampleTokenConfig.fetch(EXAMPLE_TOKEN_CONFIG)
+    .then((x) => x.vaultBalance);
+
+describe("example token lock/release", () => {
+  test("initialize with a transfer hook", async () => {
+    const hookMint = Keypair.generate();
+    const space = getMintLen([ExtensionType.TransferHook]);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: payer.publicKey,
+          newAccountPubkey: hookMint.publicKey,
+          space,
+          lamports: await connection.getMinimumBalanceForRentExemption(space),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferHookInstruction(
+          hookMint.publicKey,
+          payer.publicKey,
+          Keypair.generate().publicKey,
+          TOKEN_2022_PROGRAM_ID,
+        ),
+        createInitializeMint2Instruction(
+          hookMint.publicKey,
+          9,
+          payer.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID,
+        ),
+      ),
+      [payer, hookMint],
+    );
+
+    await expect(
+      initializeLockRelease({
+        payer,
+        admin: admin.publicKey,
+        mint: hookMint.publicKey,
+      }),
+    ).rejects.toThrow("TransferHookNotSupported");
+  });
+
+  test("initialize", async () => {
+    await expect(
+      initializeLockRelease({
+        payer,
+        admin: admin.publicKey,
+        mint,
+        chainId: POLYGON_AMOY_CHAIN_ID,
+      }),
+    ).rejects.toThrow("NotSolanaChain");
+
+    await initializeLockRelease({ payer, admin: admin.publicKey, mint });
+
+    const config = await EXAMPLE_TOKEN_PROGRAM.account.exampleTokenConfig
+      .fetch(EXAMPLE_TOKEN_CONFIG);
+    expect(config.lockRelease).toBeTrue();
+    expect(config.mint).toEqual(mint);
+    expect(config.vaultBalance.isZero()).toBeTrue();
+
+    await registerExtension({
+      authority: admin,
+      payer,
+      ipfsCid: "bafkreihwalovxt6jjsko7gws4m34frlqoqi5huqd5x3lacjrqyyzxm3tuy",
+    });
+    await setOrigin({
+      authority: admin,
+      payer,
+      chainId: SOLANA_CHAIN_ID,
+      address: EXAMPLE_TOKEN_PROGRAM.programId.toBuffer(),
+      enabled: true,
+      decimals: 9,
+    });
+  });
+
+  test("bridge and receive back", async () => {
+    const payload = await bridgeOut();
+
+    expect(await getAccount(connection, vault).then((x) => x.amount))
+      .toEqual(BigInt(amount.toString()));
+    expect((await fetchVaultBalance()).eq(amount)).toBeTrue();
+
+    await receive(payload);
+
+    const receiverAta = getAssociatedTokenAddressSync(
+      mint,
+      receiver.publicKey,
+    );
+    expect(await getAccount(connection, receiverAta).then((x) => x.amount))
+      .toEqual(BigInt(amount.toString()));
+    expect(await getAccount(connection, vault).then((x) => x.amount))
+      .toEqual(0n);
+    expect((await fetchVaultBalance()).isZero()).toBeTrue();
+  });
+
+  test("release above the locked amount", async () => {
+    await bridgeOut();
+    expect((await fetchVaultBalance()).eq(amount)).toBeTrue();
+
+    const payload = Buffer.from(
+      AbiCoder.defaultAbiCoder().encode(["bytes", "bytes", "uint256"], [
+        sender.publicKey.toBuffer(),
+        receiver.publicKey.toBuffer(),
+        amount.muln(2).toString(),
+      ]).slice(2),
+      "hex",
+    );
+    await expect(receive(payload)).rejects.toThrow(
+      "InsufficientVaultBalance",
+    );
+
+    expect(await getAccount(connection, vault).then((x) => x.amount))
+      .toEqual(BigInt(amount.toString()));
+    expect((await fetchVaultBalance()).eq(amount)).toBeTrue();
+  });
+});
@@ -0,0 +1,97 @@
+use example_nft::payload::NftTransfer;
+use solana_program::{instruction::AccountMeta, pubkey, pubkey::Pubkey, system_program};
+use uip_solana_sdk::{deserialize_message_data, MessageDataRef};
+
+#[repr(C)]
+pub struct InstructionInfo {
+    pub compute_units: u32,
+    pub heap_frame: u32,
+    pub accounts_len: u32,
+    pub accounts: [AccountMeta; 32],
+}
+
+/// The interface version supported by the extension.
+#[no_mangle]
+pub extern "C" fn get_api_version() -> u32 {
+    0
+}
+
+/// Populates `result` with the required compute units, heap frame and account
+/// metadata based on the provided serialized message data.
+///
+/// # Safety
+///
+/// The caller must ensure that `msg_data_ptr` points to a valid array of
+/// `msg_data_len` initialized bytes.
+#[no_mangle]
+pub unsafe extern "C" fn get_instruction_info(
+    msg_data_ptr: *const u8,
+    msg_data_len: usize,
+    result: &mut InstructionInfo,
+) {
+    let msg_data = core::slice::from_raw_parts(msg_data_ptr, msg_data_len);
+    let MessageDataRef {
+        payload,
+        src_chain_id,
+        ..
+    } = deserialize_message_data(msg_data).unwrap();
+
+    let transfer = NftTransfer::decode(payload).unwrap();
+
+    let to: Pubkey = (&transfer.to as &[u8]).try_into().unwrap();
+    let program_id: Pubkey = example_nft::ID.to_bytes().into();
+
+    let (config_pda, _) = Pubkey::find_program_address(&[b"CONFIG"], &program_id);
+    result.accounts[0] = AccountMeta::new_readonly(config_pda, false);
+
+    let (origin_pda, _) =
+        Pubkey::find_program_address(&[b"ORIGIN", &src_chain_id.to_le_bytes()], &program_id);
+    result.accounts[1] = AccountMeta::new_readonly(origin_pda, false);
+
+    // Both the copy minted by the bridge and the Solana NFT locked in the vault
+    // are passed, since the program decides which one is delivered.
+    let (copy_mint, _) =
+        Pubkey::find_program_address(&[b"NFT_MINT", &transfer.token_id], &program_id);
+    result.accounts[2] = AccountMeta::new(copy_mint, false);
+    result.accounts[3] = AccountMeta::new(find_metadata(&copy_mint), false);
+    result.accounts[4] = AccountMeta::new(find_ata(&to, &copy_mint), false);
+
+    let native_mint = Pubkey::new_from_array(transfer.token_id);
+    result.accounts[5] = AccountMeta::new_readonly(native_mint, false);
+    result.accounts[6] = AccountMeta::new(find_ata(&config_pda, &native_mint), false);
+    result.accounts[7] = AccountMeta::new(find_ata(&to, &native_mint), false);
+
+    result.accounts[8] = AccountMeta::new_readonly(to, false);
+    result.accounts[9] = AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false);
+    result.accounts[10] = AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false);
+    result.accounts[11] = AccountMeta::new_readonly(METAPLEX_PROGRAM_ID, false);
+    result.accounts[12] = AccountMeta::new_readonly(system_program::ID, false);
+
+    result.accounts_len = 13;
+    result.compute_units = 150_000;
+    result.heap_frame = 0;
+}
+
+const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+const METAPLEX_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+fn find_ata(wallet_address: &Pubkey, token_mint_address: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            &wallet_address.to_bytes(),
+            &TOKEN_PROGRAM_ID.to_bytes(),
+            &token_mint_address.to_bytes(),
+        ],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+fn find_metadata(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", METAPLEX_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &METAPLEX_PROGRAM_ID,
+    )
+    .0
+}
@@ -0,0 +1,216 @@
+import { IdlTypes, Program } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, TransactionSignature } from "@solana/web3.js";
+import { ExampleNft } from "../target/types/example_nft";
+import BN from "bn.js";
+import { ENDPOINT_CONFIG, fetchUtsConnector, findExtension } from "./endpoint";
+import { CID } from "multiformats";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+
+anchor.setProvider(anchor.AnchorProvider.env());
+export const EXAMPLE_NFT_PROGRAM: Program<ExampleNft> =
+  anchor.workspace.ExampleNft;
+
+export const EXAMPLE_NFT_CONFIG = PublicKey.findProgramAddressSync(
+  [Buffer.from("CONFIG")],
+  EXAMPLE_NFT_PROGRAM.programId,
+)[0];
+
+export const METAPLEX_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+);
+
+export const findOrigin = (chainId: BN) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("ORIGIN"), chainId.toArrayLike(Buffer, "le", 16)],
+    EXAMPLE_NFT_PROGRAM.programId,
+  )[0];
+
+/** Finds the mint of the copy of the NFT `tokenId` minted by the bridge. */
+export const findCopyMint = (tokenId: Buffer) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("NFT_MINT"), tokenId],
+    EXAMPLE_NFT_PROGRAM.programId,
+  )[0];
+
+export const findMetadata = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), METAPLEX_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    METAPLEX_PROGRAM_ID,
+  )[0];
+
+export type InitializeInput = {
+  payer: Keypair;
+  admin: PublicKey;
+};
+
+export async function initialize(
+  { payer, admin }: InitializeInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_NFT_PROGRAM.methods
+    .initialize(admin)
+    .accounts({
+      payer: payer.publicKey,
+    })
+    .signers([payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type MintInput = {
+  admin: Keypair;
+  payer: Keypair;
+  owner: PublicKey;
+  name: string;
+  symbol: string;
+  uri: string;
+};
+
+export async function mint(
+  { admin, payer, owner, name, symbol, uri }: MintInput,
+): Promise<{ transactionSignature: TransactionSignature; mint: PublicKey }> {
+  const mint = new Keypair();
+  const transactionSignature = await EXAMPLE_NFT_PROGRAM.methods
+    .mint(name, symbol, uri)
+    .accounts({
+      admin: admin.publicKey,
+      payer: payer.publicKey,
+      mint: mint.publicKey,
+      metadata: findMetadata(mint.publicKey),
+      owner,
+      tokenAccount: getAssociatedTokenAddressSync(mint.publicKey, owner, true),
+    })
+    .signers([admin, payer, mint])
+    .rpc();
+  return { transactionSignature, mint: mint.publicKey };
+}
+
+export type RegisterExtensionInput = {
+  admin: Keypair;
+  payer: Keypair;
+  ipfsCid: string;
+};
+
+export async function registerExtension(
+  { admin, payer, ipfsCid }: RegisterExtensionInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_NFT_PROGRAM.methods
+    .registerExtension(Array.from(CID.parse(ipfsCid).toV1().bytes))
+    .accounts({
+      payer: payer.publicKey,
+      extension: findExtension(EXAMPLE_NFT_PROGRAM.programId),
+      admin: admin.publicKey,
+    })
+    .signers([payer, admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type SetOriginInput = {
+  admin: Keypair;
+  payer: Keypair;
+  chainId: BN;
+  address: Buffer;
+  enabled: boolean;
+};
+
+export async function setOrigin(
+  { admin, payer, chainId, address, enabled }: SetOriginInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_NFT_PROGRAM.methods
+    .setOrigin(chainId, Array.from(address), enabled)
+    .accounts({
+      admin: admin.publicKey,
+      payer: payer.publicKey,
+    })
+    .signers([admin, payer])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type RemoveOriginInput = {
+  admin: Keypair;
+  chainId: BN;
+};
+
+export async function removeOrigin(
+  { admin, chainId }: RemoveOriginInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_NFT_PROGRAM.methods
+    .removeOrigin(chainId)
+    .accounts({
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type Recipient = IdlTypes<ExampleNft>["recipient"];
+
+/** Parses a 0x-prefixed EVM address or a base58 Solana address. */
+export function parseRecipient(address: string): Recipient {
+  if (address.startsWith("0x")) {
+    const bytes = Buffer.from(address.slice(2), "hex");
+    return { evm: { address: Array.from(bytes) } };
+  }
+  return { solana: { address: new PublicKey(address) } };
+}
+
+export type BridgeInput = {
+  /** Mint of the NFT held by the sender. */
+  mint: PublicKey;
+  /** Identifier of the NFT, its mint address for NFTs native to Solana. */
+  tokenId: Buffer;
+  destChainId: BN;
+  to: Recipient;
+  uipFee: BN;
+  customGasLimit: BN;
+  sender: Keypair;
+};
+
+export async function bridge(
+  {
+    mint,
+    tokenId,
+    destChainId,
+    to,
+    uipFee,
+    customGasLimit,
+    sender,
+  }: BridgeInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_NFT_PROGRAM.methods
+    .bridge(Array.from(tokenId), destChainId, to, uipFee, customGasLimit)
+    .accounts({
+      sender: sender.publicKey,
+      endpointConfig: ENDPOINT_CONFIG,
+      utsConnector: await fetchUtsConnector(),
+      tokenAccount: getAssociatedTokenAddressSync(mint, sender.publicKey, true),
+      mint,
+      metadata: findMetadata(mint),
+      vault: getAssociatedTokenAddressSync(mint, EXAMPLE_NFT_CONFIG, true),
+    })
+    .signers([sender])
+    .rpc();
+  return { transactionSignature };
+}
+
+export type UpdateAdminInput = {
+  admin: Keypair;
+  newAdmin: PublicKey;
+};
+
+export async function updateAdmin(
+  { admin, newAdmin }: UpdateAdminInput,
+): Promise<{ transactionSignature: TransactionSignature }> {
+  const transactionSignature = await EXAMPLE_NFT_PROGRAM.methods
+    .updateAdmin(newAdmin)
+    .accountsStrict({
+      config: EXAMPLE_NFT_CONFIG,
+      admin: admin.publicKey,
+    })
+    .signers([admin])
+    .rpc();
+  return { transactionSignature };
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+/// Example NFT error code.
+#[error_code]
+pub enum ExampleNftError {
+    /// 6000 0x1770
+    #[msg("Sender smart contract is not allowed")]
+    SenderSmartContractNotAllowed,
+
+    /// 6001 0x1771
+    #[msg("Destination smart contract is not allowed")]
+    DestinationSmartContractNotAllowed,
+
+    /// 6002 0x1772
+    #[msg("Recipient is not a valid address on the destination chain")]
+    InvalidRecipient,
+
+    /// 6003 0x1773
+    #[msg("Mint is not an NFT")]
+    NotAnNft,
+
+    /// 6004 0x1774
+    #[msg("Token id does not match the mint")]
+    InvalidTokenId,
+
+    /// 6005 0x1775
+    #[msg("NFT is already minted on this chain")]
+    AlreadyMinted,
+
+    /// 6006 0x1776
+    #[msg("Metadata does not belong to the mint")]
+    InvalidMetadata,
+
+    /// 6007 0x1777
+    #[msg("Mint authority of the NFT is not revoked")]
+    MintAuthorityNotRevoked,
+}
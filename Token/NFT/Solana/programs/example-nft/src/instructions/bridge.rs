@@ -0,0 +1,191 @@
+use crate::{error::*, metaplex, payload::NftTransfer, state::*, utils::*};
+use anchor_lang::prelude::*;
+use solana_invoke::invoke;
+use spl_token::instruction::{burn, transfer_checked};
+use uip_solana_sdk::{chains::*, Commitment, UipEndpoint};
+
+#[derive(Accounts)]
+#[instruction(token_id: [u8; 32], dest_chain_id: u128)]
+pub struct Bridge<'info> {
+    #[account(seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleNftConfig>,
+    #[account(seeds = [&b"ORIGIN"[..], &dest_chain_id.to_le_bytes()], bump)]
+    origin: Account<'info, Origin>,
+    #[account(mut)]
+    sender: Signer<'info>,
+    /// CHECK: checked in CPI
+    #[account(mut)]
+    token_account: AccountInfo<'info>,
+    /// CHECK: it's checked to be the NFT `token_id` in the handler
+    #[account(mut)]
+    mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the Metaplex metadata account of the mint
+    #[account(address = metaplex::find_metadata(mint.key))]
+    metadata: AccountInfo<'info>,
+    /// CHECK: it's checked to be the config ATA, only used for Solana NFTs
+    #[account(mut, address = find_ata(&config.key(), mint.key))]
+    vault: AccountInfo<'info>,
+    /// CHECK: checked in the CPI
+    endpoint_config: AccountInfo<'info>,
+    /// CHECK: checked in the CPI
+    #[account(mut)]
+    uts_connector: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    #[account(seeds = [b"UIP_SIGNER"], bump)]
+    program_signer: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL token program
+    #[account(address = spl_token::ID)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+    uip_program: Program<'info, UipEndpoint>,
+}
+
+/// Recipient of the NFT on the destination chain.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Recipient {
+    /// An EVM address.
+    Evm { address: [u8; 20] },
+    /// A Solana wallet, whose ATA receives the NFT.
+    Solana { address: Pubkey },
+}
+
+impl Recipient {
+    /// Encodes the recipient in the format expected on the chain `chain_id`,
+    /// failing if it's a zero address or not an address of that chain.
+    pub fn encode(&self, chain_id: u128) -> Result<Vec<u8>> {
+        let is_solana = matches!(chain_id, SOLANA_MAINNET_CHAIN_ID | SOLANA_DEVNET_CHAIN_ID);
+        match self {
+            Recipient::Evm { address } if !is_solana && *address != [0; 20] => {
+                // ABI-encoded as `address`, left-padded to 32 bytes.
+                let mut encoded = vec![0; 12];
+                encoded.extend_from_slice(address);
+                Ok(encoded)
+            }
+            Recipient::Solana { address } if is_solana && *address != Pubkey::default() => {
+                Ok(address.to_bytes().to_vec())
+            }
+            _ => err!(ExampleNftError::InvalidRecipient),
+        }
+    }
+}
+
+pub fn bridge(
+    ctx: Context<Bridge>,
+    token_id: [u8; 32],
+    dest_chain_id: u128,
+    to: Recipient,
+    uip_fee: u64,
+    custom_gas_limit: u128,
+) -> Result<()> {
+    let origin = &ctx.accounts.origin;
+    let sender = &ctx.accounts.sender;
+    let token_account = &ctx.accounts.token_account;
+    let mint = &ctx.accounts.mint;
+    let vault = &ctx.accounts.vault;
+
+    require!(
+        origin.enabled,
+        ExampleNftError::DestinationSmartContractNotAllowed
+    );
+    let to = to.encode(dest_chain_id)?;
+
+    let is_copy = *mint.key == find_copy_mint(&token_id).0;
+    let nft_mint = unpack_nft_mint(mint)?;
+    require_eq!(nft_mint.supply, 1, ExampleNftError::NotAnNft);
+    // Copies are minted by the config, while any other holder of the mint
+    // authority could mint a second token and bridge the same NFT twice.
+    require!(
+        is_copy || nft_mint.mint_authority.is_none(),
+        ExampleNftError::MintAuthorityNotRevoked
+    );
+    let (name, symbol, uri) = metaplex::read_metadata(&ctx.accounts.metadata, mint.key)?;
+
+    if is_copy {
+        // A copy is burned, its mint is reused if the NFT comes back.
+        let ix = burn(
+            &spl_token::ID,
+            token_account.key,
+            mint.key,
+            sender.key,
+            &[],
+            1,
+        )?;
+        invoke(
+            &ix,
+            &[
+                token_account.to_account_info(),
+                mint.to_account_info(),
+                sender.to_account_info(),
+            ],
+        )?;
+    } else {
+        require!(
+            mint.key.to_bytes() == token_id,
+            ExampleNftError::InvalidTokenId
+        );
+
+        create_ata_if_needed(
+            &sender.to_account_info(),
+            vault,
+            &ctx.accounts.config.to_account_info(),
+            mint,
+        )?;
+        let ix = transfer_checked(
+            &spl_token::ID,
+            token_account.key,
+            mint.key,
+            vault.key,
+            sender.key,
+            &[],
+            1,
+            0,
+        )?;
+        invoke(
+            &ix,
+            &[
+                token_account.to_account_info(),
+                mint.to_account_info(),
+                vault.to_account_info(),
+                sender.to_account_info(),
+            ],
+        )?;
+    }
+
+    let payload = NftTransfer {
+        from: sender.key.to_bytes().to_vec(),
+        to,
+        token_id,
+        name,
+        symbol,
+        uri,
+    }
+    .encode();
+
+    UipEndpoint::propose()
+        .payer(sender.to_account_info())
+        .endpoint_config(ctx.accounts.endpoint_config.to_account_info())
+        .uts_connector(ctx.accounts.uts_connector.to_account_info())
+        .program_signer(ctx.accounts.program_signer.to_account_info())
+        .system_program(ctx.accounts.system_program.to_account_info())
+        .program_signer_bump(ctx.bumps.program_signer)
+        .sender(&crate::ID)
+        .total_fee(uip_fee)
+        .dest_chain_id(dest_chain_id)
+        .dest_addr(&origin.address)
+        .payload(&payload)
+        .custom_gas_limit(custom_gas_limit)
+        .proposal_commitment(Commitment::Confirmed)
+        .call()?;
+
+    msg!(
+        "{} sent NFT {} to {}",
+        sender.key,
+        hex::encode(token_id),
+        dest_chain_id
+    );
+
+    Ok(())
+}
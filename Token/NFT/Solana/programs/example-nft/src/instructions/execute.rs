@@ -0,0 +1,253 @@
+use crate::{error::*, metaplex, payload::NftTransfer, state::*, utils::*};
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+use solana_invoke::{invoke, invoke_signed};
+use spl_token::{
+    instruction::{initialize_mint2, mint_to, transfer_checked},
+    solana_program::program_pack::Pack,
+};
+use uip_solana_sdk::{parse_uip_message, route_instruction, MessageDataRef};
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    /// CHECK: It's checked in `parse_uip_message`.
+    uip_msg: AccountInfo<'info>,
+}
+
+pub fn execute<'info>(ctx: Context<'_, '_, 'info, 'info, Execute>) -> Result<()> {
+    let uip_msg_data = ctx.accounts.uip_msg.try_borrow_data()?;
+    let MessageDataRef {
+        payload,
+        sender_addr,
+        src_chain_id,
+        ..
+    } = parse_uip_message(&ctx.accounts.uip_msg, &uip_msg_data, &crate::ID)?;
+
+    msg!("CCM instruction: ReceiveMessage");
+
+    let transfer = NftTransfer::decode(payload).ok_or(ProgramError::InvalidInstructionData)?;
+
+    route_instruction(
+        &crate::ID,
+        bridge_mint,
+        ctx.remaining_accounts,
+        BridgeMintIxData {
+            src_chain_id,
+            token_id: transfer.token_id,
+        },
+        BridgeMintParams {
+            sender_addr,
+            transfer: &transfer,
+        },
+    )?;
+
+    msg!(
+        "{} received NFT {} from {}",
+        hex::encode(&transfer.to),
+        hex::encode(transfer.token_id),
+        hex::encode(&transfer.from)
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ix_data: BridgeMintIxData)]
+struct BridgeMint<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(seeds = [b"CONFIG"], bump)]
+    config: Account<'info, ExampleNftConfig>,
+    #[account(
+        seeds = [&b"ORIGIN"[..], &ix_data.src_chain_id.to_le_bytes()],
+        bump,
+    )]
+    origin: Account<'info, Origin>,
+    /// CHECK: it's derived from the token id and only created if a copy of the
+    /// NFT is minted for the first time
+    #[account(mut, seeds = [&b"NFT_MINT"[..], &ix_data.token_id], bump)]
+    copy_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the Metaplex metadata account of the copy
+    #[account(mut, address = metaplex::find_metadata(copy_mint.key))]
+    copy_metadata: AccountInfo<'info>,
+    /// CHECK: it's checked to be the `to` ATA of the copy
+    #[account(mut, address = find_ata(to.key, copy_mint.key))]
+    copy_token_account: AccountInfo<'info>,
+    /// CHECK: it's checked to be the mint of a Solana NFT with the token id,
+    /// only used if it's locked in the vault
+    #[account(address = Pubkey::new_from_array(ix_data.token_id))]
+    native_mint: AccountInfo<'info>,
+    /// CHECK: it's checked to be the config ATA of the Solana NFT
+    #[account(mut, address = find_ata(&config.key(), native_mint.key))]
+    vault: AccountInfo<'info>,
+    /// CHECK: it's checked to be the `to` ATA of the Solana NFT
+    #[account(mut, address = find_ata(to.key, native_mint.key))]
+    native_token_account: AccountInfo<'info>,
+    /// CHECK: it's checked to be the recipient from the payload
+    to: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL token program
+    #[account(address = spl_token::ID)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the Metaplex token metadata program
+    #[account(address = metaplex::ID)]
+    metaplex_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Data for use in the anchor `instruction` attribute.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct BridgeMintIxData {
+    src_chain_id: u128,
+    token_id: [u8; 32],
+}
+
+/// Input for the `bridge_mint` function.
+struct BridgeMintParams<'a> {
+    sender_addr: &'a [u8],
+    transfer: &'a NftTransfer,
+}
+
+/// Releases the NFT from the vault if it's a Solana NFT bridged back, or mints
+/// a copy of it otherwise.
+fn bridge_mint(ctx: Context<BridgeMint>, params: BridgeMintParams) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let config = &ctx.accounts.config;
+    let origin = &ctx.accounts.origin;
+    let to = &ctx.accounts.to;
+    let vault = &ctx.accounts.vault;
+    let transfer = params.transfer;
+    let config_seeds: &[&[&[u8]]] = &[&[b"CONFIG", &[ctx.bumps.config]]];
+
+    require!(
+        origin.enabled && origin.address[..] == *params.sender_addr,
+        ExampleNftError::SenderSmartContractNotAllowed
+    );
+    require!(
+        to.key.as_ref() == transfer.to,
+        ExampleNftError::InvalidRecipient
+    );
+
+    if !vault.data_is_empty() {
+        let native_mint = &ctx.accounts.native_mint;
+        let token_account = &ctx.accounts.native_token_account;
+
+        create_ata_if_needed(payer, token_account, to, native_mint)?;
+        let ix = transfer_checked(
+            &spl_token::ID,
+            vault.key,
+            native_mint.key,
+            token_account.key,
+            &config.key(),
+            &[],
+            1,
+            0,
+        )?;
+        invoke_signed(
+            &ix,
+            &[
+                vault.to_account_info(),
+                native_mint.to_account_info(),
+                token_account.to_account_info(),
+                config.to_account_info(),
+            ],
+            config_seeds,
+        )?;
+
+        return Ok(());
+    }
+
+    let copy_mint = &ctx.accounts.copy_mint;
+    let copy_metadata = &ctx.accounts.copy_metadata;
+    let token_account = &ctx.accounts.copy_token_account;
+    let system_program = &ctx.accounts.system_program;
+
+    if copy_mint.data_is_empty() {
+        let space = spl_token::state::Mint::LEN;
+        let mint_seeds: &[&[&[u8]]] = &[&[b"NFT_MINT", &transfer.token_id, &[ctx.bumps.copy_mint]]];
+        let ctx2 = CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.to_account_info(),
+                to: copy_mint.to_account_info(),
+            },
+            mint_seeds,
+        );
+        create_account(
+            ctx2,
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &spl_token::ID,
+        )?;
+        let ix = initialize_mint2(&spl_token::ID, copy_mint.key, &config.key(), None, 0)?;
+        invoke(&ix, &[copy_mint.to_account_info()])?;
+    }
+    require_eq!(
+        unpack_nft_mint(copy_mint)?.supply,
+        0,
+        ExampleNftError::AlreadyMinted
+    );
+
+    // The metadata is kept when the copy is burned, and refreshed if the NFT
+    // comes back.
+    if copy_metadata.data_is_empty() {
+        let ix = metaplex::create_metadata_account_v3(
+            copy_metadata.key,
+            copy_mint.key,
+            &config.key(),
+            payer.key,
+            transfer.name.clone(),
+            transfer.symbol.clone(),
+            transfer.uri.clone(),
+        )?;
+        invoke_signed(
+            &ix,
+            &[
+                copy_metadata.to_account_info(),
+                copy_mint.to_account_info(),
+                config.to_account_info(),
+                payer.to_account_info(),
+                system_program.to_account_info(),
+            ],
+            config_seeds,
+        )?;
+    } else {
+        let ix = metaplex::update_metadata_account_v2(
+            copy_metadata.key,
+            &config.key(),
+            transfer.name.clone(),
+            transfer.symbol.clone(),
+            transfer.uri.clone(),
+        )?;
+        invoke_signed(
+            &ix,
+            &[copy_metadata.to_account_info(), config.to_account_info()],
+            config_seeds,
+        )?;
+    }
+
+    create_ata_if_needed(payer, token_account, to, copy_mint)?;
+    let ix = mint_to(
+        &spl_token::ID,
+        copy_mint.key,
+        token_account.key,
+        &config.key(),
+        &[],
+        1,
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            copy_mint.to_account_info(),
+            token_account.to_account_info(),
+            config.to_account_info(),
+        ],
+        config_seeds,
+    )?;
+
+    Ok(())
+}
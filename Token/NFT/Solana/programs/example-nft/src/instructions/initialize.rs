@@ -0,0 +1,25 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        space = 8 + ExampleNftConfig::space(),
+        payer = payer,
+        seeds = [b"CONFIG"],
+        bump
+    )]
+    config: Account<'info, ExampleNftConfig>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+pub fn initialize(ctx: Context<Initialize>, admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.admin = admin;
+
+    Ok(())
+}
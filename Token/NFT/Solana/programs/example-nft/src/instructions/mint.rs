@@ -0,0 +1,130 @@
+use crate::{metaplex, state::*, utils::*};
+use anchor_lang::{
+    prelude::*,
+    system_program::{create_account, CreateAccount},
+};
+use solana_invoke::{invoke, invoke_signed};
+use spl_token::{
+    instruction::{initialize_mint2, mint_to, set_authority, AuthorityType},
+    solana_program::program_pack::Pack,
+};
+
+#[derive(Accounts)]
+pub struct Mint<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleNftConfig>,
+    admin: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    /// The new mint, created by the instruction.
+    #[account(mut)]
+    mint: Signer<'info>,
+    /// CHECK: it's checked to be the Metaplex metadata account of the mint
+    #[account(mut, address = metaplex::find_metadata(mint.key))]
+    metadata: AccountInfo<'info>,
+    /// CHECK: it's the owner of the minted NFT
+    owner: AccountInfo<'info>,
+    /// CHECK: it's checked to be the `owner` ATA
+    #[account(mut, address = find_ata(owner.key, mint.key))]
+    token_account: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL token program
+    #[account(address = spl_token::ID)]
+    token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the SPL associated token program
+    #[account(address = spl_associated_token_account::ID)]
+    associated_token_program: AccountInfo<'info>,
+    /// CHECK: it's checked to be the Metaplex token metadata program
+    #[account(address = metaplex::ID)]
+    metaplex_program: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+pub fn mint(ctx: Context<Mint>, name: String, symbol: String, uri: String) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let payer = &ctx.accounts.payer;
+    let mint = &ctx.accounts.mint;
+    let metadata = &ctx.accounts.metadata;
+    let owner = &ctx.accounts.owner;
+    let token_account = &ctx.accounts.token_account;
+    let system_program = &ctx.accounts.system_program;
+    let config_seeds: &[&[&[u8]]] = &[&[b"CONFIG", &[ctx.bumps.config]]];
+
+    let space = spl_token::state::Mint::LEN;
+    let ctx2 = CpiContext::new(
+        system_program.to_account_info(),
+        CreateAccount {
+            from: payer.to_account_info(),
+            to: mint.to_account_info(),
+        },
+    );
+    create_account(
+        ctx2,
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &spl_token::ID,
+    )?;
+    let ix = initialize_mint2(&spl_token::ID, mint.key, &config.key(), None, 0)?;
+    invoke(&ix, &[mint.to_account_info()])?;
+
+    let ix = metaplex::create_metadata_account_v3(
+        metadata.key,
+        mint.key,
+        &config.key(),
+        payer.key,
+        name,
+        symbol,
+        uri,
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            metadata.to_account_info(),
+            mint.to_account_info(),
+            config.to_account_info(),
+            payer.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        config_seeds,
+    )?;
+
+    create_ata_if_needed(
+        &payer.to_account_info(),
+        token_account,
+        owner,
+        &mint.to_account_info(),
+    )?;
+    let ix = mint_to(
+        &spl_token::ID,
+        mint.key,
+        token_account.key,
+        &config.key(),
+        &[],
+        1,
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            mint.to_account_info(),
+            token_account.to_account_info(),
+            config.to_account_info(),
+        ],
+        config_seeds,
+    )?;
+
+    // No more tokens can be minted, so the NFT stays unique.
+    let ix = set_authority(
+        &spl_token::ID,
+        mint.key,
+        None,
+        AuthorityType::MintTokens,
+        &config.key(),
+        &[],
+    )?;
+    invoke_signed(
+        &ix,
+        &[mint.to_account_info(), config.to_account_info()],
+        config_seeds,
+    )?;
+
+    Ok(())
+}
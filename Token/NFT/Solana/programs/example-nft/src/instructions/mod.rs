@@ -0,0 +1,13 @@
+pub use self::{
+    bridge::*, execute::*, initialize::*, mint::*, register_extension::*, remove_origin::*,
+    set_origin::*, update_admin::*,
+};
+
+mod bridge;
+mod execute;
+mod initialize;
+mod mint;
+mod register_extension;
+mod remove_origin;
+mod set_origin;
+mod update_admin;
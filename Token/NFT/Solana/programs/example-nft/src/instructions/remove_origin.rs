@@ -0,0 +1,24 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(chain_id: u128)]
+pub struct RemoveOrigin<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleNftConfig>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [&b"ORIGIN"[..], &chain_id.to_le_bytes()],
+        bump
+    )]
+    origin: Account<'info, Origin>,
+    #[account(mut)]
+    admin: Signer<'info>,
+}
+
+/// Forgets the example NFT deployment known for `chain_id`, returning the rent
+/// to the admin.
+pub fn remove_origin(_ctx: Context<RemoveOrigin>, _chain_id: u128) -> Result<()> {
+    Ok(())
+}
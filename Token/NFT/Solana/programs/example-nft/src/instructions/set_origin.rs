@@ -0,0 +1,37 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(chain_id: u128)]
+pub struct SetOrigin<'info> {
+    #[account(seeds = [b"CONFIG"], bump, has_one = admin)]
+    config: Account<'info, ExampleNftConfig>,
+    #[account(
+        init_if_needed,
+        space = 8 + Origin::space(),
+        payer = payer,
+        seeds = [&b"ORIGIN"[..], &chain_id.to_le_bytes()],
+        bump
+    )]
+    origin: Account<'info, Origin>,
+    admin: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Creates or updates the example NFT deployment known for `chain_id`.
+pub fn set_origin(
+    ctx: Context<SetOrigin>,
+    chain_id: u128,
+    address: [u8; 32],
+    enabled: bool,
+) -> Result<()> {
+    let origin = &mut ctx.accounts.origin;
+
+    origin.chain_id = chain_id;
+    origin.address = address;
+    origin.enabled = enabled;
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UpdateAdmin<'info> {
+    #[account(mut, has_one = admin)]
+    config: Account<'info, ExampleNftConfig>,
+    admin: Signer<'info>,
+}
+
+pub fn update_admin(ctx: Context<UpdateAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.admin = new_admin;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+//! Example NFT protocol, utilizing UIP for cross-chain communication.
+#![allow(unexpected_cfgs)]
+
+use crate::instructions::*;
+use anchor_lang::prelude::*;
+
+pub mod error;
+mod instructions;
+mod metaplex;
+pub mod payload;
+pub mod state;
+mod utils;
+
+declare_id!("6CRi985YkuQ2XZSKMi7P3EMXyPY9xfW3Vib3rhXpYZqx");
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_security_txt::security_txt! {
+    name: "NFT Example",
+    project_url: "https://github.com/Entangle-Protocol/UIP-examples",
+    contacts: "email:lincot@disroot.org,discord:lincot",
+    policy: "Please contact us if you have discovered a bug"
+}
+
+/// The example NFT program module.
+#[program]
+pub mod example_nft {
+    use super::*;
+
+    /// Initializes the example NFT bridge contract.
+    pub fn initialize(ctx: Context<Initialize>, admin: Pubkey) -> Result<()> {
+        instructions::initialize(ctx, admin)
+    }
+
+    /// Mints a new NFT with Metaplex metadata to `owner`.
+    pub fn mint(ctx: Context<Mint>, name: String, symbol: String, uri: String) -> Result<()> {
+        instructions::mint(ctx, name, symbol, uri)
+    }
+
+    /// Registers the UIP example NFT extension with the specified IPFS CID.
+    pub fn register_extension(ctx: Context<RegisterExtension>, ipfs_cid: [u8; 36]) -> Result<()> {
+        instructions::register_extension(ctx, ipfs_cid)
+    }
+
+    /// Creates or updates the example NFT deployment known for `chain_id`.
+    pub fn set_origin(
+        ctx: Context<SetOrigin>,
+        chain_id: u128,
+        address: [u8; 32],
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_origin(ctx, chain_id, address, enabled)
+    }
+
+    /// Removes the example NFT deployment known for `chain_id`.
+    pub fn remove_origin(ctx: Context<RemoveOrigin>, chain_id: u128) -> Result<()> {
+        instructions::remove_origin(ctx, chain_id)
+    }
+
+    /// Sends the NFT `token_id` to `to` on the chain `dest_chain_id`, paying
+    /// the specified `uip_fee`. A copy minted by the bridge is burned, while a
+    /// Solana NFT, whose token id is its mint address, is locked in the vault.
+    pub fn bridge(
+        ctx: Context<Bridge>,
+        token_id: [u8; 32],
+        dest_chain_id: u128,
+        to: Recipient,
+        uip_fee: u64,
+        custom_gas_limit: u128,
+    ) -> Result<()> {
+        instructions::bridge(ctx, token_id, dest_chain_id, to, uip_fee, custom_gas_limit)
+    }
+
+    /// Executes an incoming cross-chain message.
+    #[instruction(discriminator = uip_solana_sdk::EXECUTE_DISCRIMINATOR)]
+    pub fn execute<'info>(ctx: Context<'_, '_, 'info, 'info, Execute>) -> Result<()> {
+        instructions::execute(ctx)
+    }
+
+    /// Update the example NFT admin.
+    #[instruction(discriminator = b"upd_admn")]
+    pub fn update_admin(ctx: Context<UpdateAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::update_admin(ctx, new_admin)
+    }
+}
@@ -0,0 +1,127 @@
+//! Minimal bindings to the Metaplex token metadata program, which are used to
+//! read and attach the metadata of an NFT.
+
+use crate::error::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, pubkey},
+};
+
+/// The Metaplex token metadata program id.
+pub const ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+
+/// Finds the metadata account of `mint`.
+pub fn find_metadata(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", ID.as_ref(), mint.as_ref()], &ID).0
+}
+
+/// Token metadata without creators, a collection and uses.
+#[derive(AnchorSerialize)]
+struct DataV2 {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    creators: Option<()>,
+    collection: Option<()>,
+    uses: Option<()>,
+}
+
+impl DataV2 {
+    fn new(name: String, symbol: String, uri: String) -> Self {
+        Self {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        }
+    }
+}
+
+/// The leading fields of a metadata account, up to the URI.
+#[derive(AnchorDeserialize)]
+struct MetadataPrefix {
+    _key: u8,
+    _update_authority: Pubkey,
+    mint: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+}
+
+/// Reads the name, symbol and URI from the `metadata` account of `mint`,
+/// without the padding Metaplex stores them with.
+pub fn read_metadata(metadata: &AccountInfo, mint: &Pubkey) -> Result<(String, String, String)> {
+    require_keys_eq!(*metadata.owner, ID, ExampleNftError::InvalidMetadata);
+    let data = metadata.try_borrow_data()?;
+    let prefix = MetadataPrefix::deserialize(&mut &data[..])
+        .map_err(|_| ExampleNftError::InvalidMetadata)?;
+    require_keys_eq!(prefix.mint, *mint, ExampleNftError::InvalidMetadata);
+
+    let trim = |s: String| s.trim_end_matches('\0').to_owned();
+    Ok((trim(prefix.name), trim(prefix.symbol), trim(prefix.uri)))
+}
+
+/// Creates mutable metadata for `mint`, with `authority` being both its mint
+/// authority and the metadata update authority.
+pub fn create_metadata_account_v3(
+    metadata: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<Instruction> {
+    let mut data = vec![CREATE_METADATA_ACCOUNT_V3];
+    // The metadata, `is_mutable` and no collection details.
+    (DataV2::new(name, symbol, uri), true, None::<()>).serialize(&mut data)?;
+
+    Ok(Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(System::id(), false),
+        ],
+        data,
+    })
+}
+
+/// Replaces the name, symbol and URI in the `metadata` account.
+pub fn update_metadata_account_v2(
+    metadata: &Pubkey,
+    update_authority: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<Instruction> {
+    let mut data = vec![UPDATE_METADATA_ACCOUNT_V2];
+    // The metadata and no changes to the update authority, the primary sale
+    // flag and mutability.
+    (
+        Some(DataV2::new(name, symbol, uri)),
+        None::<Pubkey>,
+        None::<bool>,
+        None::<bool>,
+    )
+        .serialize(&mut data)?;
+
+    Ok(Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*update_authority, true),
+        ],
+        data,
+    })
+}
@@ -0,0 +1,57 @@
+//! Example NFT cross-chain payload.
+
+use alloy_sol_types::{
+    sol_data::{Bytes, String, Uint},
+    SolType,
+};
+use ruint::aliases::U256;
+
+type NftTransferAbi = (Bytes, Bytes, Uint<256>, String, String, String);
+
+/// An NFT transfer between chains, carrying the metadata needed to mint a copy
+/// of it on the destination chain.
+#[derive(Debug)]
+pub struct NftTransfer {
+    /// Sender of the NFT on the source chain.
+    pub from: Vec<u8>,
+    /// Recipient of the NFT on the destination chain.
+    pub to: Vec<u8>,
+    /// Identifier of the NFT, the same on all chains. NFTs native to Solana
+    /// are identified by their mint address.
+    pub token_id: [u8; 32],
+    /// Name of the NFT.
+    pub name: std::string::String,
+    /// Symbol of the NFT.
+    pub symbol: std::string::String,
+    /// URI of the NFT metadata.
+    pub uri: std::string::String,
+}
+
+impl NftTransfer {
+    /// Decodes an NFT transfer.
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        let (from, to, token_id, name, symbol, uri) =
+            NftTransferAbi::abi_decode_params(payload, true).ok()?;
+
+        Some(Self {
+            from: from.into(),
+            to: to.into(),
+            token_id: token_id.to_be_bytes(),
+            name,
+            symbol,
+            uri,
+        })
+    }
+
+    /// Encodes the NFT transfer as a payload.
+    pub fn encode(&self) -> Vec<u8> {
+        NftTransferAbi::abi_encode_params(&(
+            self.from.clone(),
+            self.to.clone(),
+            U256::from_be_bytes(self.token_id),
+            self.name.clone(),
+            self.symbol.clone(),
+            self.uri.clone(),
+        ))
+    }
+}
@@ -0,0 +1,38 @@
+//! Example NFT account structures.
+
+use anchor_lang::prelude::*;
+
+/// The smart contract configuration.
+#[account]
+#[derive(Debug)]
+pub struct ExampleNftConfig {
+    /// Administrator key that can be used to register the UIP extension.
+    pub admin: Pubkey,
+}
+
+impl ExampleNftConfig {
+    pub(crate) fn space() -> usize {
+        32
+    }
+}
+
+/// A known example NFT deployment on another chain.
+#[account]
+#[derive(Debug)]
+pub struct Origin {
+    /// Identifier of the remote chain.
+    pub chain_id: u128,
+    /// Address of the example NFT contract on the remote chain.
+    pub address: [u8; 32],
+    /// Whether NFTs can be bridged to and from the remote chain.
+    pub enabled: bool,
+}
+
+impl Origin {
+    pub(crate) fn space() -> usize {
+        let space_chain_id = 16;
+        let space_address = 32;
+        let space_enabled = 1;
+        space_chain_id + space_address + space_enabled
+    }
+}
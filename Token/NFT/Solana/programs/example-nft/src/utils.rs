@@ -0,0 +1,57 @@
+use crate::error::*;
+use anchor_lang::prelude::*;
+use solana_invoke::invoke;
+use spl_associated_token_account::instruction::create_associated_token_account;
+use spl_token::{solana_program::program_pack::Pack, state::Mint};
+
+pub fn find_ata(wallet_address: &Pubkey, token_mint_address: &Pubkey) -> Pubkey {
+    const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+        pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+    Pubkey::find_program_address(
+        &[
+            &wallet_address.to_bytes(),
+            &spl_token::ID.to_bytes(),
+            &token_mint_address.to_bytes(),
+        ],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Finds the mint of the copy of the NFT `token_id` minted by the bridge.
+pub fn find_copy_mint(token_id: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"NFT_MINT", token_id], &crate::ID)
+}
+
+/// Unpacks `mint`, failing if it's not an SPL token mint without decimals.
+pub fn unpack_nft_mint(mint: &AccountInfo) -> Result<Mint> {
+    require_keys_eq!(*mint.owner, spl_token::ID, ExampleNftError::NotAnNft);
+    let mint = Mint::unpack(&mint.try_borrow_data()?)?;
+    require_eq!(mint.decimals, 0, ExampleNftError::NotAnNft);
+
+    Ok(mint)
+}
+
+/// Creates `token_account` as the ATA of `to` if it doesn't exist.
+pub fn create_ata_if_needed<'info>(
+    payer: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+) -> Result<()> {
+    if token_account.data_is_empty() {
+        let ix = create_associated_token_account(payer.key, to.key, mint.key, &spl_token::ID);
+        invoke(
+            &ix,
+            &[
+                payer.clone(),
+                token_account.clone(),
+                to.clone(),
+                mint.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+import * as anchor from "@coral-xyz/anchor";
+import { bridge, parseRecipient } from "../helpers/exampleNft";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import BN from "bn.js";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 5) {
+    console.error(
+      "Usage: bridge <mint> <dst-chain-id> <uip-fee> <custom-gas-limit> <to> [<token-id>]",
+    );
+    process.exit(1);
+  }
+
+  const mint = new PublicKey(process.argv[2]);
+  const destChainId = new BN(process.argv[3]);
+  const uipFee = new BN(process.argv[4]);
+  const customGasLimit = new BN(process.argv[5]);
+  const to = parseRecipient(process.argv[6]);
+  // NFTs native to Solana are identified by their mint, copies by the token id
+  // they were bridged with.
+  const tokenId = process.argv[7]
+    ? new BN(process.argv[7]).toArrayLike(Buffer, "be", 32)
+    : mint.toBuffer();
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature } = await bridge({
+    mint,
+    tokenId,
+    destChainId,
+    uipFee,
+    customGasLimit,
+    sender: payer,
+    to,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
@@ -0,0 +1,37 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { expect } from "bun:test";
+import { initialize, registerExtension } from "../helpers/exampleNft";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 1) {
+    console.error("Usage: initialize <ipfs-cid>");
+    process.exit(1);
+  }
+
+  const ipfsCid = process.argv[2];
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  try {
+    const { transactionSignature } = await initialize({
+      payer,
+      admin: payer.publicKey,
+    });
+    console.log("Initialize transaction signature:", transactionSignature);
+  } catch (e) {
+    expect(e.toString()).toInclude("already in use");
+    console.warn("Already initialized");
+  }
+
+  const { transactionSignature } = await registerExtension({
+    admin: payer,
+    payer,
+    ipfsCid,
+  });
+  console.log("RegisterExtension transaction signature:", transactionSignature);
+}
+
+main();
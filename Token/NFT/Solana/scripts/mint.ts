@@ -0,0 +1,32 @@
+import * as anchor from "@coral-xyz/anchor";
+import { mint } from "../helpers/exampleNft";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { PublicKey } from "@solana/web3.js";
+
+async function main(): Promise<void> {
+  if (process.argv.length < 2 + 4) {
+    console.error("Usage: mint <owner> <name> <symbol> <uri>");
+    process.exit(1);
+  }
+
+  const owner = new PublicKey(process.argv[2]);
+  const [name, symbol, uri] = process.argv.slice(3, 6);
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const { transactionSignature, mint: nft } = await mint({
+    admin: payer,
+    payer,
+    owner,
+    name,
+    symbol,
+    uri,
+  });
+
+  console.log("Transaction signature:", transactionSignature);
+  console.log("Mint:", nft.toBase58());
+}
+
+main();
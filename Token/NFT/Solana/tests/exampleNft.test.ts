@@ -0,0 +1,411 @@
+import {
+  encodeTransmitterParams,
+  executeFull,
+  findExtension,
+  findMessage,
+  signMsg,
+  UIP_PROGRAM,
+  unloadMessage,
+} from "../helpers/endpoint";
+import {
+  bridge,
+  EXAMPLE_NFT_CONFIG,
+  EXAMPLE_NFT_PROGRAM,
+  findCopyMint,
+  findMetadata,
+  findOrigin,
+  initialize,
+  METAPLEX_PROGRAM_ID,
+  mint,
+  registerExtension,
+  removeOrigin,
+  setOrigin,
+  updateAdmin,
+} from "../helpers/exampleNft";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { afterAll, beforeAll, describe, expect, test } from "bun:test";
+import {
+  disperse,
+  readKeypairFromFile,
+  setupTests,
+  SOLANA_CHAIN_ID,
+  TEIB_CHAIN_ID,
+  transferEverything,
+} from "../helpers/utils";
+import BN from "bn.js";
+import bs58 from "bs58";
+import { AbiCoder, Wallet } from "ethers";
+import { randomInt } from "crypto";
+import { CID } from "multiformats";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAccount,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+const admin = readKeypairFromFile("keys/admin.json");
+const executor = readKeypairFromFile("keys/executor.json");
+const sender = new Keypair();
+const receiver = new Keypair();
+const signer = new Wallet(
+  "0x74e3ffad2b87174dc1d806edf1a01e3b017cf1be05d1894d329826f10fa1d72f",
+);
+const superSigner = new Wallet(
+  "0xf496bcca0a4896011dbdbe2ec80417ed759a6a9cc72477b3a65b8d99b066b150",
+);
+const transmitterParams = {
+  proposalCommitment: { confirmed: {} },
+  customGasLimit: new BN(2),
+};
+const transmitterParamsEncoded = encodeTransmitterParams(transmitterParams);
+
+const { connection, payer } = setupTests();
+
+beforeAll(async () => {
+  await disperse(
+    connection,
+    [admin.publicKey, executor.publicKey, sender.publicKey, receiver.publicKey],
+    payer,
+    150_000_000,
+  );
+});
+
+afterAll(async () => {
+  await transferEverything(connection, [executor, sender, receiver], payer);
+});
+
+/** Returns the accounts of the execution of an NFT transfer to `to`. */
+function executeAccounts(srcChainId: BN, tokenId: Buffer, to: PublicKey) {
+  const copyMint = findCopyMint(tokenId);
+  const nativeMint = new PublicKey(tokenId);
+  return [
+    { pubkey: EXAMPLE_NFT_CONFIG, isSigner: false, isWritable: false },
+    { pubkey: findOrigin(srcChainId), isSigner: false, isWritable: false },
+    { pubkey: copyMint, isSigner: false, isWritable: true },
+    { pubkey: findMetadata(copyMint), isSigner: false, isWritable: true },
+    {
+      pubkey: getAssociatedTokenAddressSync(copyMint, to, true),
+      isSigner: false,
+      isWritable: true,
+    },
+    { pubkey: nativeMint, isSigner: false, isWritable: false },
+    {
+      pubkey: getAssociatedTokenAddressSync(
+        nativeMint,
+        EXAMPLE_NFT_CONFIG,
+        true,
+      ),
+      isSigner: false,
+      isWritable: true,
+    },
+    {
+      pubkey: getAssociatedTokenAddressSync(nativeMint, to, true),
+      isSigner: false,
+      isWritable: true,
+    },
+    { pubkey: to, isSigner: false, isWritable: false },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: METAPLEX_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+}
+
+describe("example nft", () => {
+  test("initialize", async () => {
+    try {
+      await initialize({ payer, admin: admin.publicKey });
+    } catch (e) {
+      expect(e.toString()).toInclude("already in use");
+    }
+
+    const config = await EXAMPLE_NFT_PROGRAM.account.exampleNftConfig.fetch(
+      EXAMPLE_NFT_CONFIG,
+    );
+    expect(config.admin).toEqual(admin.publicKey);
+  });
+
+  test("registerExtension", async () => {
+    const ipfsCid =
+      "bafkreihwalovxt6jjsko7gws4m34frlqoqi5huqd5x3lacjrqyyzxm3tuy";
+
+    await registerExtension({ admin, payer, ipfsCid });
+
+    const extension = await UIP_PROGRAM.account.extension.fetch(
+      findExtension(EXAMPLE_NFT_PROGRAM.programId),
+    );
+    expect(extension.program).toEqual(EXAMPLE_NFT_PROGRAM.programId);
+    expect(extension.ipfsCid).toEqual(
+      Array.from(CID.parse(ipfsCid).toV1().bytes),
+    );
+  });
+
+  test("setOrigin", async () => {
+    await setOrigin({
+      admin,
+      payer,
+      chainId: SOLANA_CHAIN_ID,
+      address: EXAMPLE_NFT_PROGRAM.programId.toBuffer(),
+      enabled: true,
+    });
+
+    const origin = await EXAMPLE_NFT_PROGRAM.account.origin.fetch(
+      findOrigin(SOLANA_CHAIN_ID),
+    );
+    expect(origin.chainId.eq(SOLANA_CHAIN_ID)).toBeTrue();
+    expect(Buffer.from(origin.address)).toEqual(
+      EXAMPLE_NFT_PROGRAM.programId.toBuffer(),
+    );
+    expect(origin.enabled).toBeTrue();
+  });
+
+  test("removeOrigin", async () => {
+    await setOrigin({
+      admin,
+      payer,
+      chainId: TEIB_CHAIN_ID,
+      address: Buffer.alloc(32, 1),
+      enabled: false,
+    });
+
+    await removeOrigin({ admin, chainId: TEIB_CHAIN_ID });
+
+    const origin = await connection.getAccountInfo(findOrigin(TEIB_CHAIN_ID));
+    expect(origin).toBeNull();
+  });
+
+  const uipFee = new BN(80085);
+  const customGasLimit = new BN(1_000_000);
+  const destAddr = EXAMPLE_NFT_PROGRAM.programId.toBuffer();
+  let nft: PublicKey;
+  let selector = new Array<number>();
+
+  test("bridge and receive", async () => {
+    ({ mint: nft } = await mint({
+      admin,
+      payer,
+      owner: sender.publicKey,
+      name: "Example NFT",
+      symbol: "EXN",
+      uri: "https://example.com/exn/1.json",
+    }));
+    expect(await getMint(connection, nft).then((x) => x.mintAuthority))
+      .toBeNull();
+
+    let payload: Buffer = Buffer.alloc(0);
+    const eventPromise: Promise<void> = new Promise((resolve, reject) => {
+      UIP_PROGRAM.addEventListener(
+        "messageProposed",
+        (event) => {
+          try {
+            expect(event.sender).toEqual(EXAMPLE_NFT_PROGRAM.programId);
+            selector = event.selector;
+            payload = event.payload;
+            resolve();
+          } catch (error) {
+            reject(error);
+          }
+        },
+      );
+
+      setTimeout(() => {
+        reject(new Error("Event did not fire within timeout"));
+      }, 15000);
+    });
+
+    const { transactionSignature } = await bridge({
+      mint: nft,
+      tokenId: nft.toBuffer(),
+      destChainId: SOLANA_CHAIN_ID,
+      to: { solana: { address: receiver.publicKey } },
+      uipFee,
+      customGasLimit,
+      sender,
+    });
+
+    await eventPromise;
+
+    const vault = getAssociatedTokenAddressSync(nft, EXAMPLE_NFT_CONFIG, true);
+    expect(await getAccount(connection, vault).then((x) => x.amount))
+      .toEqual(1n);
+
+    const txId = bs58.decode(transactionSignature);
+    const msgData = {
+      initialProposal: {
+        senderAddr: EXAMPLE_NFT_PROGRAM.programId.toBuffer(),
+        destAddr: new PublicKey(destAddr),
+        totalFee: uipFee,
+        payload,
+        reserved: Buffer.from([]),
+        transmitterParams: transmitterParamsEncoded,
+        selector,
+      },
+      srcChainData: {
+        srcBlockNumber: new BN(randomInt(256)),
+        srcChainId: SOLANA_CHAIN_ID,
+        srcOpTxId: [
+          Array.from(txId.subarray(0, 32)),
+          Array.from(txId.subarray(32)),
+        ],
+      },
+    };
+
+    await executeFull({
+      executor,
+      msgData,
+      signatures: [signMsg(signer, msgData)],
+      superSignatures: [signMsg(superSigner, msgData)],
+      accounts: executeAccounts(
+        SOLANA_CHAIN_ID,
+        nft.toBuffer(),
+        receiver.publicKey,
+      ),
+      spendingLimit: new BN(5_000_000),
+    });
+
+    // The Solana NFT comes back, so it's released from the vault.
+    expect(await getAccount(connection, vault).then((x) => x.amount))
+      .toEqual(0n);
+    const tokenAccount = getAssociatedTokenAddressSync(
+      nft,
+      receiver.publicKey,
+    );
+    expect(await getAccount(connection, tokenAccount).then((x) => x.amount))
+      .toEqual(1n);
+
+    await unloadMessage({ payer: executor, message: findMessage(msgData) });
+  });
+
+  test("receive and bridge a copy", async () => {
+    const tokenId = new BN(42).toArrayLike(Buffer, "be", 32);
+    const payload = Buffer.from(
+      AbiCoder.defaultAbiCoder()
+        .encode(
+          ["bytes", "bytes", "uint256", "string", "string", "string"],
+          [
+            Buffer.alloc(32, 1),
+            receiver.publicKey.toBuffer(),
+            42,
+            "Remote NFT",
+            "RNFT",
+            "https://example.com/rnft/42.json",
+          ],
+        )
+        .slice(2),
+      "hex",
+    );
+
+    const msgData = {
+      initialProposal: {
+        senderAddr: EXAMPLE_NFT_PROGRAM.programId.toBuffer(),
+        destAddr: new PublicKey(destAddr),
+        totalFee: uipFee,
+        payload,
+        reserved: Buffer.from([]),
+        transmitterParams: transmitterParamsEncoded,
+        selector,
+      },
+      srcChainData: {
+        srcBlockNumber: new BN(randomInt(256)),
+        srcChainId: SOLANA_CHAIN_ID,
+        srcOpTxId: [
+          Array.from(Keypair.generate().publicKey.toBuffer()),
+          Array.from(Keypair.generate().publicKey.toBuffer()),
+        ],
+      },
+    };
+
+    await executeFull({
+      executor,
+      msgData,
+      signatures: [signMsg(signer, msgData)],
+      superSignatures: [signMsg(superSigner, msgData)],
+      accounts: executeAccounts(SOLANA_CHAIN_ID, tokenId, receiver.publicKey),
+      spendingLimit: new BN(10_000_000),
+    });
+
+    const copyMint = findCopyMint(tokenId);
+    const tokenAccount = getAssociatedTokenAddressSync(
+      copyMint,
+      receiver.publicKey,
+    );
+    expect(await getAccount(connection, tokenAccount).then((x) => x.amount))
+      .toEqual(1n);
+    expect(await connection.getAccountInfo(findMetadata(copyMint))).not
+      .toBeNull();
+
+    await unloadMessage({ payer: executor, message: findMessage(msgData) });
+
+    await bridge({
+      mint: copyMint,
+      tokenId,
+      destChainId: SOLANA_CHAIN_ID,
+      to: { solana: { address: sender.publicKey } },
+      uipFee,
+      customGasLimit,
+      sender: receiver,
+    });
+
+    // The copy is burned, while its mint is kept for the NFT to come back.
+    expect(await getMint(connection, copyMint).then((x) => x.supply))
+      .toEqual(0n);
+  });
+
+  test("bridge with a wrong token id", async () => {
+    await expect(
+      bridge({
+        mint: nft,
+        tokenId: Buffer.alloc(32, 7),
+        destChainId: SOLANA_CHAIN_ID,
+        to: { solana: { address: sender.publicKey } },
+        uipFee,
+        customGasLimit,
+        sender: receiver,
+      }),
+    ).rejects.toThrow("InvalidTokenId");
+  });
+
+  test("bridge with an active mint authority", async () => {
+    const mint = await createMint(connection, sender, sender.publicKey, null, 0);
+    const tokenAccount = await createAccount(
+      connection,
+      sender,
+      mint,
+      sender.publicKey,
+    );
+    await mintTo(connection, sender, mint, tokenAccount, sender, 1);
+
+    await expect(
+      bridge({
+        mint,
+        tokenId: mint.toBuffer(),
+        destChainId: SOLANA_CHAIN_ID,
+        to: { solana: { address: receiver.publicKey } },
+        uipFee,
+        customGasLimit,
+        sender,
+      }),
+    ).rejects.toThrow("MintAuthorityNotRevoked");
+  });
+
+  test("update admin", async () => {
+    await updateAdmin({ admin, newAdmin: payer.publicKey });
+
+    const config = await EXAMPLE_NFT_PROGRAM.account.exampleNftConfig.fetch(
+      EXAMPLE_NFT_CONFIG,
+    );
+    expect(config.admin).toEqual(payer.publicKey);
+
+    await updateAdmin({ admin: payer, newAdmin: admin.publicKey });
+
+    const config2 = await EXAMPLE_NFT_PROGRAM.account.exampleNftConfig.fetch(
+      EXAMPLE_NFT_CONFIG,
+    );
+    expect(config2.admin).toEqual(admin.publicKey);
+  });
+});
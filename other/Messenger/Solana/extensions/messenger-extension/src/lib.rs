@@ -29,16 +29,25 @@ pub unsafe extern "C" fn get_instruction_info(
     result: &mut InstructionInfo,
 ) {
     let msg_data = core::slice::from_raw_parts(msg_data_ptr, msg_data_len);
-    let MessageDataRef { msg_hash, .. } = deserialize_message_data(msg_data).unwrap();
+    let MessageDataRef {
+        msg_hash,
+        src_chain_id,
+        ..
+    } = deserialize_message_data(msg_data).unwrap();
 
     let (messenger_pda, _) =
         Pubkey::find_program_address(&[b"MESSENGER"], &messenger::ID.to_bytes().into());
     result.accounts[0] = AccountMeta::new(messenger_pda, false);
+    let (origin_pda, _) = Pubkey::find_program_address(
+        &[b"ORIGIN", &src_chain_id.to_le_bytes()],
+        &messenger::ID.to_bytes().into(),
+    );
+    result.accounts[1] = AccountMeta::new_readonly(origin_pda, false);
     let (message_pda, _) =
         Pubkey::find_program_address(&[b"MESSAGE", msg_hash], &messenger::ID.to_bytes().into());
-    result.accounts[1] = AccountMeta::new(message_pda, false);
-    result.accounts[2] = AccountMeta::new_readonly(system_program::ID, false);
-    result.accounts_len = 3;
+    result.accounts[2] = AccountMeta::new(message_pda, false);
+    result.accounts[3] = AccountMeta::new_readonly(system_program::ID, false);
+    result.accounts_len = 4;
     result.compute_units = 30_000;
     result.heap_frame = 0;
 }
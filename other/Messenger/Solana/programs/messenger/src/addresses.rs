@@ -1,57 +0,0 @@
-use hex_literal::hex;
-
-pub const ETHEREUM_ADDRESS: [u8; 32] =
-    hex!("0000000000000000000000005539a189b0Bc22858A28d83C22829160d8B0B502");
-pub const ETHEREUM_SEPOLIA_ADDRESS: [u8; 32] =
-    hex!("0000000000000000000000006Bed0fc0Ce716a135e7Bc45C8C2701c3F92c305A");
-
-pub const POLYGON_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
-pub const POLYGON_AMOY_ADDRESS: [u8; 32] =
-    hex!("000000000000000000000000d308F2B0BbeFDAC788361bAFf37e5E2154ecdb46");
-
-pub const MANTLE_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
-pub const MANTLE_SEPOLIA_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000087E566026a4419df38135881b3901156f744a2D2");
-
-pub const EIB_ADDRESS: [u8; 32] =
-    hex!("0000000000000000000000007c6b8c663FEC53650d5433C40e3CfAaCa24eA9F9");
-pub const TEIB_ADDRESS: [u8; 32] =
-    hex!("000000000000000000000000f32488b8486426d0157E2c092c83F49A22b57f5C");
-
-pub const BASE_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
-pub const BASE_SEPOLIA_ADDRESS: [u8; 32] =
-    hex!("000000000000000000000000A44f5Cb1940297d85812BcC46184E10C8EA333E0");
-
-pub const SONIC_ADDRESS: [u8; 32] =
-    hex!("0000000000000000000000005539a189b0Bc22858A28d83C22829160d8B0B502");
-pub const SONIC_BLAZE_TESTNET_ADDRESS: [u8; 32] =
-    hex!("0000000000000000000000007849B81013c9531D5aCB351537142D86837032Ba");
-
-pub const AVALANCHE_C_CHAIN_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
-pub const AVALANCHE_FUJI_ADDRESS: [u8; 32] =
-    hex!("0000000000000000000000008fddD41A2ad5D973a4D0bd7f1F427584Ad176dD4");
-
-pub const MANTA_PACIFIC_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
-
-pub const ABSTRACT_ADDRESS: [u8; 32] =
-    hex!("000000000000000000000000c1629a968aA715083c303aa827B412721ED78298");
-
-pub const BERACHAIN_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
-
-pub const BSC_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
-
-pub const IMMUTABLE_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
-
-pub const OPTIMISM_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
-
-pub const ARBITRUM_ADDRESS: [u8; 32] =
-    hex!("00000000000000000000000079bf2F1C95B5b49C7bF7D24665165D5F0186E873");
@@ -20,4 +20,8 @@ pub enum MessengerError {
     /// 6003 0x1773
     #[msg("Destination smart contract is not allowed")]
     DestinationSmartContractNotAllowed,
+
+    /// 6004 0x1774
+    #[msg("Destination chain is unknown")]
+    UnknownDestinationChain,
 }
@@ -1,7 +1,7 @@
-use crate::{addresses::*, error::*, state::*};
+use crate::{error::*, state::*};
 use alloy_sol_types::{sol_data, SolType};
 use anchor_lang::prelude::*;
-use uip_solana_sdk::{chains::*, parse_uip_message, route_instruction, MessageDataRef};
+use uip_solana_sdk::{parse_uip_message, route_instruction, MessageDataRef};
 
 #[derive(Accounts)]
 pub struct Execute<'info> {
@@ -19,52 +19,20 @@ pub fn execute<'info>(ctx: Context<'_, '_, 'info, 'info, Execute>) -> Result<()>
         ..
     } = parse_uip_message(&ctx.accounts.uip_msg, &uip_msg_data, &crate::ID)?;
 
-    #[cfg(not(feature = "mainnet"))]
-    let allowed_origins = [
-        (&SOLANA_DEVNET_CHAIN_ID, &crate::ID.to_bytes()[..]),
-        (&ETHEREUM_SEPOLIA_CHAIN_ID, &ETHEREUM_SEPOLIA_ADDRESS),
-        (&POLYGON_AMOY_CHAIN_ID, &POLYGON_AMOY_ADDRESS),
-        (&MANTLE_SEPOLIA_CHAIN_ID, &MANTLE_SEPOLIA_ADDRESS),
-        (&TEIB_CHAIN_ID, &TEIB_ADDRESS),
-        (&SONIC_BLAZE_TESTNET_CHAIN_ID, &SONIC_BLAZE_TESTNET_ADDRESS),
-        (&AVALANCHE_FUJI_CHAIN_ID, &AVALANCHE_FUJI_ADDRESS),
-    ];
-    #[cfg(feature = "mainnet")]
-    let allowed_origins = [
-        (&SOLANA_MAINNET_CHAIN_ID, &crate::ID.to_bytes()[..]),
-        (&ETHEREUM_CHAIN_ID, &ETHEREUM_ADDRESS),
-        (&SONIC_MAINNET_CHAIN_ID, &SONIC_ADDRESS),
-        (&AVALANCHE_C_CHAIN_CHAIN_ID, &AVALANCHE_C_CHAIN_ADDRESS),
-        (&EIB_CHAIN_ID, &EIB_ADDRESS),
-        (&POLYGON_CHAIN_ID, &POLYGON_ADDRESS),
-        (&MANTA_PACIFIC_CHAIN_ID, &MANTA_PACIFIC_ADDRESS),
-        (&ABSTRACT_CHAIN_ID, &ABSTRACT_ADDRESS),
-        (&BERACHAIN_CHAIN_ID, &BERACHAIN_ADDRESS),
-        (&MANTLE_CHAIN_ID, &MANTLE_ADDRESS),
-        (&BSC_CHAIN_ID, &BSC_ADDRESS),
-        (&IMMUTABLE_CHAIN_ID, &IMMUTABLE_ADDRESS),
-        (&OPTIMISM_CHAIN_ID, &OPTIMISM_ADDRESS),
-        (&BASE_CHAIN_ID, &BASE_ADDRESS),
-        (&ARBITRUM_ONE_CHAIN_ID, &ARBITRUM_ADDRESS),
-    ];
-
-    require!(
-        allowed_origins.contains(&(&src_chain_id, sender_addr)),
-        MessengerError::SenderSmartContractNotAllowed
-    );
-
     msg!("CCM instruction: ReceiveMessage");
 
     let (text, sender) = decode_message(payload)?;
 
     let ix_data = ReceiveMessageIxData {
         msg_hash: *msg_hash,
+        src_chain_id,
         text_len: text.len() as _,
         sender_len: sender.len() as _,
     };
     let params = ReceiveMessageParams {
         text,
         sender,
+        sender_addr,
         src_chain_id,
     };
 
@@ -86,6 +54,11 @@ struct ReceiveMessage<'info> {
     payer: Signer<'info>,
     #[account(mut)]
     messenger: Account<'info, Messenger>,
+    #[account(
+        seeds = [&b"ORIGIN"[..], &ix_data.src_chain_id.to_le_bytes()],
+        bump,
+    )]
+    origin: Account<'info, Origin>,
     #[account(
         init,
         space = 8 + CrossChainMessage::space(ix_data.sender_len as _, ix_data.text_len as _),
@@ -104,21 +77,29 @@ struct ReceiveMessage<'info> {
 #[derive(AnchorSerialize, AnchorDeserialize)]
 struct ReceiveMessageIxData {
     msg_hash: [u8; 32],
+    src_chain_id: u128,
     text_len: u64,
     sender_len: u64,
 }
 
 /// Input for the `receive_message` function.
-struct ReceiveMessageParams {
+struct ReceiveMessageParams<'a> {
     text: String,
     sender: Vec<u8>,
+    sender_addr: &'a [u8],
     src_chain_id: u128,
 }
 
 fn receive_message(ctx: Context<ReceiveMessage>, message: ReceiveMessageParams) -> Result<()> {
     let messenger = &mut ctx.accounts.messenger;
+    let origin = &ctx.accounts.origin;
     let message_account = &mut ctx.accounts.message;
 
+    require!(
+        origin.enabled && origin.address[..] == *message.sender_addr,
+        MessengerError::SenderSmartContractNotAllowed
+    );
+
     if let Some(allowed_senders) = &messenger.allowed_senders {
         require!(
             allowed_senders.contains(&message.sender),
@@ -1,12 +1,14 @@
 pub use self::{
-    execute::*, initialize::*, noop::*, register_extension::*, send_message::*,
-    set_allowed_senders::*, update_admin::*,
+    execute::*, initialize::*, noop::*, register_extension::*, remove_origin::*, send_message::*,
+    set_allowed_senders::*, set_origin::*, update_admin::*,
 };
 
 mod execute;
 mod initialize;
 mod noop;
 mod register_extension;
+mod remove_origin;
 mod send_message;
 mod set_allowed_senders;
+mod set_origin;
 mod update_admin;
@@ -1,15 +1,20 @@
-use crate::{addresses::*, error::*};
+use crate::{error::*, state::*};
 use alloy_sol_types::{
     sol_data::{self, Bytes},
     SolType,
 };
 use anchor_lang::prelude::*;
-use uip_solana_sdk::{chains::*, Commitment, UipEndpoint};
+use uip_solana_sdk::{Commitment, UipEndpoint};
 
 #[derive(Accounts)]
+#[instruction(dest_chain_id: u128)]
 pub struct SendMessage<'info> {
     #[account(mut)]
     sender: Signer<'info>,
+    /// CHECK: it's derived from the destination chain id and deserialized in
+    /// the handler
+    #[account(seeds = [&b"ORIGIN"[..], &dest_chain_id.to_le_bytes()], bump)]
+    origin: AccountInfo<'info>,
     /// CHECK: checked in the CPI
     endpoint_config: AccountInfo<'info>,
     /// CHECK: checked in the CPI
@@ -22,103 +27,46 @@ pub struct SendMessage<'info> {
     uip_program: Program<'info, UipEndpoint>,
 }
 
-/// Different destination chains for sending messages.
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub enum Destination {
-    SolanaMainnet,
-    SolanaDevnet,
-    EthereumSepolia,
-    PolygonAmoy,
-    MantleSepolia,
-    Teib,
-    BaseSepolia,
-    SonicBlazeTestnet,
-    AvalancheFuji,
-    Ethereum,
-    Sonic,
-    Avalanche,
-    Eib,
-    Polygon,
-    MantaPacific,
-    Abstract,
-    Berachain,
-    Mantle,
-    Bsc,
-    Immutable,
-    Optimism,
-    Base,
-    Arbitrum,
+/// Commitment the source transaction must reach before the message is
+/// processed, corresponds to EVM `blockFinalizationOption`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProposalCommitment {
+    /// Corresponds to the EVM Fast finalization option.
+    Confirmed,
+    /// Corresponds to the EVM Standard finalization option.
+    Finalized,
+}
+
+impl From<ProposalCommitment> for Commitment {
+    fn from(commitment: ProposalCommitment) -> Self {
+        match commitment {
+            ProposalCommitment::Confirmed => Commitment::Confirmed,
+            ProposalCommitment::Finalized => Commitment::Finalized,
+        }
+    }
 }
 
 pub fn send_message(
     ctx: Context<SendMessage>,
-    destination: Destination,
+    _dest_chain_id: u128,
     uip_fee: u64,
     custom_gas_limit: u128,
     text: String,
+    commitment: ProposalCommitment,
 ) -> Result<()> {
     let text = sol_data::String::abi_encode(&text);
     let payload = <(Bytes, Bytes)>::abi_encode_params(&(text, ctx.accounts.sender.key()));
 
-    match destination {
-        Destination::SolanaMainnet
-        | Destination::Ethereum
-        | Destination::Sonic
-        | Destination::Avalanche
-        | Destination::Eib
-        | Destination::Polygon
-        | Destination::MantaPacific
-        | Destination::Abstract
-        | Destination::Berachain
-        | Destination::Mantle
-        | Destination::Bsc
-        | Destination::Immutable
-        | Destination::Optimism
-        | Destination::Arbitrum
-        | Destination::Base => {
-            #[cfg(not(feature = "mainnet"))]
-            return err!(MessengerError::DestinationSmartContractNotAllowed);
-        }
-        Destination::SolanaDevnet
-        | Destination::EthereumSepolia
-        | Destination::PolygonAmoy
-        | Destination::MantleSepolia
-        | Destination::Teib
-        | Destination::BaseSepolia
-        | Destination::SonicBlazeTestnet
-        | Destination::AvalancheFuji => {
-            #[cfg(feature = "mainnet")]
-            return err!(MessengerError::DestinationSmartContractNotAllowed);
-        }
-    }
-
-    let (dest_chain_id, dest_addr) = match destination {
-        Destination::SolanaMainnet => (SOLANA_MAINNET_CHAIN_ID, crate::ID.to_bytes()),
-        Destination::SolanaDevnet => (SOLANA_DEVNET_CHAIN_ID, crate::ID.to_bytes()),
-        Destination::Ethereum => (ETHEREUM_CHAIN_ID, ETHEREUM_ADDRESS),
-        Destination::EthereumSepolia => (ETHEREUM_SEPOLIA_CHAIN_ID, ETHEREUM_SEPOLIA_ADDRESS),
-        Destination::Polygon => (POLYGON_CHAIN_ID, POLYGON_ADDRESS),
-        Destination::PolygonAmoy => (POLYGON_AMOY_CHAIN_ID, POLYGON_AMOY_ADDRESS),
-        Destination::Mantle => (MANTLE_CHAIN_ID, MANTLE_ADDRESS),
-        Destination::MantleSepolia => (MANTLE_SEPOLIA_CHAIN_ID, MANTLE_SEPOLIA_ADDRESS),
-        Destination::Eib => (EIB_CHAIN_ID, EIB_ADDRESS),
-        Destination::Teib => (TEIB_CHAIN_ID, TEIB_ADDRESS),
-        Destination::Base => (BASE_CHAIN_ID, BASE_ADDRESS),
-        Destination::BaseSepolia => (BASE_SEPOLIA_CHAIN_ID, BASE_SEPOLIA_ADDRESS),
-        Destination::Sonic => (SONIC_MAINNET_CHAIN_ID, SONIC_ADDRESS),
-        Destination::SonicBlazeTestnet => {
-            (SONIC_BLAZE_TESTNET_CHAIN_ID, SONIC_BLAZE_TESTNET_ADDRESS)
-        }
-        Destination::Avalanche => (AVALANCHE_C_CHAIN_CHAIN_ID, AVALANCHE_C_CHAIN_ADDRESS),
-        Destination::AvalancheFuji => (AVALANCHE_FUJI_CHAIN_ID, AVALANCHE_FUJI_ADDRESS),
-        Destination::MantaPacific => (MANTA_PACIFIC_CHAIN_ID, MANTA_PACIFIC_ADDRESS),
-        Destination::Abstract => (ABSTRACT_CHAIN_ID, ABSTRACT_ADDRESS),
-        Destination::Berachain => (BERACHAIN_CHAIN_ID, BERACHAIN_ADDRESS),
-        Destination::Bsc => (BSC_CHAIN_ID, BSC_ADDRESS),
-        Destination::Immutable => (IMMUTABLE_CHAIN_ID, IMMUTABLE_ADDRESS),
-        Destination::Optimism => (OPTIMISM_CHAIN_ID, OPTIMISM_ADDRESS),
-        Destination::Arbitrum => (ARBITRUM_ONE_CHAIN_ID, ARBITRUM_ADDRESS),
-    };
+    let origin = &ctx.accounts.origin;
+    require!(
+        !origin.data_is_empty(),
+        MessengerError::UnknownDestinationChain
+    );
+    let origin = Origin::try_deserialize(&mut &origin.try_borrow_data()?[..])?;
+    require!(
+        origin.enabled,
+        MessengerError::DestinationSmartContractNotAllowed
+    );
 
     UipEndpoint::propose()
         .payer(ctx.accounts.sender.to_account_info())
@@ -129,11 +77,11 @@ pub fn send_message(
         .program_signer_bump(ctx.bumps.program_signer)
         .sender(&crate::ID)
         .total_fee(uip_fee)
-        .dest_chain_id(dest_chain_id)
-        .dest_addr(&dest_addr)
+        .dest_chain_id(origin.chain_id)
+        .dest_addr(&origin.address)
         .payload(&payload)
         .custom_gas_limit(custom_gas_limit)
-        .proposal_commitment(Commitment::Confirmed)
+        .proposal_commitment(commitment.into())
         .call()?;
 
     Ok(())
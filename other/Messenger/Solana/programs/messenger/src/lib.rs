@@ -4,7 +4,6 @@
 use crate::instructions::*;
 use anchor_lang::prelude::*;
 
-mod addresses;
 pub mod error;
 mod instructions;
 pub mod state;
@@ -39,16 +38,26 @@ pub mod messenger {
         instructions::register_extension(ctx, ipfs_cid)
     }
 
-    /// Sends a cross-chain message to a specified destination, paying the
-    /// specified `uip_fee`.
+    /// Sends a cross-chain message to the chain `dest_chain_id`, paying the
+    /// specified `uip_fee`. The destination contract is resolved from the
+    /// origin set for the chain. The message is processed once the
+    /// transaction reaches the `commitment`.
     pub fn send_message(
         ctx: Context<SendMessage>,
-        destination: Destination,
+        dest_chain_id: u128,
         uip_fee: u64,
         custom_gas_limit: u128,
         text: String,
+        commitment: ProposalCommitment,
     ) -> Result<()> {
-        instructions::send_message(ctx, destination, uip_fee, custom_gas_limit, text)
+        instructions::send_message(
+            ctx,
+            dest_chain_id,
+            uip_fee,
+            custom_gas_limit,
+            text,
+            commitment,
+        )
     }
 
     /// Executes an incoming cross-chain message, saving the received message in
@@ -72,6 +81,22 @@ pub mod messenger {
         instructions::set_allowed_senders(ctx, allowed_senders)
     }
 
+    /// Sets the messenger contract `address` on the chain `chain_id` and
+    /// whether sending and receiving messages is `enabled`.
+    pub fn set_origin(
+        ctx: Context<SetOrigin>,
+        chain_id: u128,
+        address: [u8; 32],
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_origin(ctx, chain_id, address, enabled)
+    }
+
+    /// Removes the messenger contract known on the chain `chain_id`.
+    pub fn remove_origin(ctx: Context<RemoveOrigin>, chain_id: u128) -> Result<()> {
+        instructions::remove_origin(ctx, chain_id)
+    }
+
     /// A dirty fix to make anchor add `CrossChainMessage` to IDL. It does't seem
     /// to register it when there are no public instructions that use the account
     /// in their context.
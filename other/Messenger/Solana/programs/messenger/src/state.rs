@@ -59,3 +59,24 @@ impl CrossChainMessage {
             + space_text
     }
 }
+
+/// A known messenger deployment on another chain.
+#[account]
+#[derive(Debug)]
+pub struct Origin {
+    /// Identifier of the remote chain.
+    pub chain_id: u128,
+    /// Address of the messenger contract on the remote chain.
+    pub address: [u8; 32],
+    /// Whether messages can be sent to and received from the remote chain.
+    pub enabled: bool,
+}
+
+impl Origin {
+    pub(crate) fn space() -> usize {
+        let space_chain_id = 16;
+        let space_address = 32;
+        let space_enabled = 1;
+        space_chain_id + space_address + space_enabled
+    }
+}
@@ -1,6 +1,31 @@
 import yargs from "yargs";
 import * as anchor from "@coral-xyz/anchor";
-import { Destination, sendMessage } from "@lincot/uip-solana-messenger-example";
+import {
+  ABSTRACT_CHAIN_ID,
+  ARBITRUM_ONE_CHAIN_ID,
+  AVALANCHE_C_CHAIN_CHAIN_ID,
+  AVALANCHE_FUJI_CHAIN_ID,
+  BASE_CHAIN_ID,
+  BASE_SEPOLIA_CHAIN_ID,
+  BERACHAIN_CHAIN_ID,
+  BSC_CHAIN_ID,
+  EIB_CHAIN_ID,
+  ETHEREUM_CHAIN_ID,
+  ETHEREUM_SEPOLIA_CHAIN_ID,
+  IMMUTABLE_CHAIN_ID,
+  MANTA_PACIFIC_CHAIN_ID,
+  MANTLE_CHAIN_ID,
+  MANTLE_SEPOLIA_CHAIN_ID,
+  OPTIMISM_CHAIN_ID,
+  POLYGON_AMOY_CHAIN_ID,
+  POLYGON_CHAIN_ID,
+  SOLANA_DEVNET_CHAIN_ID,
+  SOLANA_MAINNET_CHAIN_ID,
+  SONIC_BLAZE_TESTNET_CHAIN_ID,
+  SONIC_MAINNET_CHAIN_ID,
+  TEIB_CHAIN_ID,
+  sendMessage,
+} from "@lincot/uip-solana-messenger-example";
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
 import { BN } from "bn.js";
 import { sendAndConfirmVersionedTx } from "../helpers/utils";
@@ -34,6 +59,12 @@ async function main(): Promise<void> {
       demandOption: true,
       description: "The text to send",
     })
+    .option("finalized", {
+      type: "boolean",
+      default: false,
+      description:
+        "Wait for the transaction to be finalized before processing the message",
+    })
     .argv;
 
   const times = Number(argv["times"]);
@@ -41,85 +72,86 @@ async function main(): Promise<void> {
   const uipFee = new BN(argv["fee"]);
   const customGasLimit = new BN(argv["custom-gas-limit"]);
   const baseText = argv["text"].join(" ");
+  const commitment = argv["finalized"] ? { finalized: {} } : { confirmed: {} };
 
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
   const payer = (provider.wallet as NodeWallet).payer;
 
-  let destination: Destination | bigint;
+  let destChainId: bigint;
   switch (dstChain) {
     case "solana-mainnet":
-      destination = { solanaMainnet: {} };
+      destChainId = SOLANA_MAINNET_CHAIN_ID;
       break;
     case "solana-devnet":
-      destination = { solanaDevnet: {} };
+      destChainId = SOLANA_DEVNET_CHAIN_ID;
       break;
     case "ethereum":
-      destination = { ethereum: {} };
+      destChainId = ETHEREUM_CHAIN_ID;
       break;
     case "ethereum-sepolia":
-      destination = { ethereumSepolia: {} };
+      destChainId = ETHEREUM_SEPOLIA_CHAIN_ID;
       break;
     case "polygon":
-      destination = { polygon: {} };
+      destChainId = POLYGON_CHAIN_ID;
       break;
     case "polygon-amoy":
-      destination = { polygonAmoy: {} };
+      destChainId = POLYGON_AMOY_CHAIN_ID;
       break;
     case "mantle":
-      destination = { mantle: {} };
+      destChainId = MANTLE_CHAIN_ID;
       break;
     case "mantle-sepolia":
-      destination = { mantleSepolia: {} };
+      destChainId = MANTLE_SEPOLIA_CHAIN_ID;
       break;
     case "eib":
-      destination = { eib: {} };
+      destChainId = EIB_CHAIN_ID;
       break;
     case "teib":
-      destination = { teib: {} };
+      destChainId = TEIB_CHAIN_ID;
       break;
     case "base":
-      destination = { base: {} };
+      destChainId = BASE_CHAIN_ID;
       break;
     case "base-sepolia":
-      destination = { baseSepolia: {} };
+      destChainId = BASE_SEPOLIA_CHAIN_ID;
       break;
     case "sonic":
-      destination = { sonic: {} };
+      destChainId = SONIC_MAINNET_CHAIN_ID;
       break;
     case "sonic-blaze-testnet":
-      destination = { sonicBlazeTestnet: {} };
+      destChainId = SONIC_BLAZE_TESTNET_CHAIN_ID;
       break;
     case "avalanche":
-      destination = { avalanche: {} };
+      destChainId = AVALANCHE_C_CHAIN_CHAIN_ID;
       break;
     case "avalanche-fuji":
-      destination = { avalancheFuji: {} };
+      destChainId = AVALANCHE_FUJI_CHAIN_ID;
       break;
     case "manta-pacific":
-      destination = { mantaPacific: {} };
+      destChainId = MANTA_PACIFIC_CHAIN_ID;
       break;
     case "abstract":
-      destination = { abstract: {} };
+      destChainId = ABSTRACT_CHAIN_ID;
       break;
     case "berachain":
-      destination = { berachain: {} };
+      destChainId = BERACHAIN_CHAIN_ID;
       break;
     case "bsc":
-      destination = { bsc: {} };
+      destChainId = BSC_CHAIN_ID;
       break;
     case "immutable":
-      destination = { immutable: {} };
+      destChainId = IMMUTABLE_CHAIN_ID;
       break;
     case "optimism":
-      destination = { optimism: {} };
+      destChainId = OPTIMISM_CHAIN_ID;
       break;
     case "arbitrum":
-      destination = { arbitrum: {} };
+      destChainId = ARBITRUM_ONE_CHAIN_ID;
       break;
     default:
       try {
-        destination = BigInt(dstChain);
+        destChainId = BigInt(dstChain);
       } catch {
         throw new Error(
           "Invalid destination, must be chain ID or one of solana-mainnet, solana-devnet, ethereum-sepolia, polygon, polygon-amoy, mantle, mantle-sepolia, eib, teib, base, base-sepolia, sonic-blaze-testnet, avalanche, avalanche-fuji, ethereum, sonic, manta-pacific, abstract, berachain, bsc, immutable, optimism, arbitrum",
@@ -139,9 +171,10 @@ async function main(): Promise<void> {
           connection: provider.connection,
           uipFee,
           customGasLimit,
-          destination,
+          destChainId,
           sender: payer.publicKey,
           text,
+          commitment,
         });
         const latestBlockhash = await provider.connection.getLatestBlockhash()
           .then((b) => b.blockhash);
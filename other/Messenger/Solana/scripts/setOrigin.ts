@@ -0,0 +1,64 @@
+import yargs from "yargs";
+import * as anchor from "@coral-xyz/anchor";
+import { setOrigin } from "@lincot/uip-solana-messenger-example";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { PublicKey } from "@solana/web3.js";
+import { toTransaction } from "@lincot/uip-solana-sdk";
+import { hexToBytes, sendAndConfirmVersionedTx } from "../helpers/utils";
+
+async function main(): Promise<void> {
+  const argv = yargs(process.argv.slice(2))
+    .option("chain-id", {
+      type: "string",
+      demandOption: true,
+      description: "Identifier of the remote chain",
+    })
+    .option("address", {
+      type: "string",
+      demandOption: true,
+      description:
+        "Address of the remote messenger, either 0x-prefixed EVM address or Solana public key",
+    })
+    .option("disabled", {
+      type: "boolean",
+      default: false,
+      description: "Register the origin without allowing messages",
+    })
+    .argv;
+
+  const chainId = BigInt(argv["chain-id"]);
+  let address: Buffer;
+  if (argv["address"].startsWith("0x")) {
+    address = Buffer.alloc(32);
+    hexToBytes(argv["address"]).copy(address, 12);
+  } else {
+    address = new PublicKey(argv["address"]).toBuffer();
+  }
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as NodeWallet).payer;
+
+  const ix = await setOrigin({
+    payer: payer.publicKey,
+    admin: payer.publicKey,
+    chainId,
+    address,
+    enabled: !argv["disabled"],
+  });
+
+  const transactionSignature = await sendAndConfirmVersionedTx(
+    provider.connection,
+    toTransaction(
+      [ix],
+      await provider.connection.getLatestBlockhash().then((b) => b.blockhash),
+      payer,
+    ),
+    [payer],
+    payer.publicKey,
+  );
+
+  console.log("Transaction signature:", transactionSignature);
+}
+
+main();
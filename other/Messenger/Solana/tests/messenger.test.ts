@@ -14,9 +14,9 @@ import {
   unloadMessage,
 } from "@lincot/uip-solana-sdk";
 import {
-  Destination,
   fetchMessenger,
   findMessage as findMessengerMessage,
+  findOrigin,
   getMessagesBySender,
   initialize as initializeMessenger,
   MAX_TEXT_LEN_ONE_TX,
@@ -26,6 +26,8 @@ import {
   sendMessage,
   sendMessageOneTx,
   setAllowedSenders,
+  setOrigin,
+  TEIB_CHAIN_ID,
   updateAdmin,
 } from "@lincot/uip-solana-messenger-example";
 import {
@@ -137,6 +139,19 @@ describe("messenger", () => {
     expect(messenger.allowedSenders).toEqual(null);
   });
 
+  test("setOrigin", async () => {
+    await sendIx(
+      await setOrigin({
+        payer: payer.publicKey,
+        admin: admin.publicKey,
+        chainId: solanaChainId,
+        address: PROGRAM_ID.toBuffer(),
+        enabled: true,
+      }),
+      [payer, admin],
+    );
+  });
+
   test("registerExtension", async () => {
     const ipfsCid =
       "bafkreia2gxlqwpkvtx2bzetzuk4swfqf54j5g2tufasexoiqrrud3xakgu";
@@ -168,9 +183,7 @@ describe("messenger", () => {
   const text = "Hello, world!";
   let selector: number[];
   let payload: Buffer;
-  const destination: Destination = connection.rpcEndpoint.includes("mainnet")
-    ? { solanaMainnet: {} }
-    : { solanaDevnet: {} };
+  const destChainId = solanaChainId;
 
   test("sendMessage", async () => {
     const eventPromise: Promise<void> = new Promise((resolve, reject) => {
@@ -193,7 +206,7 @@ describe("messenger", () => {
     const transactionSignature = await sendIx(
       await sendMessageOneTx({
         connection,
-        destination: solanaChainId,
+        destChainId,
         uipFee,
         customGasLimit,
         text,
@@ -212,7 +225,7 @@ describe("messenger", () => {
       await sendIx(
         await sendMessageOneTx({
           connection,
-          destination,
+          destChainId,
           uipFee,
           customGasLimit,
           text,
@@ -227,6 +240,22 @@ describe("messenger", () => {
     );
   });
 
+  test("sendMessage to unknown chain", async () => {
+    await expect(
+      sendIx(
+        await sendMessageOneTx({
+          connection,
+          destChainId: TEIB_CHAIN_ID,
+          uipFee,
+          customGasLimit,
+          text,
+          sender: sender.publicKey,
+        }),
+        [sender],
+      ),
+    ).rejects.toThrow("UnknownDestinationChain");
+  });
+
   test("sendMessage (big)", async () => {
     const bigText = "a".repeat(5000);
     const eventPromise: Promise<void> = new Promise((resolve, reject) => {
@@ -254,7 +283,7 @@ describe("messenger", () => {
 
     const { preInstructions, instruction } = await sendMessage({
       connection,
-      destination,
+      destChainId,
       uipFee,
       customGasLimit,
       text: bigText,
@@ -298,7 +327,7 @@ describe("messenger", () => {
         connection,
         uipFee,
         customGasLimit,
-        destination,
+        destChainId,
         text,
         sender: sender.publicKey,
       }),
@@ -335,6 +364,11 @@ describe("messenger", () => {
 
     const accounts = [
       { pubkey: MESSENGER, isSigner: false, isWritable: true },
+      {
+        pubkey: findOrigin(solanaChainId),
+        isSigner: false,
+        isWritable: false,
+      },
       {
         pubkey: findMessengerMessage(msgHashFull(msgData, solanaChainId)),
         isSigner: false,
@@ -345,6 +379,11 @@ describe("messenger", () => {
 
     const accountsSimulation = [
       { pubkey: MESSENGER, isSigner: false, isWritable: true },
+      {
+        pubkey: findOrigin(solanaChainId),
+        isSigner: false,
+        isWritable: false,
+      },
       {
         pubkey: findMessengerMessage(Array.from({ length: 32 }, () => 0)),
         isSigner: false,
@@ -443,7 +482,7 @@ describe("messenger", () => {
         connection,
         uipFee,
         customGasLimit,
-        destination,
+        destChainId,
         text,
         sender: sender.publicKey,
       }),
@@ -496,6 +535,11 @@ describe("messenger", () => {
           executor: executor.publicKey,
           accounts: [
             { pubkey: MESSENGER, isSigner: false, isWritable: true },
+            {
+              pubkey: findOrigin(solanaChainId),
+              isSigner: false,
+              isWritable: false,
+            },
             {
               pubkey: findMessengerMessage(msgHashFull(msgData, solanaChainId)),
               isSigner: false,
@@ -541,6 +585,11 @@ describe("messenger", () => {
         executor: executor.publicKey,
         accounts: [
           { pubkey: MESSENGER, isSigner: false, isWritable: true },
+          {
+            pubkey: findOrigin(solanaChainId),
+            isSigner: false,
+            isWritable: false,
+          },
           {
             pubkey: findMessengerMessage(msgHashFull(msgData, solanaChainId)),
             isSigner: false,
@@ -22,7 +22,6 @@ import {
   fetchAccount,
   getMockProvider,
   InstructionWithCu,
-  toBigint,
   toBN,
   toTransaction,
 } from "./utils";
@@ -38,9 +37,9 @@ export const PROGRAM_ID = new PublicKey(messengerIdl.address);
 
 export type Message = IdlTypes<Messenger>["crossChainMessage"];
 export type MessengerAccount = IdlTypes<Messenger>["messenger"];
-export type Destination = IdlTypes<Messenger>["destination"];
+export type ProposalCommitment = IdlTypes<Messenger>["proposalCommitment"];
 
-export const MAX_TEXT_LEN_ONE_TX = 817;
+export const MAX_TEXT_LEN_ONE_TX = 769;
 
 export const MESSENGER = PublicKey.findProgramAddressSync(
   [Buffer.from("MESSENGER")],
@@ -56,6 +55,15 @@ export const findMessage = (msgHash: number[] | Buffer) =>
     PROGRAM_ID,
   )[0];
 
+export const findOrigin = (chainId: BN | bigint) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("ORIGIN"),
+      toBN(chainId).toArrayLike(Buffer, "le", 16),
+    ],
+    PROGRAM_ID,
+  )[0];
+
 export type InitializeParams = {
   payer: PublicKey;
   admin: PublicKey;
@@ -144,13 +152,63 @@ export async function setAllowedSenders(
   return { instruction, cuLimit: 30_000 };
 }
 
+export type SetOriginParams = {
+  payer: PublicKey;
+  admin: PublicKey;
+  chainId: BN | bigint;
+  address: Buffer;
+  enabled: boolean;
+};
+
+export async function setOrigin(
+  {
+    payer,
+    admin,
+    chainId,
+    address,
+    enabled,
+  }: SetOriginParams,
+): Promise<InstructionWithCu> {
+  const instruction = await getProgram().methods
+    .setOrigin(toBN(chainId), Array.from(address), enabled)
+    .accountsStrict({
+      messenger: MESSENGER,
+      origin: findOrigin(chainId),
+      admin,
+      payer,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+  return { instruction, cuLimit: 30_000 };
+}
+
+export type RemoveOriginParams = {
+  admin: PublicKey;
+  chainId: BN | bigint;
+};
+
+export async function removeOrigin(
+  { admin, chainId }: RemoveOriginParams,
+): Promise<InstructionWithCu> {
+  const instruction = await getProgram().methods
+    .removeOrigin(toBN(chainId))
+    .accountsStrict({
+      messenger: MESSENGER,
+      origin: findOrigin(chainId),
+      admin,
+    })
+    .instruction();
+  return { instruction, cuLimit: 20_000 };
+}
+
 export type SendMessageParams = {
   connection: Connection;
-  destination: Destination | bigint | BN;
+  destChainId: bigint | BN;
   uipFee: BN | bigint;
   customGasLimit: BN | bigint;
   text: string;
   sender: PublicKey;
+  commitment?: ProposalCommitment;
 };
 
 export async function sendMessage(
@@ -170,22 +228,25 @@ export async function sendMessageOneTx(
     connection,
     uipFee,
     customGasLimit,
-    destination,
+    destChainId,
     text,
     sender,
+    commitment,
   }: SendMessageParams,
 ): Promise<InstructionWithCu> {
   const instruction = await getProgram().methods
     .sendMessage(
-      toDestination(destination),
+      toBN(destChainId),
       toBN(uipFee),
       toBN(customGasLimit),
       text,
+      commitment ?? { confirmed: {} },
     )
     .accounts({
       endpointConfig: ENDPOINT_CONFIG,
       utsConnector: await fetchUtsConnector(connection),
       sender,
+      origin: findOrigin(destChainId),
     })
     .instruction();
   return { instruction, cuLimit: 50_000 };
@@ -196,9 +257,10 @@ async function sendMessageManyTx(
     connection,
     uipFee,
     customGasLimit,
-    destination,
+    destChainId,
     text,
     sender,
+    commitment,
   }: SendMessageParams,
 ): Promise<
   { preInstructions: InstructionWithCu[]; instruction: InstructionWithCu }
@@ -206,8 +268,9 @@ async function sendMessageManyTx(
   const data = encodeSendMessageParams({
     uipFee,
     customGasLimit,
-    destination,
+    destChainId,
     text,
+    commitment: commitment ?? { confirmed: {} },
   });
   const chunkHolderId = Math.floor(Math.random() * (1 << 19));
   const preInstructions = await loadByChunks({
@@ -222,6 +285,11 @@ async function sendMessageManyTx(
     chunkHolderId,
     accounts: [
       { pubkey: sender, isSigner: true, isWritable: true },
+      {
+        pubkey: findOrigin(destChainId),
+        isSigner: false,
+        isWritable: false,
+      },
       { pubkey: ENDPOINT_CONFIG, isSigner: false, isWritable: false },
       {
         pubkey: await fetchUtsConnector(connection),
@@ -334,73 +402,28 @@ export async function getMessagesBySender(
 const SEND_MESSAGE_DISCRIMINATOR = [57, 40, 34, 178, 189, 10, 65, 26];
 
 type EncodeSendMessgeParams = {
-  destination: Destination | bigint | BN;
+  destChainId: bigint | BN;
   uipFee: BN | bigint;
   customGasLimit: BN | bigint;
   text: string;
+  commitment: ProposalCommitment;
 };
 
 function encodeSendMessageParams({
-  destination: _destination,
+  destChainId,
   uipFee,
   customGasLimit,
   text,
+  commitment,
 }: EncodeSendMessgeParams): Buffer {
-  const destination = toDestination(_destination);
-
-  const res = Buffer.alloc(8 + 1 + 8 + 16 + 4 + text.length);
+  const res = Buffer.alloc(8 + 16 + 8 + 16 + 4 + text.length + 1);
   let offset = 0;
 
   res.set(SEND_MESSAGE_DISCRIMINATOR, offset);
   offset += 8;
 
-  let destinationNum: number;
-  if (destination.solanaMainnet) {
-    destinationNum = 0;
-  } else if (destination.solanaDevnet) {
-    destinationNum = 1;
-  } else if (destination.ethereumSepolia) {
-    destinationNum = 2;
-  } else if (destination.polygonAmoy) {
-    destinationNum = 3;
-  } else if (destination.mantleSepolia) {
-    destinationNum = 4;
-  } else if (destination.teib) {
-    destinationNum = 5;
-  } else if (destination.baseSepolia) {
-    destinationNum = 6;
-  } else if (destination.sonicBlazeTestnet) {
-    destinationNum = 7;
-  } else if (destination.avalancheFuji) {
-    destinationNum = 8;
-  } else if (destination.ethereum) {
-    destinationNum = 9;
-  } else if (destination.sonic) {
-    destinationNum = 10;
-  } else if (destination.avalanche) {
-    destinationNum = 11;
-  } else if (destination.eib) {
-    destinationNum = 12;
-  } else if (destination.polygon) {
-    destinationNum = 13;
-  } else if (destination.mantaPacific) {
-    destinationNum = 14;
-  } else if (destination.abstract) {
-    destinationNum = 15;
-  } else if (destination.berachain) {
-    destinationNum = 16;
-  } else if (destination.mantle) {
-    destinationNum = 17;
-  } else if (destination.bsc) {
-    destinationNum = 18;
-  } else if (destination.immutable) {
-    destinationNum = 19;
-  } else {
-    throw new Error("invalid destination");
-  }
-
-  res.set([destinationNum], offset);
-  offset += 1;
+  res.set(toBN(destChainId).toArrayLike(Buffer, "le", 16), offset);
+  offset += 16;
 
   res.set(toBN(uipFee).toArrayLike(Buffer, "le", 8), offset);
   offset += 8;
@@ -413,92 +436,32 @@ function encodeSendMessageParams({
   res.set(new TextEncoder().encode(text), offset);
   offset += text.length;
 
+  res.writeUint8("finalized" in commitment ? 1 : 0, offset);
+  offset += 1;
+
   return res;
 }
 
-const SOLANA_MAINNET_CHAIN_ID = 11100000000000000501n;
-const SOLANA_DEVNET_CHAIN_ID = 100000000000000000000n;
-const ETHEREUM_CHAIN_ID = 1n;
-const ETHEREUM_SEPOLIA_CHAIN_ID = 11155111n;
-const POLYGON_CHAIN_ID = 137n;
-const POLYGON_AMOY_CHAIN_ID = 80002n;
-const MANTLE_CHAIN_ID = 5000n;
-const MANTLE_SEPOLIA_CHAIN_ID = 5003n;
-const EIB_CHAIN_ID = 33033n;
-const TEIB_CHAIN_ID = 33133n;
-const BASE_CHAIN_ID = 8453n;
-const BASE_SEPOLIA_CHAIN_ID = 84532n;
-const SONIC_MAINNET_CHAIN_ID = 146n;
-const SONIC_BLAZE_TESTNET_CHAIN_ID = 57054n;
-const AVALANCHE_C_CHAIN_CHAIN_ID = 43114n;
-const AVALANCHE_FUJI_CHAIN_ID = 43113n;
-const MANTA_PACIFIC_CHAIN_ID = 169n;
-const ABSTRACT_CHAIN_ID = 2741n;
-const BSC_CHAIN_ID = 56n;
-const BERACHAIN_CHAIN_ID = 80094n;
-const IMMUTABLE_CHAIN_ID = 13371n;
-const OPTIMISM_CHAIN_ID = 10n;
-const ARBITRUM_ONE_CHAIN_ID = 42161n;
-
-const toDestination = (
-  _destination: Destination | bigint | BN,
-): Destination => {
-  let destination: Destination | bigint;
-  if (BN.isBN(_destination)) {
-    destination = toBigint(_destination);
-  } else if (typeof _destination != "bigint") {
-    return _destination;
-  } else {
-    destination = _destination;
-  }
-
-  if (destination == SOLANA_MAINNET_CHAIN_ID) {
-    return { solanaMainnet: {} };
-  } else if (destination == SOLANA_DEVNET_CHAIN_ID) {
-    return { solanaDevnet: {} };
-  } else if (destination == ETHEREUM_CHAIN_ID) {
-    return { ethereum: {} };
-  } else if (destination == ETHEREUM_SEPOLIA_CHAIN_ID) {
-    return { ethereumSepolia: {} };
-  } else if (destination == POLYGON_CHAIN_ID) {
-    return { polygon: {} };
-  } else if (destination == POLYGON_AMOY_CHAIN_ID) {
-    return { polygonAmoy: {} };
-  } else if (destination == MANTLE_CHAIN_ID) {
-    return { mantle: {} };
-  } else if (destination == MANTLE_SEPOLIA_CHAIN_ID) {
-    return { mantleSepolia: {} };
-  } else if (destination == EIB_CHAIN_ID) {
-    return { eib: {} };
-  } else if (destination == TEIB_CHAIN_ID) {
-    return { teib: {} };
-  } else if (destination == BASE_SEPOLIA_CHAIN_ID) {
-    return { baseSepolia: {} };
-  } else if (destination == SONIC_MAINNET_CHAIN_ID) {
-    return { sonic: {} };
-  } else if (destination == SONIC_BLAZE_TESTNET_CHAIN_ID) {
-    return { sonicBlazeTestnet: {} };
-  } else if (destination == AVALANCHE_C_CHAIN_CHAIN_ID) {
-    return { avalanche: {} };
-  } else if (destination == AVALANCHE_FUJI_CHAIN_ID) {
-    return { avalancheFuji: {} };
-  } else if (destination == MANTA_PACIFIC_CHAIN_ID) {
-    return { mantaPacific: {} };
-  } else if (destination == ABSTRACT_CHAIN_ID) {
-    return { abstract: {} };
-  } else if (destination == BSC_CHAIN_ID) {
-    return { bsc: {} };
-  } else if (destination == BERACHAIN_CHAIN_ID) {
-    return { berachain: {} };
-  } else if (destination == IMMUTABLE_CHAIN_ID) {
-    return { immutable: {} };
-  } else if (destination == OPTIMISM_CHAIN_ID) {
-    return { optimism: {} };
-  } else if (destination == BASE_CHAIN_ID) {
-    return { base: {} };
-  } else if (destination == ARBITRUM_ONE_CHAIN_ID) {
-    return { arbitrum: {} };
-  } else {
-    throw new Error("Illegal destination chain ID");
-  }
-};
+export const SOLANA_MAINNET_CHAIN_ID = 11100000000000000501n;
+export const SOLANA_DEVNET_CHAIN_ID = 100000000000000000000n;
+export const ETHEREUM_CHAIN_ID = 1n;
+export const ETHEREUM_SEPOLIA_CHAIN_ID = 11155111n;
+export const POLYGON_CHAIN_ID = 137n;
+export const POLYGON_AMOY_CHAIN_ID = 80002n;
+export const MANTLE_CHAIN_ID = 5000n;
+export const MANTLE_SEPOLIA_CHAIN_ID = 5003n;
+export const EIB_CHAIN_ID = 33033n;
+export const TEIB_CHAIN_ID = 33133n;
+export const BASE_CHAIN_ID = 8453n;
+export const BASE_SEPOLIA_CHAIN_ID = 84532n;
+export const SONIC_MAINNET_CHAIN_ID = 146n;
+export const SONIC_BLAZE_TESTNET_CHAIN_ID = 57054n;
+export const AVALANCHE_C_CHAIN_CHAIN_ID = 43114n;
+export const AVALANCHE_FUJI_CHAIN_ID = 43113n;
+export const MANTA_PACIFIC_CHAIN_ID = 169n;
+export const ABSTRACT_CHAIN_ID = 2741n;
+export const BSC_CHAIN_ID = 56n;
+export const BERACHAIN_CHAIN_ID = 80094n;
+export const IMMUTABLE_CHAIN_ID = 13371n;
+export const OPTIMISM_CHAIN_ID = 10n;
+export const ARBITRUM_ONE_CHAIN_ID = 42161n;